@@ -0,0 +1,297 @@
+//! Device tables generated from the device registry
+//!
+//! Reads `registry.toml` (or the file named by `DEVICE_REGISTRY`, as in the
+//! tower) and writes one const table per device kind to
+//! `$OUT_DIR/devices.rs`, which `src/config.rs` includes. The registry is the
+//! only place group addresses are maintained; a device lacking an address
+//! its table needs fails the build.
+
+use records::address::AddressStyle;
+use records::{Device, DeviceKind, GroupAddress, Registry, Role};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// One device table of `config.rs`
+struct Table {
+    /// Const name, e.g. `SWITCHES`
+    name: &'static str,
+    /// Doc comment of the const
+    doc: &'static str,
+    /// Device group feature the table belongs to
+    feature: Option<&'static str>,
+    /// Element type, defined in `config.rs`
+    ty: &'static str,
+    /// Kind of the devices listed
+    kind: DeviceKind,
+    /// Only list devices with this role (texts split into displays and panels)
+    only_with: Option<Role>,
+    /// Group address fields and the role each is read from
+    fields: &'static [(&'static str, Role)],
+}
+
+const SENSOR: &[(&str, Role)] = &[("state", Role::State)];
+const ACTUATOR: &[(&str, Role)] = &[("state", Role::State), ("control", Role::Control)];
+
+const TABLES: &[Table] = &[
+    Table {
+        name: "SWITCHES",
+        doc: "Switch actuators (DPT 1.001)",
+        feature: None,
+        ty: "Actuator",
+        kind: DeviceKind::Switch,
+        only_with: None,
+        fields: ACTUATOR,
+    },
+    Table {
+        name: "TEMPERATURE_SENSORS",
+        doc: "Temperature sensors (DPT 9.001)",
+        feature: None,
+        ty: "Sensor",
+        kind: DeviceKind::Temperature,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "DIMMERS",
+        doc: "Dimmers",
+        feature: None,
+        ty: "Dimmer",
+        kind: DeviceKind::Dimmer,
+        only_with: None,
+        fields: &[
+            ("state", Role::State),
+            ("value", Role::Value),
+            ("step", Role::Step),
+        ],
+    },
+    Table {
+        name: "BLINDS",
+        doc: "Blinds, each merged from its three status objects",
+        feature: None,
+        ty: "Blind",
+        kind: DeviceKind::Blind,
+        only_with: None,
+        fields: &[
+            ("up_down", Role::Move),
+            ("step", Role::Step),
+            ("position", Role::Position),
+            ("slat_angle", Role::SlatAngle),
+            ("position_state", Role::PositionState),
+            ("slat_angle_state", Role::SlatAngleState),
+            ("moving", Role::Moving),
+        ],
+    },
+    Table {
+        name: "ILLUMINANCE_SENSORS",
+        doc: "Illuminance sensors (DPT 9.004)",
+        feature: Some("sensors"),
+        ty: "Sensor",
+        kind: DeviceKind::Illuminance,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "WIND_SENSORS",
+        doc: "Wind speed sensors (DPT 9.005)",
+        feature: Some("sensors"),
+        ty: "Sensor",
+        kind: DeviceKind::WindSpeed,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "HUMIDITY_SENSORS",
+        doc: "Humidity sensors (DPT 9.007)",
+        feature: Some("sensors"),
+        ty: "Sensor",
+        kind: DeviceKind::Humidity,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "CO2_SENSORS",
+        doc: "CO2 sensors (DPT 9.008)",
+        feature: Some("sensors"),
+        ty: "Sensor",
+        kind: DeviceKind::Co2,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "ENERGY_METERS",
+        doc: "Active energy counters (DPT 13.010)",
+        feature: Some("energy"),
+        ty: "Sensor",
+        kind: DeviceKind::EnergyCounter,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "POWER_METERS",
+        doc: "Active power meters (DPT 14.056)",
+        feature: Some("energy"),
+        ty: "Sensor",
+        kind: DeviceKind::Power,
+        only_with: None,
+        fields: SENSOR,
+    },
+    Table {
+        name: "COLOR_LIGHTS",
+        doc: "RGB and RGBW LED strips",
+        feature: Some("color"),
+        ty: "ColorLight",
+        kind: DeviceKind::ColorLight,
+        only_with: None,
+        fields: ACTUATOR,
+    },
+    Table {
+        name: "SCENES",
+        doc: "Scene objects, observed (DPT 17.001 / 18.001) and activated (DPT 18.001)",
+        feature: Some("scene"),
+        ty: "Actuator",
+        kind: DeviceKind::Scene,
+        only_with: None,
+        fields: ACTUATOR,
+    },
+    Table {
+        name: "CLOCKS",
+        doc: "Master clocks",
+        feature: Some("clock"),
+        ty: "Clock",
+        kind: DeviceKind::Clock,
+        only_with: None,
+        fields: &[
+            ("time", Role::Time),
+            ("date", Role::Date),
+            ("datetime", Role::DateTime),
+        ],
+    },
+    Table {
+        name: "TEXT_DISPLAYS",
+        doc: "Text displays (DPT 16.001)",
+        feature: Some("text"),
+        ty: "Display",
+        kind: DeviceKind::Text,
+        only_with: Some(Role::Control),
+        fields: &[("control", Role::Control)],
+    },
+    Table {
+        name: "TEXT_PANELS",
+        doc: "Status texts, read as ASCII (DPT 16.000)",
+        feature: Some("text"),
+        ty: "Sensor",
+        kind: DeviceKind::Text,
+        only_with: Some(Role::State),
+        fields: SENSOR,
+    },
+    Table {
+        name: "THERMOSTATS",
+        doc: "Thermostat HVAC modes (DPT 20.102)",
+        feature: Some("hvac"),
+        ty: "Actuator",
+        kind: DeviceKind::HvacMode,
+        only_with: None,
+        fields: ACTUATOR,
+    },
+    Table {
+        name: "SETPOINTS",
+        doc: "Thermostat setpoints (DPT 9.001)",
+        feature: Some("hvac"),
+        ty: "Actuator",
+        kind: DeviceKind::Setpoint,
+        only_with: None,
+        fields: ACTUATOR,
+    },
+];
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=DEVICE_REGISTRY");
+    let path = std::env::var("DEVICE_REGISTRY")
+        .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/../registry.toml").to_string());
+    println!("cargo:rerun-if-changed={}", path);
+
+    let registry = Registry::load(&path).unwrap_or_else(|e| panic!("{}", e));
+    let mut code = format!("// Generated by build.rs from {}\n", path);
+    for table in TABLES {
+        write_table(&mut code, &registry, table);
+    }
+
+    let out = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("devices.rs");
+    std::fs::write(out, code).unwrap();
+}
+
+/// Write the const of one table, with every device of its kind
+fn write_table(code: &mut String, registry: &Registry, table: &Table) {
+    let devices: Vec<&Device> = registry
+        .devices(table.kind)
+        .filter(|device| {
+            table
+                .only_with
+                .is_none_or(|role| device.address(role).is_some())
+        })
+        .collect();
+
+    writeln!(code, "\n/// {}", table.doc).unwrap();
+    if let Some(feature) = table.feature {
+        writeln!(code, "#[cfg(feature = {:?})]", feature).unwrap();
+    }
+    writeln!(
+        code,
+        "pub const {}: [{}; {}] = [",
+        table.name,
+        table.ty,
+        devices.len()
+    )
+    .unwrap();
+    for device in devices {
+        writeln!(code, "    {} {{", table.ty).unwrap();
+        writeln!(
+            code,
+            "        device: DeviceTopic::new({:?}, {:?}),",
+            device.room, device.name
+        )
+        .unwrap();
+        for &(field, role) in table.fields {
+            let address = device.address(role).unwrap_or_else(|| {
+                panic!(
+                    "{} ({}) has no {} group address, which the gateway's {} table needs",
+                    device.id(),
+                    device.kind,
+                    role,
+                    table.name
+                )
+            });
+            writeln!(code, "        {}: {},", field, constructor(address)).unwrap();
+        }
+        if table.kind == DeviceKind::ColorLight {
+            writeln!(code, "        white: {},", is_rgbw(device)).unwrap();
+        }
+        writeln!(code, "    }},").unwrap();
+    }
+    writeln!(code, "];").unwrap();
+}
+
+/// Const expression for a group address, in the notation of the registry
+fn constructor(address: GroupAddress) -> String {
+    match address.style() {
+        AddressStyle::ThreeLevel => format!(
+            "GroupAddress::new({}, {}, {})",
+            address.main(),
+            address.middle(),
+            address.sub()
+        ),
+        AddressStyle::TwoLevel => format!(
+            "GroupAddress::from_raw({}).with_style(records::address::AddressStyle::TwoLevel)",
+            address.raw()
+        ),
+        AddressStyle::Free => format!("GroupAddress::from_raw({})", address.raw()),
+    }
+}
+
+/// Whether a color light takes RGBW (DPT 251.600) rather than RGB (DPT 232.600)
+fn is_rgbw(device: &Device) -> bool {
+    device
+        .datapoints
+        .iter()
+        .any(|datapoint| datapoint.dpt.main == 251)
+}
@@ -0,0 +1,892 @@
+//! KNX ↔ MQTT Bridge
+//!
+//! Contains the links between the KNX bus and MQTT:
+//! - Bridge: Wire format and clock of the links; `configure` registers them
+//! - Buffers: Runtime-specific record buffers (Embassy or Tokio)
+//! - Link codecs: KNX telegrams to stamped records, records to MQTT and back
+//!
+//! Every state record is linked from the KNX status addresses of the devices
+//! of its kind, and every command record from their MQTT control topics. A
+//! [`Dispatch`] per record then publishes each value where it belongs: a state
+//! to the topic of the device reporting on its address, a command to the group
+//! addresses of the device it names, or as a rejection to the error topic if
+//! its kind's allow-list does not hold that address. The devices come from the
+//! tables in [`config`](crate::config), so adding one adds links but no
+//! dispatches.
+//!
+//! The links are only declared here. The connectors registered for the `knx`
+//! and `mqtt` schemes carry them, so the same configuration runs against the
+//! real connectors on the firmware and against fakes in the host tests.
+//!
+//! This module is no_std and works in both embedded and std environments.
+
+use crate::config::*;
+use crate::dispatch::{Dispatch, Message};
+use aimdb_core::{AimDbBuilder, RecordRegistrar, Spawn};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::sync::atomic::{AtomicU32, Ordering};
+use records::blinds::knx::BlindStatus;
+use records::{
+    AllowList, BlindControl, BlindState, CommandRejection, DeviceTopic, DimmerControl, DimmerState,
+    Encoding, GroupAddress, RecordCodec, RecordError, RecordMeta, Route, SwitchControl,
+    SwitchState, Temperature, Timestamped,
+};
+#[cfg(feature = "clock")]
+use records::{ClockControl, ClockState};
+#[cfg(feature = "sensors")]
+use records::{Co2, Humidity, Illuminance, Quantity, SensorReading, WindSpeed};
+#[cfg(feature = "color")]
+use records::{ColorLightControl, ColorLightState};
+#[cfg(feature = "energy")]
+use records::{EnergyCounter, KnxRecord, PowerReading};
+#[cfg(feature = "hvac")]
+use records::{HvacModeControl, HvacModeState, SetpointControl, SetpointState};
+#[cfg(feature = "scene")]
+use records::{SceneControl, SceneState};
+#[cfg(feature = "text")]
+use records::{TextEncoding, TextMessage, TextMessageControl};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Wire format and clock of the gateway's links
+#[derive(Debug, Clone, Copy)]
+pub struct Bridge {
+    encoding: Encoding,
+    boot_id: u32,
+    uptime_ms: fn() -> u64,
+}
+
+/// Buffer kind of a record, as on Embassy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferType {
+    /// Ring with one read position per consumer, for records shared by devices
+    SpmcRing,
+    /// Latest value only, for records of a single device
+    SingleLatest,
+}
+
+/// Record buffers of one runtime
+///
+/// The bridge sizes every buffer like the firmware needs it: `CAP` entries
+/// and one consumer per dispatch reading the record. Runtimes without
+/// fixed-size buffers may ignore the sizes.
+pub trait Buffers<R: Spawn + 'static> {
+    /// Configure the record's buffer
+    fn buffer_sized<'a, T, const CAP: usize, const CONSUMERS: usize>(
+        reg: &'a mut RecordRegistrar<'a, T, R>,
+        buffer_type: BufferType,
+    ) -> &'a mut RecordRegistrar<'a, T, R>
+    where
+        T: Send + Sync + Clone + Debug + 'static;
+}
+
+/// KNX telegrams dropped because their payload could not be decoded
+static DROPPED_TELEGRAMS: AtomicU32 = AtomicU32::new(0);
+
+/// Encoder of a command for one group address, `Ok(None)` if it belongs elsewhere
+type KnxEncoder<T> = fn(&T) -> Result<Option<Vec<u8>>, RecordError>;
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl Bridge {
+    /// Create a bridge publishing in `encoding`
+    ///
+    /// `boot_id` counts the gateway's boots (consumers only compare uptimes
+    /// within one boot, and take the highest as current) and `uptime_ms`
+    /// reads the runtime's clock.
+    pub const fn new(encoding: Encoding, boot_id: u32, uptime_ms: fn() -> u64) -> Self {
+        Self {
+            encoding,
+            boot_id,
+            uptime_ms,
+        }
+    }
+
+    /// Wire format of the MQTT links
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+}
+
+// ============================================================================
+// LINK CODECS
+// ============================================================================
+
+/// Number of KNX telegrams dropped since boot because they failed to decode
+pub fn dropped_telegrams() -> u32 {
+    DROPPED_TELEGRAMS.load(Ordering::Relaxed)
+}
+
+impl Bridge {
+    /// Stamp a state decoded from a KNX telegram with its receive metadata
+    ///
+    /// The KNX connector does not pass the telegram's source address to
+    /// deserializers, so `source` stays empty, and without a time sync only
+    /// the uptime is known.
+    pub fn stamp<T: Timestamped>(&self, record: T) -> T {
+        match RecordMeta::new(GATEWAY_ID, self.boot_id, (self.uptime_ms)()) {
+            Ok(meta) => record.with_meta(meta),
+            Err(_) => record,
+        }
+    }
+
+    /// MQTT link URL of a record topic
+    fn mqtt_url(&self, topic: &str) -> String {
+        alloc::format!("mqtt://{}", self.encoding.topic(topic))
+    }
+
+    /// MQTT message of a record on its topic, empty if it fails to serialize
+    fn mqtt_message<T: RecordCodec>(&self, topic: &str, record: &T) -> Vec<Message> {
+        match record.encode(self.encoding) {
+            Ok(bytes) => vec![(self.encoding.topic(topic), bytes)],
+            Err(e) => {
+                warn!("Serialization failed: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// MQTT message of a state on its device's topic, empty for unknown devices
+    fn device_state<T: RecordCodec>(&self, device: Option<DeviceTopic>, state: &T) -> Vec<Message> {
+        match device {
+            Some(device) => self.mqtt_message(&device.state(), state),
+            None => Vec::new(),
+        }
+    }
+
+    /// Rejection of a command for a group address off its allow-list
+    ///
+    /// Empty for allowed commands, which are written to KNX instead.
+    fn reject(&self, commands: AllowList, kind: &str, address: GroupAddress) -> Vec<Message> {
+        let Some(rejection) = commands.route(address).rejection(address) else {
+            return Vec::new();
+        };
+        warn!(
+            "🚫 Rejected {} command for {}: {}",
+            kind,
+            address.to_heapless().as_str(),
+            rejection.reason
+        );
+        self.mqtt_message(CommandRejection::MQTT_TOPIC, &self.stamp(rejection))
+    }
+
+    /// Deserialize a command received on its MQTT topic, logging rejected ones
+    fn decode_mqtt<T: RecordCodec>(&self, data: &[u8], kind: &str) -> Result<T, String> {
+        T::decode(data, self.encoding).map_err(|e| {
+            warn!("Dropping {} command: {}", kind, e);
+            e.to_string()
+        })
+    }
+}
+
+/// KNX link URL of a group address
+fn knx_url(address: impl core::fmt::Display) -> String {
+    alloc::format!("knx://{}", address)
+}
+
+/// Count and log a KNX telegram that failed to decode
+///
+/// Returns the error message expected by the connector, which then drops
+/// the telegram instead of forwarding a default value to MQTT.
+fn drop_telegram(kind: &str, e: RecordError) -> String {
+    let dropped = DROPPED_TELEGRAMS.fetch_add(1, Ordering::Relaxed) + 1;
+    warn!(
+        "🗑️  Dropped malformed {} telegram: {} (total dropped: {})",
+        kind, e, dropped
+    );
+    e.to_string()
+}
+
+/// KNX write of a command to its group address, empty if it fails to encode
+fn knx_write(address: GroupAddress, bytes: Result<Vec<u8>, RecordError>) -> Vec<Message> {
+    match bytes {
+        Ok(bytes) => vec![(address.to_string(), bytes)],
+        Err(e) => {
+            warn!("Serialization failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// KNX writes of a command, one per group address whose encoder takes it
+///
+/// Encoders return `Ok(None)` for commands that belong on another of the
+/// device's group addresses, which are skipped without an error.
+fn knx_writes<T>(control: &T, objects: &[(GroupAddress, KnxEncoder<T>)]) -> Vec<Message> {
+    objects
+        .iter()
+        .filter_map(|(address, encode)| match encode(control) {
+            Ok(bytes) => bytes.map(|bytes| (address.to_string(), bytes)),
+            Err(e) => {
+                warn!("Serialization failed: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Device of the sensor reporting on a group address
+fn sensor_at(sensors: &[Sensor], address: GroupAddress) -> Option<DeviceTopic<'static>> {
+    sensors
+        .iter()
+        .find(|sensor| sensor.state == address)
+        .map(|sensor| sensor.device)
+}
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+impl Bridge {
+    /// Register the links of every device in the enabled device groups
+    ///
+    /// The builder must have connectors for the `knx` and `mqtt` schemes.
+    pub fn configure<R, B>(&self, builder: &mut AimDbBuilder<R>)
+    where
+        R: Spawn + 'static,
+        B: Buffers<R>,
+    {
+        self.configure_states::<SwitchState, R, B>(
+            builder,
+            SWITCHES.iter().map(|switch| switch.state),
+            "switch",
+            records::switch::knx::from_knx,
+        );
+        self.configure_commands::<SwitchControl, 2, R, B>(
+            builder,
+            SWITCHES.iter().map(|switch| switch.device),
+            "switch control",
+        );
+
+        self.configure_states::<Temperature, R, B>(
+            builder,
+            TEMPERATURE_SENSORS.iter().map(|sensor| sensor.state),
+            "temperature",
+            records::temperature::knx::from_knx,
+        );
+
+        self.configure_states::<DimmerState, R, B>(
+            builder,
+            DIMMERS.iter().map(|dimmer| dimmer.state),
+            "dimmer",
+            records::dimmer::knx::from_knx,
+        );
+        self.configure_commands::<DimmerControl, 2, R, B>(
+            builder,
+            DIMMERS.iter().map(|dimmer| dimmer.device),
+            "dimmer control",
+        );
+
+        self.configure_blinds::<R, B>(builder);
+
+        #[cfg(feature = "sensors")]
+        {
+            self.configure_sensor::<Illuminance, R, B>(builder, &ILLUMINANCE_SENSORS);
+            self.configure_sensor::<WindSpeed, R, B>(builder, &WIND_SENSORS);
+            self.configure_sensor::<Humidity, R, B>(builder, &HUMIDITY_SENSORS);
+            self.configure_sensor::<Co2, R, B>(builder, &CO2_SENSORS);
+        }
+
+        #[cfg(feature = "energy")]
+        {
+            self.configure_states::<EnergyCounter, R, B>(
+                builder,
+                ENERGY_METERS.iter().map(|meter| meter.state),
+                "energy counter",
+                records::energy::knx::counter_from_knx,
+            );
+            self.configure_states::<PowerReading, R, B>(
+                builder,
+                POWER_METERS.iter().map(|meter| meter.state),
+                "power",
+                PowerReading::from_knx,
+            );
+        }
+
+        #[cfg(feature = "color")]
+        self.configure_color::<R, B>(builder);
+
+        #[cfg(feature = "scene")]
+        {
+            self.configure_states::<SceneState, R, B>(
+                builder,
+                SCENES.iter().map(|scene| scene.state),
+                "scene",
+                records::scene::knx::from_knx,
+            );
+            self.configure_commands::<SceneControl, 2, R, B>(
+                builder,
+                SCENES.iter().map(|scene| scene.device),
+                "scene",
+            );
+        }
+
+        #[cfg(feature = "clock")]
+        self.configure_clock::<R, B>(builder);
+
+        #[cfg(feature = "text")]
+        {
+            self.configure_states::<TextMessage, R, B>(
+                builder,
+                TEXT_PANELS.iter().map(|panel| panel.state),
+                "text",
+                |data, address| records::text::knx::from_knx(data, address, TextEncoding::Ascii),
+            );
+            self.configure_commands::<TextMessageControl, 2, R, B>(
+                builder,
+                TEXT_DISPLAYS.iter().map(|display| display.device),
+                "text",
+            );
+        }
+
+        #[cfg(feature = "hvac")]
+        {
+            self.configure_states::<HvacModeState, R, B>(
+                builder,
+                THERMOSTATS.iter().map(|thermostat| thermostat.state),
+                "HVAC mode",
+                records::hvac::knx::mode_from_knx,
+            );
+            self.configure_commands::<HvacModeControl, 2, R, B>(
+                builder,
+                THERMOSTATS.iter().map(|thermostat| thermostat.device),
+                "HVAC mode",
+            );
+            self.configure_states::<SetpointState, R, B>(
+                builder,
+                SETPOINTS.iter().map(|setpoint| setpoint.state),
+                "setpoint",
+                records::hvac::knx::setpoint_from_knx,
+            );
+            self.configure_commands::<SetpointControl, 2, R, B>(
+                builder,
+                SETPOINTS.iter().map(|setpoint| setpoint.device),
+                "setpoint",
+            );
+        }
+    }
+
+    /// State record linked from the status group address of each device of a kind
+    ///
+    /// `mqtt_dispatch` publishes each state to its device's topic; the ring
+    /// keeps one device's state from being overwritten by another's before it
+    /// is published.
+    fn configure_states<T, R, B>(
+        &self,
+        builder: &mut AimDbBuilder<R>,
+        addresses: impl IntoIterator<Item = GroupAddress>,
+        kind: &'static str,
+        decode: fn(&[u8], GroupAddress) -> Result<T, RecordError>,
+    ) where
+        T: Timestamped + Send + Sync + Clone + Debug + 'static,
+        R: Spawn + 'static,
+        B: Buffers<R>,
+    {
+        let bridge = *self;
+
+        // Configure the state record (inbound: KNX → AimDB)
+        builder.configure::<T>(|reg| {
+            let mut reg = B::buffer_sized::<_, 8, 1>(reg, BufferType::SpmcRing);
+            for address in addresses {
+                reg = reg
+                    // Subscribe from the device's status group address (e.g. 1/0/7)
+                    .link_from(&knx_url(address))
+                    .with_deserializer(move |data: &[u8]| {
+                        decode(data, address)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram(kind, e))
+                    })
+                    .finish();
+            }
+        });
+    }
+
+    /// Command record linked from the control topic of each device of a kind
+    ///
+    /// `knx_dispatch` writes each command to the device it names and, for
+    /// kinds with an allow-list, `mqtt_dispatch` rejects the others;
+    /// `CONSUMERS` counts the dispatches reading the record.
+    fn configure_commands<T, const CONSUMERS: usize, R, B>(
+        &self,
+        builder: &mut AimDbBuilder<R>,
+        devices: impl IntoIterator<Item = DeviceTopic<'static>>,
+        kind: &'static str,
+    ) where
+        T: RecordCodec + Send + Sync + Clone + Debug + 'static,
+        R: Spawn + 'static,
+        B: Buffers<R>,
+    {
+        let bridge = *self;
+
+        // Configure the command record (inbound: MQTT → AimDB)
+        builder.configure::<T>(|reg| {
+            let mut reg = B::buffer_sized::<_, 8, CONSUMERS>(reg, BufferType::SpmcRing);
+            for device in devices {
+                reg = reg
+                    // Subscribe from the device's MQTT commands
+                    .link_from(&bridge.mqtt_url(&device.control()))
+                    .with_deserializer(move |data: &[u8]| bridge.decode_mqtt(data, kind))
+                    .finish();
+            }
+        });
+    }
+
+    /// Blinds, each merged from its three status objects
+    fn configure_blinds<R: Spawn + 'static, B: Buffers<R>>(&self, builder: &mut AimDbBuilder<R>) {
+        let bridge = *self;
+
+        // Configure BlindState record (inbound: KNX → AimDB)
+        builder.configure::<BlindState>(|reg| {
+            let mut reg = B::buffer_sized::<_, 8, 1>(reg, BufferType::SpmcRing);
+            for blind in BLINDS {
+                // Last known status of the blind, merged from the actuator's three status objects
+                let status = Arc::new(BlindStatus::new(blind.position_state));
+                let (slat_status, moving_status) = (status.clone(), status.clone());
+                reg = reg
+                    // Subscribe from the three KNX status objects (e.g. 2/1/4, 2/1/5, 2/1/6)
+                    .link_from(&knx_url(blind.position_state))
+                    .with_deserializer(move |data: &[u8]| {
+                        status
+                            .position_from_knx(data)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram("blind position", e))
+                    })
+                    .finish()
+                    .link_from(&knx_url(blind.slat_angle_state))
+                    .with_deserializer(move |data: &[u8]| {
+                        slat_status
+                            .slat_angle_from_knx(data)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram("blind slat", e))
+                    })
+                    .finish()
+                    .link_from(&knx_url(blind.moving))
+                    .with_deserializer(move |data: &[u8]| {
+                        moving_status
+                            .moving_from_knx(data)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram("blind moving", e))
+                    })
+                    .finish();
+            }
+        });
+
+        self.configure_commands::<BlindControl, 2, R, B>(
+            builder,
+            BLINDS.iter().map(|blind| blind.device),
+            "blind control",
+        );
+    }
+
+    /// DPT 9 sensors of one quantity
+    #[cfg(feature = "sensors")]
+    fn configure_sensor<Q: Quantity, R: Spawn + 'static, B: Buffers<R>>(
+        &self,
+        builder: &mut AimDbBuilder<R>,
+        sensors: &[Sensor],
+    ) {
+        self.configure_states::<SensorReading<Q>, R, B>(
+            builder,
+            sensors.iter().map(|sensor| sensor.state),
+            Q::NAME,
+            records::sensor::knx::from_knx::<Q>,
+        );
+    }
+
+    /// RGB and RGBW LED strips, each decoded with its own DPT
+    #[cfg(feature = "color")]
+    fn configure_color<R: Spawn + 'static, B: Buffers<R>>(&self, builder: &mut AimDbBuilder<R>) {
+        let bridge = *self;
+
+        // Configure ColorLightState record (inbound: KNX → AimDB)
+        builder.configure::<ColorLightState>(|reg| {
+            let mut reg = B::buffer_sized::<_, 8, 1>(reg, BufferType::SpmcRing);
+            for light in COLOR_LIGHTS {
+                let address = light.state;
+                reg = if light.white {
+                    // Subscribe from the RGBW strip status (e.g. 4/1/1, DPT 251.600)
+                    reg.link_from(&knx_url(address))
+                        .with_deserializer(move |data: &[u8]| {
+                            records::color::knx::rgbw_from_knx(data, address)
+                                .map(|state| bridge.stamp(state))
+                                .map_err(|e| drop_telegram("RGBW color", e))
+                        })
+                        .finish()
+                } else {
+                    // Subscribe from the RGB strip status (e.g. 4/0/1, DPT 232.600)
+                    reg.link_from(&knx_url(address))
+                        .with_deserializer(move |data: &[u8]| {
+                            records::color::knx::rgb_from_knx(data, address)
+                                .map(|state| bridge.stamp(state))
+                                .map_err(|e| drop_telegram("RGB color", e))
+                        })
+                        .finish()
+                };
+            }
+        });
+
+        self.configure_commands::<ColorLightControl, 2, R, B>(
+            builder,
+            COLOR_LIGHTS.iter().map(|light| light.device),
+            "color control",
+        );
+    }
+
+    /// Master clocks, each with time, date and combined objects
+    #[cfg(feature = "clock")]
+    fn configure_clock<R: Spawn + 'static, B: Buffers<R>>(&self, builder: &mut AimDbBuilder<R>) {
+        let bridge = *self;
+
+        // Configure ClockState record (inbound: KNX → AimDB)
+        builder.configure::<ClockState>(|reg| {
+            let mut reg = B::buffer_sized::<_, 8, 1>(reg, BufferType::SpmcRing);
+            for clock in CLOCKS {
+                let Clock {
+                    time,
+                    date,
+                    datetime,
+                    ..
+                } = clock;
+                reg = reg
+                    // Subscribe from the clock's time (e.g. 7/0/0, DPT 10.001)
+                    .link_from(&knx_url(time))
+                    .with_deserializer(move |data: &[u8]| {
+                        records::clock::knx::state_from_time_knx(data, time)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram("time", e))
+                    })
+                    .finish()
+                    // Subscribe from the clock's date (e.g. 7/0/1, DPT 11.001)
+                    .link_from(&knx_url(date))
+                    .with_deserializer(move |data: &[u8]| {
+                        records::clock::knx::state_from_date_knx(data, date)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram("date", e))
+                    })
+                    .finish()
+                    // Subscribe from the clock's date and time (e.g. 7/0/2, DPT 19.001)
+                    .link_from(&knx_url(datetime))
+                    .with_deserializer(move |data: &[u8]| {
+                        records::clock::knx::state_from_datetime_knx(data, datetime)
+                            .map(|state| bridge.stamp(state))
+                            .map_err(|e| drop_telegram("date/time", e))
+                    })
+                    .finish();
+            }
+        });
+
+        self.configure_commands::<ClockControl, 1, R, B>(
+            builder,
+            CLOCKS.iter().map(|clock| clock.device),
+            "clock",
+        );
+    }
+}
+
+// ============================================================================
+// DISPATCH
+// ============================================================================
+
+impl Bridge {
+    /// Records written to KNX per command, for a [`Dispatcher`] around the KNX connector
+    ///
+    /// [`Dispatcher`]: crate::dispatch::Dispatcher
+    pub fn knx_dispatch<R: Spawn + 'static>(&self) -> Vec<Dispatch<R>> {
+        #[allow(unused_mut)]
+        let mut dispatches = vec![
+            // Each switch command to the actuator it names, if on the allow-list
+            Dispatch::new(
+                |control: &SwitchControl| match SWITCH_COMMANDS.route(control.address) {
+                    Route::Write(address) => {
+                        knx_write(address, records::switch::knx::to_knx(control))
+                    }
+                    Route::Reject(_) => Vec::new(),
+                },
+            ),
+            // Each dimmer command to the dimmer whose value or step object it
+            // names: absolute brightness to the value object (DPT 5.001),
+            // relative dimming to the step object (DPT 3.007)
+            Dispatch::new(
+                |control: &DimmerControl| match DIMMER_COMMANDS.route(control.address) {
+                    Route::Write(address) => DIMMERS
+                        .iter()
+                        .filter(|dimmer| dimmer.value == address || dimmer.step == address)
+                        .flat_map(|dimmer| {
+                            knx_writes(
+                                control,
+                                &[
+                                    (dimmer.value, records::dimmer::knx::brightness_to_knx),
+                                    (dimmer.step, records::dimmer::knx::step_to_knx),
+                                ],
+                            )
+                        })
+                        .collect(),
+                    Route::Reject(_) => Vec::new(),
+                },
+            ),
+            // Each blind command to the blind whose object it names: up/down
+            // (DPT 1.008), stop/step (DPT 1.007), position and slat angle (DPT 5.001)
+            Dispatch::new(
+                |control: &BlindControl| match BLIND_COMMANDS.route(control.address) {
+                    Route::Write(address) => BLINDS
+                        .iter()
+                        .filter(|blind| {
+                            [blind.up_down, blind.step, blind.position, blind.slat_angle]
+                                .contains(&address)
+                        })
+                        .flat_map(|blind| {
+                            knx_writes(
+                                control,
+                                &[
+                                    (blind.up_down, records::blinds::knx::move_to_knx),
+                                    (blind.step, records::blinds::knx::step_to_knx),
+                                    (blind.position, records::blinds::knx::position_to_knx),
+                                    (blind.slat_angle, records::blinds::knx::slat_angle_to_knx),
+                                ],
+                            )
+                        })
+                        .collect(),
+                    Route::Reject(_) => Vec::new(),
+                },
+            ),
+        ];
+
+        // Each color command to the strip it names, RGB (DPT 232.600) or RGBW (DPT 251.600)
+        #[cfg(feature = "color")]
+        dispatches.push(Dispatch::new(
+            |control: &ColorLightControl| match COLOR_COMMANDS.route(control.address) {
+                Route::Write(address) => {
+                    let white = COLOR_LIGHTS
+                        .iter()
+                        .any(|light| light.control == address && light.white);
+                    let bytes = if white {
+                        records::color::knx::rgbw_to_knx(control)
+                    } else {
+                        records::color::knx::rgb_to_knx(control)
+                    };
+                    knx_write(address, bytes)
+                }
+                Route::Reject(_) => Vec::new(),
+            },
+        ));
+
+        // Each scene activation to the scene object it names (DPT 18.001)
+        #[cfg(feature = "scene")]
+        dispatches.push(Dispatch::new(|control: &SceneControl| match SCENE_COMMANDS
+            .route(control.address)
+        {
+            Route::Write(address) => knx_write(address, records::scene::knx::to_knx(control)),
+            Route::Reject(_) => Vec::new(),
+        }));
+
+        // Each clock command to every clock: time (DPT 10.001) and date (DPT 11.001)
+        // if set, date and time (DPT 19.001) always
+        #[cfg(feature = "clock")]
+        dispatches.push(Dispatch::new(|control: &ClockControl| {
+            CLOCKS
+                .iter()
+                .flat_map(|clock| {
+                    knx_writes(
+                        control,
+                        &[
+                            (clock.time, |control: &ClockControl| {
+                                control
+                                    .datetime
+                                    .time
+                                    .map(|time| records::clock::knx::time_to_knx(&time))
+                                    .transpose()
+                            }),
+                            (clock.date, |control: &ClockControl| {
+                                control
+                                    .datetime
+                                    .date
+                                    .map(|date| records::clock::knx::date_to_knx(&date))
+                                    .transpose()
+                            }),
+                            (clock.datetime, |control: &ClockControl| {
+                                records::clock::knx::datetime_to_knx(&control.datetime).map(Some)
+                            }),
+                        ],
+                    )
+                })
+                .collect()
+        }));
+
+        // Each text to the display it names (DPT 16.000 / 16.001 by the command's encoding)
+        #[cfg(feature = "text")]
+        dispatches.push(Dispatch::new(
+            |control: &TextMessageControl| match TEXT_COMMANDS.route(control.address) {
+                Route::Write(address) => knx_write(address, records::text::knx::to_knx(control)),
+                Route::Reject(_) => Vec::new(),
+            },
+        ));
+
+        // Each HVAC mode (DPT 20.102) and setpoint (DPT 9.001) to the object it names
+        #[cfg(feature = "hvac")]
+        dispatches.extend([
+            Dispatch::new(|control: &HvacModeControl| {
+                match HVAC_MODE_COMMANDS.route(control.address) {
+                    Route::Write(address) => {
+                        knx_write(address, records::hvac::knx::mode_to_knx(control))
+                    }
+                    Route::Reject(_) => Vec::new(),
+                }
+            }),
+            Dispatch::new(|control: &SetpointControl| {
+                match SETPOINT_COMMANDS.route(control.address) {
+                    Route::Write(address) => {
+                        knx_write(address, records::hvac::knx::setpoint_to_knx(control))
+                    }
+                    Route::Reject(_) => Vec::new(),
+                }
+            }),
+        ]);
+
+        dispatches
+    }
+
+    /// Records published to per-device MQTT topics, for a [`Dispatcher`] around the MQTT connector
+    ///
+    /// Every state goes to the topic of the device reporting on its address,
+    /// and every command off its kind's allow-list to the error topic.
+    ///
+    /// [`Dispatcher`]: crate::dispatch::Dispatcher
+    pub fn mqtt_dispatch<R: Spawn + 'static>(&self) -> Vec<Dispatch<R>> {
+        let bridge = *self;
+        #[allow(unused_mut)]
+        let mut dispatches = vec![
+            Dispatch::new(move |state: &SwitchState| {
+                let switch = SWITCHES.iter().find(|switch| switch.state == state.address);
+                bridge.device_state(switch.map(|switch| switch.device), state)
+            }),
+            Dispatch::new(move |control: &SwitchControl| {
+                bridge.reject(SWITCH_COMMANDS, "switch", control.address)
+            }),
+            Dispatch::new(move |state: &Temperature| {
+                bridge.device_state(sensor_at(&TEMPERATURE_SENSORS, state.address), state)
+            }),
+            Dispatch::new(move |state: &DimmerState| {
+                let dimmer = DIMMERS.iter().find(|dimmer| dimmer.state == state.address);
+                bridge.device_state(dimmer.map(|dimmer| dimmer.device), state)
+            }),
+            Dispatch::new(move |control: &DimmerControl| {
+                bridge.reject(DIMMER_COMMANDS, "dimmer", control.address)
+            }),
+            // Blind states carry their position status address
+            Dispatch::new(move |state: &BlindState| {
+                let blind = BLINDS
+                    .iter()
+                    .find(|blind| blind.position_state == state.address);
+                bridge.device_state(blind.map(|blind| blind.device), state)
+            }),
+            Dispatch::new(move |control: &BlindControl| {
+                bridge.reject(BLIND_COMMANDS, "blind", control.address)
+            }),
+        ];
+
+        #[cfg(feature = "sensors")]
+        dispatches.extend([
+            self.sensor_dispatch::<Illuminance, R>(&ILLUMINANCE_SENSORS),
+            self.sensor_dispatch::<WindSpeed, R>(&WIND_SENSORS),
+            self.sensor_dispatch::<Humidity, R>(&HUMIDITY_SENSORS),
+            self.sensor_dispatch::<Co2, R>(&CO2_SENSORS),
+        ]);
+
+        #[cfg(feature = "energy")]
+        dispatches.extend([
+            Dispatch::new(move |state: &EnergyCounter| {
+                bridge.device_state(sensor_at(&ENERGY_METERS, state.address), state)
+            }),
+            Dispatch::new(move |state: &PowerReading| {
+                bridge.device_state(sensor_at(&POWER_METERS, state.address), state)
+            }),
+        ]);
+
+        #[cfg(feature = "color")]
+        dispatches.extend([
+            Dispatch::new(move |state: &ColorLightState| {
+                let light = COLOR_LIGHTS
+                    .iter()
+                    .find(|light| light.state == state.address);
+                bridge.device_state(light.map(|light| light.device), state)
+            }),
+            Dispatch::new(move |control: &ColorLightControl| {
+                bridge.reject(COLOR_COMMANDS, "color", control.address)
+            }),
+        ]);
+
+        #[cfg(feature = "scene")]
+        dispatches.extend([
+            Dispatch::new(move |state: &SceneState| {
+                let scene = SCENES.iter().find(|scene| scene.state == state.address);
+                bridge.device_state(scene.map(|scene| scene.device), state)
+            }),
+            Dispatch::new(move |control: &SceneControl| {
+                bridge.reject(SCENE_COMMANDS, "scene", control.address)
+            }),
+        ]);
+
+        // Clock states carry the time, date or date and time address they were read from
+        #[cfg(feature = "clock")]
+        dispatches.push(Dispatch::new(move |state: &ClockState| {
+            let clock = CLOCKS
+                .iter()
+                .find(|clock| [clock.time, clock.date, clock.datetime].contains(&state.address));
+            bridge.device_state(clock.map(|clock| clock.device), state)
+        }));
+
+        #[cfg(feature = "text")]
+        dispatches.extend([
+            Dispatch::new(move |state: &TextMessage| {
+                bridge.device_state(sensor_at(&TEXT_PANELS, state.address), state)
+            }),
+            Dispatch::new(move |control: &TextMessageControl| {
+                bridge.reject(TEXT_COMMANDS, "text", control.address)
+            }),
+        ]);
+
+        #[cfg(feature = "hvac")]
+        dispatches.extend([
+            Dispatch::new(move |state: &HvacModeState| {
+                let thermostat = THERMOSTATS
+                    .iter()
+                    .find(|thermostat| thermostat.state == state.address);
+                bridge.device_state(thermostat.map(|thermostat| thermostat.device), state)
+            }),
+            Dispatch::new(move |state: &SetpointState| {
+                let setpoint = SETPOINTS
+                    .iter()
+                    .find(|setpoint| setpoint.state == state.address);
+                bridge.device_state(setpoint.map(|setpoint| setpoint.device), state)
+            }),
+            Dispatch::new(move |control: &HvacModeControl| {
+                bridge.reject(HVAC_MODE_COMMANDS, "HVAC mode", control.address)
+            }),
+            Dispatch::new(move |control: &SetpointControl| {
+                bridge.reject(SETPOINT_COMMANDS, "setpoint", control.address)
+            }),
+        ]);
+
+        dispatches
+    }
+
+    /// Readings of one DPT 9 quantity, each to its sensor's topic
+    #[cfg(feature = "sensors")]
+    fn sensor_dispatch<Q: Quantity, R: Spawn + 'static>(
+        &self,
+        sensors: &'static [Sensor],
+    ) -> Dispatch<R> {
+        let bridge = *self;
+        Dispatch::new(move |state: &SensorReading<Q>| {
+            bridge.device_state(sensor_at(sensors, state.address), state)
+        })
+    }
+}
@@ -0,0 +1,195 @@
+//! Installation Configuration
+//!
+//! Contains what the gateway is wired to in this installation:
+//! - Network: KNX/IP gateway, MQTT broker and the wire format on MQTT
+//! - Devices: One table per kind, with each device's room and name (from
+//!   which its MQTT topics derive) and the KNX group addresses it uses,
+//!   generated from `registry.toml` (or the file named by `DEVICE_REGISTRY`)
+//! - Allow-lists: Group addresses MQTT commands of each kind may write to
+//!
+//! This module is no_std and works in both embedded and std environments.
+
+use records::{AllowList, DeviceTopic, Encoding, GroupAddress};
+
+// ============================================================================
+// NETWORK
+// ============================================================================
+
+/// Gateway id, used as MQTT client id and in record metadata
+pub const GATEWAY_ID: &str = "knx-gateway-001";
+
+/// KNX/IP gateway IP address
+pub const KNX_GATEWAY_IP: &str = "192.168.1.19";
+/// KNX/IP gateway port
+pub const KNX_GATEWAY_PORT: u16 = 3671;
+
+/// MQTT broker IP address
+pub const MQTT_BROKER_IP: &str = "192.168.1.7";
+/// MQTT broker port
+pub const MQTT_BROKER_PORT: u16 = 1883;
+
+/// Wire format of the MQTT links (the tower's `MQTT_ENCODING` must match)
+#[cfg(not(feature = "postcard"))]
+pub const MQTT_ENCODING: Encoding = Encoding::Json;
+/// Wire format of the MQTT links (the tower's `MQTT_ENCODING` must match)
+#[cfg(feature = "postcard")]
+pub const MQTT_ENCODING: Encoding = Encoding::Postcard;
+
+// ============================================================================
+// DEVICES
+// ============================================================================
+
+/// Device reporting one state, without commands (sensors, meters, status texts)
+pub struct Sensor {
+    /// Room and name, from which its MQTT state topic derives
+    pub device: DeviceTopic<'static>,
+    /// Group address of the reported value
+    pub state: GroupAddress,
+}
+
+/// Device with one status and one command group address
+pub struct Actuator {
+    /// Room and name, from which its MQTT topics derive
+    pub device: DeviceTopic<'static>,
+    /// Group address of the status feedback
+    pub state: GroupAddress,
+    /// Group address commands are written to
+    pub control: GroupAddress,
+}
+
+/// Dimming actuator channel
+pub struct Dimmer {
+    /// Room and name, from which its MQTT topics derive
+    pub device: DeviceTopic<'static>,
+    /// Group address of the brightness status (DPT 5.001)
+    pub state: GroupAddress,
+    /// Group address of the absolute brightness object (DPT 5.001)
+    pub value: GroupAddress,
+    /// Group address of the relative dimming object (DPT 3.007)
+    pub step: GroupAddress,
+}
+
+/// Shutter actuator channel
+pub struct Blind {
+    /// Room and name, from which its MQTT topics derive
+    pub device: DeviceTopic<'static>,
+    /// Group address of the long-time up/down object (DPT 1.008)
+    pub up_down: GroupAddress,
+    /// Group address of the stop/step object (DPT 1.007)
+    pub step: GroupAddress,
+    /// Group address of the absolute position object (DPT 5.001)
+    pub position: GroupAddress,
+    /// Group address of the absolute slat angle object (DPT 5.001)
+    pub slat_angle: GroupAddress,
+    /// Group address of the position status (DPT 5.001), naming the blind's states
+    pub position_state: GroupAddress,
+    /// Group address of the slat angle status (DPT 5.001)
+    pub slat_angle_state: GroupAddress,
+    /// Group address of the movement status (DPT 1.011)
+    pub moving: GroupAddress,
+}
+
+/// LED strip driven by color objects
+#[cfg(feature = "color")]
+pub struct ColorLight {
+    /// Room and name, from which its MQTT topics derive
+    pub device: DeviceTopic<'static>,
+    /// Group address of the color status
+    pub state: GroupAddress,
+    /// Group address of the color object
+    pub control: GroupAddress,
+    /// RGBW (DPT 251.600) rather than RGB (DPT 232.600)
+    pub white: bool,
+}
+
+/// Master clock, sending and receiving time and date
+#[cfg(feature = "clock")]
+pub struct Clock {
+    /// Room and name, from which its MQTT topics derive
+    pub device: DeviceTopic<'static>,
+    /// Group address of the time (DPT 10.001)
+    pub time: GroupAddress,
+    /// Group address of the date (DPT 11.001)
+    pub date: GroupAddress,
+    /// Group address of the date and time (DPT 19.001)
+    pub datetime: GroupAddress,
+}
+
+/// Display showing texts sent to it, without status
+#[cfg(feature = "text")]
+pub struct Display {
+    /// Room and name, from which its MQTT control topic derives
+    pub device: DeviceTopic<'static>,
+    /// Group address of the text object (DPT 16.001)
+    pub control: GroupAddress,
+}
+
+// Devices bridged by the gateway, one table per kind, each device on its own
+// MQTT topics `knx/<room>/<name>/state` and `/control`. The tables are
+// generated by `build.rs` from `registry.toml`, so the gateway and the tower
+// bridge the same devices. The bridge's dispatches route the states and
+// commands of every device in a table, so adding a device takes no slots of
+// the firmware's task pool.
+include!(concat!(env!("OUT_DIR"), "/devices.rs"));
+
+// ============================================================================
+// ALLOW-LISTS
+// ============================================================================
+
+// A command goes to the group address named by its `address`, whichever
+// device topic it arrived on, if that address is one of the command objects
+// of a device of its kind; commands for any other address are rejected.
+// Clock commands name no address and go to every clock.
+
+/// Command objects of every device in a table, in table order
+macro_rules! command_objects {
+    ($devices:expr => $($object:ident),+) => {{
+        const PER_DEVICE: usize = [$(stringify!($object)),+].len();
+        const OBJECTS: [GroupAddress; $devices.len() * PER_DEVICE] = {
+            let mut addresses = [GroupAddress::new(0, 0, 0); $devices.len() * PER_DEVICE];
+            let mut i = 0;
+            while i < $devices.len() {
+                let objects = [$($devices[i].$object),+];
+                let mut j = 0;
+                while j < PER_DEVICE {
+                    addresses[i * PER_DEVICE + j] = objects[j];
+                    j += 1;
+                }
+                i += 1;
+            }
+            addresses
+        };
+        &OBJECTS
+    }};
+}
+
+/// Group addresses SwitchControl commands may write to
+pub const SWITCH_COMMANDS: AllowList = AllowList::new(command_objects!(SWITCHES => control));
+
+/// Group addresses DimmerControl commands may write to: value and step objects
+pub const DIMMER_COMMANDS: AllowList = AllowList::new(command_objects!(DIMMERS => value, step));
+
+/// Group addresses BlindControl commands may write to: up/down, stop/step,
+/// position and slat angle objects
+pub const BLIND_COMMANDS: AllowList =
+    AllowList::new(command_objects!(BLINDS => up_down, step, position, slat_angle));
+
+/// Group addresses ColorLightControl commands may write to
+#[cfg(feature = "color")]
+pub const COLOR_COMMANDS: AllowList = AllowList::new(command_objects!(COLOR_LIGHTS => control));
+
+/// Group addresses SceneControl commands may write to
+#[cfg(feature = "scene")]
+pub const SCENE_COMMANDS: AllowList = AllowList::new(command_objects!(SCENES => control));
+
+/// Group addresses TextMessageControl commands may write to
+#[cfg(feature = "text")]
+pub const TEXT_COMMANDS: AllowList = AllowList::new(command_objects!(TEXT_DISPLAYS => control));
+
+/// Group addresses HvacModeControl commands may write to
+#[cfg(feature = "hvac")]
+pub const HVAC_MODE_COMMANDS: AllowList = AllowList::new(command_objects!(THERMOSTATS => control));
+
+/// Group addresses SetpointControl commands may write to
+#[cfg(feature = "hvac")]
+pub const SETPOINT_COMMANDS: AllowList = AllowList::new(command_objects!(SETPOINTS => control));
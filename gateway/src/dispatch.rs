@@ -0,0 +1,138 @@
+//! Routed Publishing
+//!
+//! Contains the outbound side of records that go to more than one place:
+//! - Dispatch: Subscription to one record, each value published where it belongs
+//! - Dispatcher: Connector builder running dispatches on the connector it wraps
+//!
+//! An aimdb outbound link publishes every value of its record to one fixed
+//! group address or topic, and can only skip a value by failing to serialize
+//! it, which the connectors log as an error. A dispatch routes each value
+//! itself instead: to one destination, to several, or to none, so only real
+//! encoding failures are logged.
+//!
+//! This module is no_std and works in both embedded and std environments.
+
+use aimdb_core::{AimDb, Connector, ConnectorBuilder, ConnectorConfig, DbError, DbResult, Spawn};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::future::Future;
+use core::pin::Pin;
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Destination (group address or MQTT topic) and payload of one publish
+pub type Message = (String, Vec<u8>);
+
+/// Starts a dispatch on a built connector
+type StartFn<R> =
+    Box<dyn Fn(&AimDb<R>, Arc<dyn Connector>, ConnectorConfig) -> DbResult<()> + Send + Sync>;
+
+/// Publishing of one record's values, each to the destinations it is routed to
+pub struct Dispatch<R: Spawn + 'static> {
+    start: StartFn<R>,
+    config: ConnectorConfig,
+}
+
+/// Connector builder that runs dispatches on the connector it wraps
+///
+/// Registered in place of the wrapped builder, under the same scheme, so
+/// the record's inbound links and plain outbound links keep working.
+pub struct Dispatcher<C, R: Spawn + 'static> {
+    connector: C,
+    dispatches: Vec<Dispatch<R>>,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl<R: Spawn + 'static> Dispatch<R> {
+    /// Publish every value of record `T` to the messages `route` returns
+    ///
+    /// An empty list skips the value without an error.
+    pub fn new<T, F>(route: F) -> Self
+    where
+        T: Send + Sync + Clone + Debug + 'static,
+        F: Fn(&T) -> Vec<Message> + Send + Sync + 'static,
+    {
+        let route = Arc::new(route);
+        let start = move |db: &AimDb<R>, connector: Arc<dyn Connector>, config| {
+            let mut reader = db.subscribe::<T>()?;
+            let route = route.clone();
+            db.spawn_task(async move {
+                loop {
+                    let value = match reader.recv().await {
+                        Ok(value) => value,
+                        Err(DbError::BufferLagged { .. }) => {
+                            warn!("Dispatch fell behind, values were skipped");
+                            continue;
+                        }
+                        Err(_) => break,
+                    };
+                    for (destination, payload) in route(&value) {
+                        if let Err(e) = connector.publish(&destination, &config, &payload).await {
+                            warn!("Failed to publish to {}: {:?}", destination.as_str(), e);
+                        }
+                    }
+                }
+            })
+        };
+
+        Self {
+            start: Box::new(start),
+            config: ConnectorConfig::default(),
+        }
+    }
+
+    /// Publish with this QoS level (MQTT)
+    pub fn with_qos(mut self, qos: u8) -> Self {
+        self.config.qos = qos;
+        self
+    }
+}
+
+impl<C, R> Dispatcher<C, R>
+where
+    C: ConnectorBuilder<R>,
+    R: Spawn + 'static,
+{
+    /// Wrap `connector`, running `dispatches` once it is built
+    pub fn new(connector: C, dispatches: Vec<Dispatch<R>>) -> Self {
+        Self {
+            connector,
+            dispatches,
+        }
+    }
+}
+
+// ============================================================================
+// CONNECTOR
+// ============================================================================
+
+impl<C, R> ConnectorBuilder<R> for Dispatcher<C, R>
+where
+    C: ConnectorBuilder<R>,
+    R: Spawn + 'static,
+{
+    fn build<'a>(
+        &'a self,
+        db: &'a AimDb<R>,
+    ) -> Pin<Box<dyn Future<Output = DbResult<Arc<dyn Connector>>> + Send + 'a>> {
+        Box::pin(async move {
+            let connector = self.connector.build(db).await?;
+            for dispatch in &self.dispatches {
+                (dispatch.start)(db, connector.clone(), dispatch.config.clone())?;
+            }
+            Ok(connector)
+        })
+    }
+
+    fn scheme(&self) -> &str {
+        self.connector.scheme()
+    }
+}
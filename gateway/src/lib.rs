@@ -0,0 +1,53 @@
+//! KNX ↔ MQTT Gateway Core
+//!
+//! The bridge the ground firmware runs, kept apart from the STM32 setup so
+//! that it builds for any aimdb runtime. The crate is no_std (with alloc):
+//! the firmware configures it on Embassy, and the host tests drive the same
+//! links on Tokio with fake KNX and MQTT connectors.
+//!
+//! ## Modules
+//!
+//! - [`config`]: Device tables (topics and group addresses) and allow-lists of the installation
+//! - [`bridge`]: Links and dispatches of every record, and the codecs behind them
+//! - [`dispatch`]: Publishing of records whose values go to different destinations
+//!
+//! ## Example Usage
+//!
+//! ```ignore
+//! use gateway::{Bridge, Dispatcher, config};
+//!
+//! let bridge = Bridge::new(config::MQTT_ENCODING, boot_id, uptime_ms);
+//! let mut builder = AimDbBuilder::new()
+//!     .runtime(runtime)
+//!     .with_connector(Dispatcher::new(
+//!         KnxConnectorBuilder::new(&gateway_url),
+//!         bridge.knx_dispatch(),
+//!     ))
+//!     .with_connector(Dispatcher::new(
+//!         MqttConnectorBuilder::new(&broker_url),
+//!         bridge.mqtt_dispatch(),
+//!     ));
+//!
+//! bridge.configure::<_, EmbassyBuffers>(&mut builder);
+//! ```
+//!
+//! ## Features
+//!
+//! The device groups (`sensors`, `energy`, `color`, `scene`, `hvac`, `text`,
+//! `clock`) are all on by default; the firmware enables the ones that fit its
+//! task pool. `defmt` or `tracing` select where dropped telegrams and refused
+//! commands are logged.
+
+#![no_std]
+
+extern crate alloc;
+
+#[macro_use]
+mod log;
+
+pub mod bridge;
+pub mod config;
+pub mod dispatch;
+
+pub use bridge::{dropped_telegrams, Bridge, BufferType, Buffers};
+pub use dispatch::{Dispatch, Dispatcher};
@@ -0,0 +1,17 @@
+//! Logging
+//!
+//! `warn!` forwards to defmt on the firmware and to tracing on the host. The
+//! format strings only use `{}` and `{:?}`, which both backends accept.
+//! Without either feature the arguments are still type-checked but nothing
+//! is logged.
+
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "defmt")]
+        defmt::warn!($($arg)*);
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+        #[cfg(not(any(feature = "defmt", feature = "tracing")))]
+        let _ = format_args!($($arg)*);
+    }};
+}
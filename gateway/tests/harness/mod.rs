@@ -0,0 +1,176 @@
+//! Host harness for the gateway bridge
+//!
+//! Runs [`Bridge::configure`] on Tokio with fake connectors in place of the
+//! KNX/IP tunnel and the MQTT broker. Each fake serves one scheme: the test
+//! injects telegrams or messages through its inbound routes and reads back
+//! what the bridge's dispatches publish.
+
+#![allow(dead_code)]
+
+use aimdb_core::buffer::BufferCfg;
+use aimdb_core::connector::ConnectorBuilder;
+use aimdb_core::transport::{Connector, ConnectorConfig, PublishError};
+use aimdb_core::{AimDb, AimDbBuilder, DbResult, RecordRegistrar, Router, RouterBuilder};
+use aimdb_tokio_adapter::{TokioAdapter, TokioRecordRegistrarExt};
+use gateway::{Bridge, BufferType, Buffers, Dispatcher};
+use records::{Encoding, GroupAddress};
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Time a test waits for the gateway to publish
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Boot id stamped into the records
+pub const BOOT_ID: u32 = 0x2A;
+
+/// Tokio buffers; the sizes only matter for Embassy's fixed-size buffers
+pub struct TokioBuffers;
+
+impl Buffers<TokioAdapter> for TokioBuffers {
+    fn buffer_sized<'a, T, const CAP: usize, const CONSUMERS: usize>(
+        reg: &'a mut RecordRegistrar<'a, T, TokioAdapter>,
+        buffer_type: BufferType,
+    ) -> &'a mut RecordRegistrar<'a, T, TokioAdapter>
+    where
+        T: Send + Sync + Clone + Debug + 'static,
+    {
+        reg.buffer(match buffer_type {
+            BufferType::SpmcRing => BufferCfg::SpmcRing { capacity: CAP },
+            BufferType::SingleLatest => BufferCfg::SingleLatest,
+        })
+    }
+}
+
+/// Message on a fake connector: resource (group address or topic) and payload
+pub type Message = (String, Vec<u8>);
+
+/// Fake connector for one scheme, built by the database like the real ones
+struct FakeConnector {
+    scheme: &'static str,
+    router: Arc<OnceLock<Router>>,
+    published: mpsc::UnboundedSender<Message>,
+}
+
+impl ConnectorBuilder<TokioAdapter> for FakeConnector {
+    fn build<'a>(
+        &'a self,
+        db: &'a AimDb<TokioAdapter>,
+    ) -> Pin<Box<dyn Future<Output = DbResult<Arc<dyn Connector>>> + Send + 'a>> {
+        Box::pin(async move {
+            let inbound = db.collect_inbound_routes(self.scheme);
+            let _ = self.router.set(RouterBuilder::from_routes(inbound).build());
+
+            // Everything the bridge sends goes through its dispatches
+            let outbound = db.collect_outbound_routes(self.scheme);
+            assert!(outbound.is_empty(), "unexpected {} links", self.scheme);
+
+            Ok(Arc::new(FakeLink(self.published.clone())) as Arc<dyn Connector>)
+        })
+    }
+
+    fn scheme(&self) -> &str {
+        self.scheme
+    }
+}
+
+/// Direct publishing through the fake connector
+struct FakeLink(mpsc::UnboundedSender<Message>);
+
+impl Connector for FakeLink {
+    fn publish(
+        &self,
+        destination: &str,
+        _config: &ConnectorConfig,
+        payload: &[u8],
+    ) -> Pin<Box<dyn Future<Output = Result<(), PublishError>> + Send + '_>> {
+        let _ = self.0.send((destination.to_string(), payload.to_vec()));
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// One side of the gateway: the KNX bus or the MQTT broker
+pub struct Bus {
+    router: Arc<OnceLock<Router>>,
+    published: mpsc::UnboundedReceiver<Message>,
+}
+
+impl Bus {
+    fn connector(scheme: &'static str) -> (FakeConnector, Self) {
+        let router = Arc::new(OnceLock::new());
+        let (tx, rx) = mpsc::unbounded_channel();
+        let connector = FakeConnector {
+            scheme,
+            router: router.clone(),
+            published: tx,
+        };
+        let bus = Self {
+            router,
+            published: rx,
+        };
+        (connector, bus)
+    }
+
+    /// Deliver a telegram or message to the gateway's inbound links
+    pub async fn send(&self, resource: &str, payload: &[u8]) {
+        let router = self.router.get().expect("connector not built");
+        router.route(resource, payload).await.expect("route");
+    }
+
+    /// Next message the gateway publishes, failing the test after a timeout
+    pub async fn recv(&mut self) -> Message {
+        tokio::time::timeout(TIMEOUT, self.published.recv())
+            .await
+            .expect("gateway published nothing")
+            .expect("connector closed")
+    }
+
+    /// Assert that the gateway publishes nothing more
+    pub async fn assert_quiet(&mut self) {
+        let quiet = tokio::time::timeout(Duration::from_millis(200), self.published.recv()).await;
+        assert!(quiet.is_err(), "unexpected message: {:?}", quiet);
+    }
+}
+
+/// Gateway bridge running on Tokio between a fake KNX bus and MQTT broker
+pub struct Harness {
+    pub db: AimDb<TokioAdapter>,
+    pub knx: Bus,
+    pub mqtt: Bus,
+}
+
+impl Harness {
+    /// Configure the bridge and build the database
+    ///
+    /// The dispatches subscribe to their records while the database is built,
+    /// so the gateway forwards from the start.
+    pub async fn start(encoding: Encoding) -> Self {
+        let (knx_connector, knx) = Bus::connector("knx");
+        let (mqtt_connector, mqtt) = Bus::connector("mqtt");
+
+        let bridge = Bridge::new(encoding, BOOT_ID, uptime_ms);
+        let runtime = Arc::new(TokioAdapter::new().expect("Tokio runtime"));
+        let mut builder = AimDbBuilder::new()
+            .runtime(runtime)
+            .with_connector(Dispatcher::new(knx_connector, bridge.knx_dispatch()))
+            .with_connector(Dispatcher::new(mqtt_connector, bridge.mqtt_dispatch()));
+        bridge.configure::<_, TokioBuffers>(&mut builder);
+        let db = builder.build().await.expect("build database");
+
+        Self { db, knx, mqtt }
+    }
+}
+
+/// Milliseconds since the first stamp in this process
+fn uptime_ms() -> u64 {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_millis() as u64
+}
+
+/// Parse a group address in 3-level notation
+pub fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
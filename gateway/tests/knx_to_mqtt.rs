@@ -0,0 +1,96 @@
+//! KNX → MQTT flows through the bridge on the host harness
+
+mod harness;
+
+use gateway::config::GATEWAY_ID;
+use harness::{address, Harness, BOOT_ID};
+use records::{BlindState, Encoding, RecordCodec, SwitchState, Temperature, Timestamped};
+
+#[tokio::test]
+async fn switch_state_reaches_its_device_topic() {
+    let mut gw = Harness::start(Encoding::Json).await;
+
+    gw.knx.send("1/0/7", &[0x01]).await;
+
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, "knx/living-room/tv/state");
+    let state = SwitchState::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(state.address, address("1/0/7"));
+    assert!(state.is_on);
+    let meta = state.meta().expect("stamped by the gateway");
+    assert_eq!(meta.gateway.as_str(), GATEWAY_ID);
+    assert_eq!(meta.boot, BOOT_ID);
+    gw.mqtt.assert_quiet().await;
+}
+
+#[tokio::test]
+async fn temperature_is_decoded_from_dpt9() {
+    let mut gw = Harness::start(Encoding::Json).await;
+
+    gw.knx.send("9/1/0", &[0x0C, 0x33]).await;
+
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, "knx/living-room/temperature/state");
+    let temperature = Temperature::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(temperature.celsius, 21.5);
+}
+
+#[tokio::test]
+async fn blind_state_merges_its_status_objects() {
+    let mut gw = Harness::start(Encoding::Json).await;
+
+    gw.knx.send("2/1/4", &[0x80]).await;
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, "knx/living-room/blind/state");
+    let state = BlindState::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(state.address.to_string(), "2/1/4");
+    assert_eq!(state.position, Some(50));
+    assert!(!state.moving);
+
+    gw.knx.send("2/1/6", &[0x01]).await;
+    let (_, payload) = gw.mqtt.recv().await;
+    let state = BlindState::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(state.position, Some(50));
+    assert!(state.moving);
+}
+
+#[cfg(feature = "color")]
+#[tokio::test]
+async fn color_strips_stay_on_their_own_topics() {
+    let mut gw = Harness::start(Encoding::Json).await;
+
+    gw.knx.send("4/0/1", &[0xFF, 0x80, 0x00]).await;
+
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, "knx/kitchen/rgb-strip/state");
+    let state = records::ColorLightState::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(state.address, address("4/0/1"));
+    assert_eq!(
+        (state.color.red, state.color.green, state.color.blue),
+        (0xFF, 0x80, 0x00)
+    );
+    gw.mqtt.assert_quiet().await;
+}
+
+#[tokio::test]
+async fn malformed_telegrams_are_dropped() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let dropped = gateway::dropped_telegrams();
+
+    gw.knx.send("1/0/7", &[0x01, 0x02]).await;
+
+    gw.mqtt.assert_quiet().await;
+    assert!(gateway::dropped_telegrams() > dropped);
+}
+
+#[tokio::test]
+async fn postcard_states_use_the_postcard_topic() {
+    let mut gw = Harness::start(Encoding::Postcard).await;
+
+    gw.knx.send("1/0/7", &[0x00]).await;
+
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, "knx/living-room/tv/state/postcard");
+    let state = SwitchState::decode(&payload, Encoding::Postcard).unwrap();
+    assert!(!state.is_on);
+}
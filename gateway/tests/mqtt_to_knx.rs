@@ -0,0 +1,155 @@
+//! MQTT → KNX flows through the bridge on the host harness
+
+mod harness;
+
+use harness::{address, Harness};
+use records::{
+    BlindCommand, BlindControl, CommandRejection, DimmerCommand, DimmerControl, Encoding,
+    RecordCodec, RejectReason, SwitchControl,
+};
+
+#[tokio::test]
+async fn switch_command_writes_to_the_named_actuator() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let command = SwitchControl::new(address("1/0/6"), true);
+
+    gw.mqtt
+        .send(
+            "knx/living-room/tv/control",
+            &command.encode(Encoding::Json).unwrap(),
+        )
+        .await;
+
+    assert_eq!(gw.knx.recv().await, ("1/0/6".to_string(), vec![0x01]));
+    gw.knx.assert_quiet().await;
+    gw.mqtt.assert_quiet().await;
+}
+
+#[tokio::test]
+async fn switch_command_off_the_allow_list_is_rejected() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let command = SwitchControl::new(address("1/0/9"), true);
+
+    gw.mqtt
+        .send(
+            "knx/living-room/tv/control",
+            &command.encode(Encoding::Json).unwrap(),
+        )
+        .await;
+
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, CommandRejection::MQTT_TOPIC);
+    let rejection = CommandRejection::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(rejection.address, address("1/0/9"));
+    assert_eq!(rejection.reason, RejectReason::UnknownAddress);
+    assert!(rejection.meta.is_some());
+    gw.knx.assert_quiet().await;
+}
+
+#[tokio::test]
+async fn dimmer_commands_pick_their_group_address() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let control = "knx/living-room/ceiling-light/control";
+
+    let brightness = DimmerControl::new(address("1/2/1"), DimmerCommand::Brightness(100));
+    gw.mqtt
+        .send(control, &brightness.encode(Encoding::Json).unwrap())
+        .await;
+    assert_eq!(gw.knx.recv().await, ("1/2/1".to_string(), vec![0xFF]));
+    gw.knx.assert_quiet().await;
+
+    let step = DimmerControl::new(
+        address("1/2/0"),
+        DimmerCommand::Step {
+            increase: true,
+            intervals: 1,
+        },
+    );
+    gw.mqtt
+        .send(control, &step.encode(Encoding::Json).unwrap())
+        .await;
+    assert_eq!(gw.knx.recv().await, ("1/2/0".to_string(), vec![0x09]));
+    gw.knx.assert_quiet().await;
+}
+
+#[tokio::test]
+async fn blind_position_goes_to_the_position_object() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let command = BlindControl::new(address("2/1/2"), BlindCommand::Position(100));
+
+    gw.mqtt
+        .send(
+            "knx/living-room/blind/control",
+            &command.encode(Encoding::Json).unwrap(),
+        )
+        .await;
+
+    assert_eq!(gw.knx.recv().await, ("2/1/2".to_string(), vec![0xFF]));
+    gw.knx.assert_quiet().await;
+}
+
+#[cfg(feature = "hvac")]
+#[tokio::test]
+async fn setpoint_is_encoded_as_dpt9() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let command = records::SetpointControl::new(address("3/1/2"), 21.5);
+
+    gw.mqtt
+        .send(
+            "knx/bedroom/setpoint/control",
+            &command.encode(Encoding::Json).unwrap(),
+        )
+        .await;
+
+    assert_eq!(gw.knx.recv().await, ("3/1/2".to_string(), vec![0x0C, 0x33]));
+}
+
+#[tokio::test]
+async fn malformed_commands_are_dropped() {
+    let mut gw = Harness::start(Encoding::Json).await;
+
+    gw.mqtt
+        .send("knx/living-room/tv/control", b"{\"is_on\":")
+        .await;
+
+    gw.knx.assert_quiet().await;
+    gw.mqtt.assert_quiet().await;
+}
+
+#[tokio::test]
+async fn postcard_commands_come_from_the_postcard_topic() {
+    let mut gw = Harness::start(Encoding::Postcard).await;
+    let command = SwitchControl::new(address("1/0/6"), false);
+
+    gw.mqtt
+        .send(
+            "knx/living-room/tv/control/postcard",
+            &command.encode(Encoding::Postcard).unwrap(),
+        )
+        .await;
+
+    assert_eq!(gw.knx.recv().await, ("1/0/6".to_string(), vec![0x00]));
+}
+
+#[cfg(feature = "color")]
+#[tokio::test]
+async fn color_command_off_the_allow_list_is_rejected() {
+    let mut gw = Harness::start(Encoding::Json).await;
+    let command =
+        records::ColorLightControl::hex(address("4/2/0"), records::Rgbw::rgb(255, 128, 0));
+
+    gw.mqtt
+        .send(
+            "knx/kitchen/rgb-strip/control",
+            &command.encode(Encoding::Json).unwrap(),
+        )
+        .await;
+
+    let (topic, payload) = gw.mqtt.recv().await;
+    assert_eq!(topic, CommandRejection::MQTT_TOPIC);
+    let rejection = CommandRejection::decode(&payload, Encoding::Json).unwrap();
+    assert_eq!(rejection.address, address("4/2/0"));
+    assert_eq!(rejection.reason, RejectReason::UnknownAddress);
+    assert!(rejection.meta.is_some());
+    gw.knx.assert_quiet().await;
+}
@@ -0,0 +1,223 @@
+//! The device tables `build.rs` generates against the registry they come from
+
+use gateway::config::*;
+use records::{DeviceKind, DeviceTopic, GroupAddress, Registry, Role};
+
+fn registry() -> Registry {
+    let path = std::env::var("DEVICE_REGISTRY")
+        .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/../registry.toml").to_string());
+    Registry::load(path).unwrap()
+}
+
+/// Checks that the registry lists `count` devices of a kind, so that neither
+/// side holds a device the other lacks
+fn assert_kind(registry: &Registry, kind: DeviceKind, count: usize) {
+    assert_eq!(registry.devices(kind).count(), count, "{kind:?} devices");
+}
+
+/// Checks that the registry has the device, of this kind, with exactly these
+/// datapoints and on the topics the gateway uses
+fn assert_device(
+    registry: &Registry,
+    kind: DeviceKind,
+    device: DeviceTopic,
+    roles: &[(Role, GroupAddress)],
+) {
+    let entry = registry
+        .devices(kind)
+        .find(|entry| entry.topic() == device)
+        .unwrap_or_else(|| panic!("{} missing from the registry", device.id()));
+    let mut datapoints: Vec<_> = entry
+        .datapoints
+        .iter()
+        .map(|datapoint| (datapoint.role, datapoint.address))
+        .collect();
+    let mut expected = roles.to_vec();
+    datapoints.sort();
+    expected.sort();
+    assert_eq!(datapoints, expected, "{}", device.id());
+    assert_eq!(entry.state_topic(), device.state());
+    if let Some(control) = entry.control_topic() {
+        assert_eq!(control, device.control());
+    }
+}
+
+fn assert_sensors(registry: &Registry, kind: DeviceKind, sensors: &[Sensor]) {
+    assert_kind(registry, kind, sensors.len());
+    for sensor in sensors {
+        assert_device(
+            registry,
+            kind,
+            sensor.device,
+            &[(Role::State, sensor.state)],
+        );
+    }
+}
+
+fn assert_actuators(registry: &Registry, kind: DeviceKind, actuators: &[Actuator]) {
+    assert_kind(registry, kind, actuators.len());
+    for actuator in actuators {
+        assert_device(
+            registry,
+            kind,
+            actuator.device,
+            &[
+                (Role::State, actuator.state),
+                (Role::Control, actuator.control),
+            ],
+        );
+    }
+}
+
+#[test]
+fn switches_and_temperature_match_the_registry() {
+    let registry = registry();
+
+    assert_actuators(&registry, DeviceKind::Switch, &SWITCHES);
+    assert_sensors(&registry, DeviceKind::Temperature, &TEMPERATURE_SENSORS);
+}
+
+#[test]
+fn dimmers_and_blinds_match_the_registry() {
+    let registry = registry();
+
+    assert_kind(&registry, DeviceKind::Dimmer, DIMMERS.len());
+    for dimmer in DIMMERS {
+        assert_device(
+            &registry,
+            DeviceKind::Dimmer,
+            dimmer.device,
+            &[
+                (Role::State, dimmer.state),
+                (Role::Value, dimmer.value),
+                (Role::Step, dimmer.step),
+            ],
+        );
+    }
+    assert_kind(&registry, DeviceKind::Blind, BLINDS.len());
+    for blind in BLINDS {
+        assert_device(
+            &registry,
+            DeviceKind::Blind,
+            blind.device,
+            &[
+                (Role::Move, blind.up_down),
+                (Role::Step, blind.step),
+                (Role::Position, blind.position),
+                (Role::SlatAngle, blind.slat_angle),
+                (Role::PositionState, blind.position_state),
+                (Role::SlatAngleState, blind.slat_angle_state),
+                (Role::Moving, blind.moving),
+            ],
+        );
+    }
+}
+
+#[cfg(feature = "sensors")]
+#[test]
+fn weather_and_room_sensors_match_the_registry() {
+    let registry = registry();
+
+    assert_sensors(&registry, DeviceKind::Illuminance, &ILLUMINANCE_SENSORS);
+    assert_sensors(&registry, DeviceKind::WindSpeed, &WIND_SENSORS);
+    assert_sensors(&registry, DeviceKind::Humidity, &HUMIDITY_SENSORS);
+    assert_sensors(&registry, DeviceKind::Co2, &CO2_SENSORS);
+}
+
+#[cfg(feature = "energy")]
+#[test]
+fn meters_match_the_registry() {
+    let registry = registry();
+
+    assert_sensors(&registry, DeviceKind::EnergyCounter, &ENERGY_METERS);
+    assert_sensors(&registry, DeviceKind::Power, &POWER_METERS);
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn color_lights_match_the_registry() {
+    let registry = registry();
+
+    assert_kind(&registry, DeviceKind::ColorLight, COLOR_LIGHTS.len());
+    for light in COLOR_LIGHTS {
+        assert_device(
+            &registry,
+            DeviceKind::ColorLight,
+            light.device,
+            &[(Role::State, light.state), (Role::Control, light.control)],
+        );
+        let entry = registry
+            .device_at(DeviceKind::ColorLight, light.control)
+            .unwrap();
+        let dpt = entry.datapoints[0].dpt;
+        assert_eq!(
+            dpt.main,
+            if light.white { 251 } else { 232 },
+            "{}",
+            entry.id()
+        );
+    }
+}
+
+#[cfg(feature = "scene")]
+#[test]
+fn scenes_match_the_registry() {
+    assert_actuators(&registry(), DeviceKind::Scene, &SCENES);
+}
+
+#[cfg(feature = "clock")]
+#[test]
+fn clocks_match_the_registry() {
+    let registry = registry();
+
+    assert_kind(&registry, DeviceKind::Clock, CLOCKS.len());
+    for clock in CLOCKS {
+        assert_device(
+            &registry,
+            DeviceKind::Clock,
+            clock.device,
+            &[
+                (Role::Time, clock.time),
+                (Role::Date, clock.date),
+                (Role::DateTime, clock.datetime),
+            ],
+        );
+    }
+}
+
+#[cfg(feature = "text")]
+#[test]
+fn displays_and_panels_match_the_registry() {
+    let registry = registry();
+
+    assert_kind(
+        &registry,
+        DeviceKind::Text,
+        TEXT_DISPLAYS.len() + TEXT_PANELS.len(),
+    );
+    for display in TEXT_DISPLAYS {
+        assert_device(
+            &registry,
+            DeviceKind::Text,
+            display.device,
+            &[(Role::Control, display.control)],
+        );
+    }
+    for panel in TEXT_PANELS {
+        assert_device(
+            &registry,
+            DeviceKind::Text,
+            panel.device,
+            &[(Role::State, panel.state)],
+        );
+    }
+}
+
+#[cfg(feature = "hvac")]
+#[test]
+fn thermostats_and_setpoints_match_the_registry() {
+    let registry = registry();
+
+    assert_actuators(&registry, DeviceKind::HvacMode, &THERMOSTATS);
+    assert_actuators(&registry, DeviceKind::Setpoint, &SETPOINTS);
+}
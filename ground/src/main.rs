@@ -9,25 +9,30 @@
 //! - Publishes device states to MQTT broker
 //! - Receives commands from MQTT and forwards to KNX bus
 //! - Runs on STM32H563ZI microcontroller with Embassy async runtime
+//!
+//! The links themselves live in the `gateway` crate, which also runs on a PC
+//! against fake connectors; this binary sets up the hardware, the network and
+//! the Embassy buffers for them.
 
 extern crate alloc;
 
-use aimdb_core::AimDbBuilder;
-use aimdb_embassy_adapter::{
-    EmbassyAdapter, EmbassyBufferType, EmbassyRecordRegistrarExt, EmbassyRecordRegistrarExtCustom,
-};
+use aimdb_core::{AimDbBuilder, RecordRegistrar};
+use aimdb_embassy_adapter::{EmbassyAdapter, EmbassyBufferType, EmbassyRecordRegistrarExtCustom};
 use aimdb_knx_connector::embassy_client::KnxConnectorBuilder;
 use aimdb_mqtt_connector::embassy_client::MqttConnectorBuilder;
 use defmt::*;
 use embassy_executor::Spawner;
 use embassy_net::StackResources;
 use embassy_stm32::eth::{Ethernet, GenericPhy, PacketQueue};
+use embassy_stm32::flash::{Blocking, Flash};
 use embassy_stm32::gpio::{Level, Output, Speed};
 use embassy_stm32::peripherals::ETH;
 use embassy_stm32::rng::Rng;
 use embassy_stm32::{Config, bind_interrupts, eth, peripherals, rng};
-use embassy_time::{Duration, Timer};
-use records::{SwitchControl, SwitchState, Temperature};
+use embassy_time::{Duration, Instant, Timer};
+use gateway::config::*;
+use gateway::{Bridge, BufferType, Buffers, Dispatcher};
+use records::{CommandRejection, DeviceTopic, GroupAddress};
 use static_cell::StaticCell;
 use {defmt_rtt as _, panic_probe as _};
 
@@ -50,15 +55,87 @@ async fn net_task(mut runner: embassy_net::Runner<'static, Device>) -> ! {
     runner.run().await
 }
 
-/// KNX/IP gateway IP address
-const KNX_GATEWAY_IP: &str = "192.168.1.19";
-/// KNX/IP gateway port
-const KNX_GATEWAY_PORT: u16 = 3671;
+/// Embassy buffers, sized by the bridge for the task pool
+struct EmbassyBuffers;
+
+impl Buffers<EmbassyAdapter> for EmbassyBuffers {
+    fn buffer_sized<'a, T, const CAP: usize, const CONSUMERS: usize>(
+        reg: &'a mut RecordRegistrar<'a, T, EmbassyAdapter>,
+        buffer_type: BufferType,
+    ) -> &'a mut RecordRegistrar<'a, T, EmbassyAdapter>
+    where
+        T: Send + Sync + Clone + core::fmt::Debug + 'static,
+    {
+        reg.buffer_sized::<CAP, CONSUMERS>(match buffer_type {
+            BufferType::SpmcRing => EmbassyBufferType::SpmcRing,
+            BufferType::SingleLatest => EmbassyBufferType::SingleLatest,
+        })
+    }
+}
+
+/// Flash offset of the boot counter: the last 8 KB sector of bank 2, far
+/// beyond the firmware
+const BOOT_COUNTER_OFFSET: u32 = 0x1F_E000;
+/// Size of that sector, erased before each write
+const BOOT_COUNTER_SECTOR: u32 = 8 * 1024;
+
+/// Count this boot in flash and return its number
+///
+/// The counter survives resets and power loss, so consumers can tell the
+/// current boot from an earlier one whose retained values arrive late.
+fn next_boot(flash: &mut Flash<'_, Blocking>) -> u32 {
+    let mut stored = [0; 4];
+    let previous = match flash.blocking_read(BOOT_COUNTER_OFFSET, &mut stored) {
+        Ok(()) if stored != [0xFF; 4] => u32::from_le_bytes(stored),
+        _ => 0, // Erased: first boot
+    };
+    let boot = previous + 1;
+
+    // The H5 programs flash in 16-byte quad-words
+    let mut word = [0xFF; 16];
+    word[..4].copy_from_slice(&boot.to_le_bytes());
+    if let Err(e) = flash
+        .blocking_erase(
+            BOOT_COUNTER_OFFSET,
+            BOOT_COUNTER_OFFSET + BOOT_COUNTER_SECTOR,
+        )
+        .and_then(|()| flash.blocking_write(BOOT_COUNTER_OFFSET, &word))
+    {
+        warn!("Failed to store the boot counter: {:?}", e);
+    }
+    boot
+}
+
+/// Milliseconds since boot, stamped into the records
+fn uptime_ms() -> u64 {
+    Instant::now().as_millis()
+}
 
-/// MQTT broker IP address
-const MQTT_BROKER_IP: &str = "192.168.1.7";
-/// MQTT broker port
-const MQTT_BROKER_PORT: u16 = 1883;
+/// Log a device's state link from its status group address
+fn log_state(address: GroupAddress, device: DeviceTopic, dpt: &str) {
+    info!(
+        "     - knx://{} → {} (DPT {})",
+        address.to_heapless().as_str(),
+        device.state().as_str(),
+        dpt
+    );
+}
+
+/// Log a device's command link to its group address
+fn log_control(device: DeviceTopic, address: GroupAddress, dpt: &str) {
+    info!(
+        "     - {} → knx://{} (JSON → DPT {})",
+        device.control().as_str(),
+        address.to_heapless().as_str(),
+        dpt
+    );
+}
+
+/// DPT of an LED strip's color objects
+#[cfg(feature = "color")]
+fn color_dpt(white: bool) -> &'static str {
+    if white { "251.600" } else { "232.600" }
+}
 
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
@@ -116,6 +193,9 @@ async fn main(spawner: Spawner) {
     rng.fill_bytes(&mut seed);
     let seed = u64::from_le_bytes(seed);
 
+    let boot_id = next_boot(&mut Flash::new_blocking(p.FLASH));
+    info!("🔢 Boot #{}", boot_id);
+
     info!("🔧 Initializing Ethernet...");
 
     // MAC address for this device
@@ -180,77 +260,158 @@ async fn main(spawner: Spawner) {
     info!("📋 Configuring connectors...");
     info!("   KNX Gateway: {}", gateway_url.as_str());
     info!("   MQTT Broker: {}", broker_url.as_str());
+    info!("   MQTT Encoding: {}", MQTT_ENCODING);
 
+    let bridge = Bridge::new(MQTT_ENCODING, boot_id, uptime_ms);
     let mut builder = AimDbBuilder::new()
         .runtime(runtime.clone())
-        .with_connector(KnxConnectorBuilder::new(&gateway_url))
-        .with_connector(MqttConnectorBuilder::new(&broker_url).with_client_id("knx-gateway-001"));
-
-    // Configure SwitchState record (inbound: KNX → AimDB, outbound: AimDB → MQTT)
-    builder.configure::<SwitchState>(|reg| {
-        reg.buffer_sized::<8, 2>(EmbassyBufferType::SingleLatest)
-            .tap(records::switch::monitors::state_monitor)
-            // Subscribe from KNX group address 1/0/7 (switch monitoring)
-            .link_from("knx://1/0/7")
-            .with_deserializer(|data: &[u8]| records::switch::knx::from_knx(data, "1/0/7"))
-            .finish()
-            // Publish to MQTT as JSON
-            .link_to(&alloc::format!("mqtt://{}", SwitchState::MQTT_TOPIC))
-            .with_serializer(|state: &SwitchState| {
-                records::switch::json::serialize_state(state)
-                    .map_err(|_| aimdb_core::connector::SerializeError::InvalidData)
-            })
-            .finish();
-    });
-
-    // Configure Temperature record (inbound: KNX → AimDB, outbound: AimDB → MQTT)
-    builder.configure::<Temperature>(|reg| {
-        reg.buffer_sized::<8, 2>(EmbassyBufferType::SingleLatest)
-            .tap(records::temperature::monitors::monitor)
-            // Subscribe from KNX temperature sensor (group address 9/1/0)
-            .link_from("knx://9/1/0")
-            .with_deserializer(|data: &[u8]| records::temperature::knx::from_knx(data, "9/1/0"))
-            .finish()
-            // Publish to MQTT as JSON
-            .link_to(&alloc::format!("mqtt://{}", Temperature::MQTT_TOPIC))
-            .with_serializer(|temp: &Temperature| {
-                records::temperature::json::serialize(temp)
-                    .map_err(|_| aimdb_core::connector::SerializeError::InvalidData)
-            })
-            .finish();
-    });
-
-    // Configure SwitchControl record (inbound: MQTT → AimDB, outbound: AimDB → KNX)
-    builder.configure::<SwitchControl>(|reg| {
-        reg.buffer_sized::<8, 2>(EmbassyBufferType::SingleLatest)
-            .tap(records::switch::monitors::control_monitor)
-            // Subscribe from MQTT commands
-            .link_from(&alloc::format!("mqtt://{}", SwitchControl::MQTT_TOPIC))
-            .with_deserializer(|data: &[u8]| records::switch::json::deserialize_control(data))
-            .finish()
-            // Publish to KNX group address 1/0/6 (switch control)
-            .link_to("knx://1/0/6")
-            .with_serializer(|control: &SwitchControl| {
-                records::switch::knx::to_knx(control)
-                    .map_err(|_| aimdb_core::connector::SerializeError::InvalidData)
-            })
-            .finish();
-    });
+        .with_connector(Dispatcher::new(
+            KnxConnectorBuilder::new(&gateway_url),
+            bridge.knx_dispatch(),
+        ))
+        .with_connector(Dispatcher::new(
+            MqttConnectorBuilder::new(&broker_url).with_client_id(GATEWAY_ID),
+            bridge.mqtt_dispatch(),
+        ));
+
+    // Records are not tapped with monitors: every tap and every dispatch
+    // occupies a slot in the 32-task Embassy pool, and the dispatches need them all.
+    // Decoded values can be watched on MQTT instead (`mosquitto_sub -t 'knx/#'`).
+    // Optional device groups are selected with Cargo features (see Cargo.toml)
+    // so that the enabled dispatches fit into the pool.
+    bridge.configure::<_, EmbassyBuffers>(&mut builder);
 
     info!("✅ Database configured with KNX and MQTT bridge:");
     info!("   KNX INBOUND (KNX → AimDB → MQTT):");
-    info!(
-        "     - knx://1/0/7 → {} (DPT 1.001)",
-        SwitchState::MQTT_TOPIC
-    );
-    info!(
-        "     - knx://9/1/0 → {} (DPT 9.001)",
-        Temperature::MQTT_TOPIC
-    );
+    for switch in SWITCHES {
+        log_state(switch.state, switch.device, "1.001");
+    }
+    for sensor in TEMPERATURE_SENSORS {
+        log_state(sensor.state, sensor.device, "9.001");
+    }
+    for dimmer in DIMMERS {
+        log_state(dimmer.state, dimmer.device, "5.001");
+    }
+    for blind in BLINDS {
+        info!(
+            "     - knx://{} + {} + {} → {} (DPT 5.001 / 1.011)",
+            blind.position_state.to_heapless().as_str(),
+            blind.slat_angle_state.to_heapless().as_str(),
+            blind.moving.to_heapless().as_str(),
+            blind.device.state().as_str()
+        );
+    }
+    #[cfg(feature = "sensors")]
+    {
+        for sensor in ILLUMINANCE_SENSORS {
+            log_state(sensor.state, sensor.device, "9.004");
+        }
+        for sensor in WIND_SENSORS {
+            log_state(sensor.state, sensor.device, "9.005");
+        }
+        for sensor in HUMIDITY_SENSORS {
+            log_state(sensor.state, sensor.device, "9.007");
+        }
+        for sensor in CO2_SENSORS {
+            log_state(sensor.state, sensor.device, "9.008");
+        }
+    }
+    #[cfg(feature = "energy")]
+    {
+        for meter in ENERGY_METERS {
+            log_state(meter.state, meter.device, "13.010");
+        }
+        for meter in POWER_METERS {
+            log_state(meter.state, meter.device, "14.056");
+        }
+    }
+    #[cfg(feature = "color")]
+    for light in COLOR_LIGHTS {
+        log_state(light.state, light.device, color_dpt(light.white));
+    }
+    #[cfg(feature = "scene")]
+    for scene in SCENES {
+        log_state(scene.state, scene.device, "18.001");
+    }
+    #[cfg(feature = "hvac")]
+    {
+        for thermostat in THERMOSTATS {
+            log_state(thermostat.state, thermostat.device, "20.102");
+        }
+        for setpoint in SETPOINTS {
+            log_state(setpoint.state, setpoint.device, "9.001");
+        }
+    }
+    #[cfg(feature = "clock")]
+    for clock in CLOCKS {
+        info!(
+            "     - knx://{} + {} + {} → {} (DPT 10.001 / 11.001 / 19.001)",
+            clock.time.to_heapless().as_str(),
+            clock.date.to_heapless().as_str(),
+            clock.datetime.to_heapless().as_str(),
+            clock.device.state().as_str()
+        );
+    }
+    #[cfg(feature = "text")]
+    for panel in TEXT_PANELS {
+        log_state(panel.state, panel.device, "16.000");
+    }
     info!("   MQTT INBOUND (MQTT → AimDB → KNX):");
+    for switch in SWITCHES {
+        log_control(switch.device, switch.control, "1.001");
+    }
+    for dimmer in DIMMERS {
+        info!(
+            "     - {} → knx://{} (JSON → DPT 5.001) / knx://{} (JSON → DPT 3.007)",
+            dimmer.device.control().as_str(),
+            dimmer.value.to_heapless().as_str(),
+            dimmer.step.to_heapless().as_str()
+        );
+    }
+    for blind in BLINDS {
+        info!(
+            "     - {} → knx://{} + {} + {} + {} (JSON → DPT 1.008 / 1.007 / 5.001)",
+            blind.device.control().as_str(),
+            blind.up_down.to_heapless().as_str(),
+            blind.step.to_heapless().as_str(),
+            blind.position.to_heapless().as_str(),
+            blind.slat_angle.to_heapless().as_str()
+        );
+    }
+    #[cfg(feature = "color")]
+    for light in COLOR_LIGHTS {
+        log_control(light.device, light.control, color_dpt(light.white));
+    }
+    #[cfg(feature = "scene")]
+    for scene in SCENES {
+        log_control(scene.device, scene.control, "18.001");
+    }
+    #[cfg(feature = "hvac")]
+    {
+        for thermostat in THERMOSTATS {
+            log_control(thermostat.device, thermostat.control, "20.102");
+        }
+        for setpoint in SETPOINTS {
+            log_control(setpoint.device, setpoint.control, "9.001");
+        }
+    }
+    #[cfg(feature = "clock")]
+    for clock in CLOCKS {
+        info!(
+            "     - {} → knx://{} + {} + {} (JSON → DPT 10.001 / 11.001 / 19.001)",
+            clock.device.control().as_str(),
+            clock.time.to_heapless().as_str(),
+            clock.date.to_heapless().as_str(),
+            clock.datetime.to_heapless().as_str()
+        );
+    }
+    #[cfg(feature = "text")]
+    for display in TEXT_DISPLAYS {
+        log_control(display.device, display.control, "16.000 / 16.001");
+    }
     info!(
-        "     - {} → knx://1/0/6 (JSON → DPT 1.001)",
-        SwitchControl::MQTT_TOPIC
+        "     - commands off the allow-lists → {}",
+        CommandRejection::MQTT_TOPIC
     );
     info!("   KNX Gateway: {}:{}", KNX_GATEWAY_IP, KNX_GATEWAY_PORT);
     info!("   MQTT Broker: {}:{}", MQTT_BROKER_IP, MQTT_BROKER_PORT);
@@ -261,10 +422,14 @@ async fn main(spawner: Spawner) {
         MQTT_BROKER_IP
     );
     info!(
-        "   Control: mosquitto_pub -h {} -t 'knx/lights/control' \\",
-        MQTT_BROKER_IP
+        "   Control: mosquitto_pub -h {} -t '{}' \\",
+        MQTT_BROKER_IP,
+        SWITCHES[0].device.control().as_str()
+    );
+    info!(
+        "            -m '{{\"address\":\"{}\",\"is_on\":true}}'",
+        SWITCHES[0].control.to_heapless().as_str()
     );
-    info!("            -m '{{\"group_address\":\"1/0/6\",\"is_on\":true}}'");
     info!("");
 
     info!("🔨 Building database...");
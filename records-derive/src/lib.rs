@@ -0,0 +1,215 @@
+//! Derive Macro for KNX Records
+//!
+//! Provides `#[derive(KnxRecord)]`, re-exported by `records` (which declares
+//! `PowerReading` with it). For a struct with an `address: GroupAddress`
+//! field and one value field it generates:
+//! - A `DPT` constant and a `new(address, value)` constructor
+//! - `records::RecordCodec` (JSON, via `records::impl_record_codec!`)
+//! - `records::KnxRecord`, whose provided methods give the KNX codec and
+//!   `records::record::monitors::monitor` the monitor
+//!
+//! State records may add a `meta: Option<RecordMeta>` field (with
+//! `#[serde(default)]`); it starts as `None` and implements
+//! `records::Timestamped` so the gateway can stamp it.
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, KnxRecord)]
+//! #[knx(dpt = "9.001")]
+//! pub struct OutdoorTemperature {
+//!     pub address: GroupAddress,
+//!     pub celsius: f32,
+//! }
+//! ```
+//!
+//! MQTT topics belong to devices rather than record types, so the gateway
+//! names them in its device tables.
+//!
+//! Attributes:
+//! - `dpt` (required): datapoint type, e.g. `"1.001"`, `"5.001"`, `"9.004"`
+//! - `json_buffer` (optional): no_std JSON buffer size, default 128 bytes
+//!   (288 with a `meta` field)
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitInt, LitStr};
+
+/// Derive `records::KnxRecord`, `records::RecordCodec` and a constructor
+#[proc_macro_derive(KnxRecord, attributes(knx))]
+pub fn derive_knx_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Options given in `#[knx(...)]`
+struct KnxAttrs {
+    dpt: LitStr,
+    json_buffer: Option<LitInt>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = parse_attrs(&input)?;
+    let codec = dpt_codec(&attrs.dpt)?;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "KnxRecord cannot be derived for generic structs",
+        ));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "KnxRecord can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "KnxRecord requires named fields",
+        ));
+    };
+
+    let mut has_address = false;
+    let mut has_meta = false;
+    let mut values = Vec::new();
+    for field in &fields.named {
+        match field.ident.as_ref() {
+            Some(ident) if ident == "address" => has_address = true,
+            Some(ident) if ident == "meta" => has_meta = true,
+            _ => values.push(field),
+        }
+    }
+    if !has_address {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "KnxRecord requires an `address: GroupAddress` field",
+        ));
+    }
+    let [value] = values.as_slice() else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "KnxRecord requires exactly one value field besides `address` and `meta`",
+        ));
+    };
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let value_ident = &value.ident;
+    let value_ty = &value.ty;
+    let dpt = &attrs.dpt;
+    let json_buffer = match &attrs.json_buffer {
+        Some(size) => quote!(#size),
+        None if has_meta => quote!(::records::codec::STATE_JSON_BUFFER_SIZE),
+        None => quote!(::records::codec::DEFAULT_JSON_BUFFER_SIZE),
+    };
+    let (meta_init, timestamped) = if has_meta {
+        (
+            quote!(meta: None,),
+            quote!(::records::impl_timestamped!(#name);),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+    let new_doc = format!("Create a new {}", name_str);
+
+    Ok(quote! {
+        impl #name {
+            /// KNX datapoint type identifier
+            pub const DPT: &'static str = #dpt;
+
+            #[doc = #new_doc]
+            pub fn new(address: ::records::GroupAddress, #value_ident: #value_ty) -> Self {
+                Self {
+                    address,
+                    #value_ident,
+                    #meta_init
+                }
+            }
+        }
+
+        ::records::impl_record_codec!(#name => #json_buffer);
+        #timestamped
+
+        impl ::records::KnxRecord for #name {
+            type Dpt = #codec;
+            const NAME: &'static str = #name_str;
+            const DPT: &'static str = #dpt;
+
+            fn from_value(address: ::records::GroupAddress, value: #value_ty) -> Self {
+                Self::new(address, value)
+            }
+
+            fn address(&self) -> ::records::GroupAddress {
+                self.address
+            }
+
+            fn value(&self) -> #value_ty {
+                self.#value_ident
+            }
+        }
+    })
+}
+
+fn parse_attrs(input: &DeriveInput) -> syn::Result<KnxAttrs> {
+    let mut dpt = None;
+    let mut json_buffer = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("knx")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("dpt") {
+                dpt = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("json_buffer") {
+                json_buffer = Some(meta.value()?.parse::<LitInt>()?);
+            } else {
+                return Err(meta.error("expected `dpt` or `json_buffer`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |key: &str| {
+        Error::new(
+            Span::call_site(),
+            format!("KnxRecord requires #[knx({} = \"...\")]", key),
+        )
+    };
+
+    Ok(KnxAttrs {
+        dpt: dpt.ok_or_else(|| missing("dpt"))?,
+        json_buffer,
+    })
+}
+
+/// Map a DPT identifier onto its `records::dpt` codec
+fn dpt_codec(dpt: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let value = dpt.value();
+    let invalid = || Error::new_spanned(dpt, format!("malformed DPT identifier {:?}", value));
+
+    let (main, sub) = value.split_once('.').ok_or_else(invalid)?;
+    if sub.len() != 3 || !sub.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let codec = match (main, sub) {
+        ("1", _) => "Boolean",
+        ("5", "001") => "Percent",
+        ("5", "004" | "005" | "006" | "010") => "Unsigned8",
+        ("7", _) => "Unsigned16",
+        ("9", _) => "Float16",
+        ("13", _) => "Signed32",
+        ("14", _) => "Float32",
+        _ => {
+            return Err(Error::new_spanned(
+                dpt,
+                format!("DPT {} is not supported by KnxRecord", value),
+            ))
+        }
+    };
+
+    let codec = Ident::new(codec, Span::call_site());
+    Ok(quote!(::records::dpt::#codec))
+}
@@ -0,0 +1,452 @@
+//! KNX Group Addresses
+//!
+//! Contains the typed group address used by every record:
+//! - GroupAddress: 16-bit KNX group address with its notation
+//! - GroupAddressError: Parse/validation failures
+//! - IndividualAddress: Physical address of a KNX device (`area.line.device`)
+//!
+//! Three notations are supported, matching ETS:
+//! - 3-level: `main/middle/sub` (5/3/8 bits, e.g. "1/0/7")
+//! - 2-level: `main/sub` (5/11 bits, e.g. "1/7")
+//! - free: raw 16-bit value (e.g. "2055")
+//!
+//! This module is no_std and does not allocate.
+
+use core::fmt;
+use core::str::FromStr;
+use heapless::String as HeaplessString;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Notation a group address is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressStyle {
+    /// `main/middle/sub` (e.g. "1/0/7")
+    ThreeLevel,
+    /// `main/sub` (e.g. "1/7")
+    TwoLevel,
+    /// Raw 16-bit value (e.g. "2055")
+    Free,
+}
+
+/// KNX group address
+///
+/// Stores the raw 16-bit address together with the notation it was written in,
+/// so that it serializes back to the same string form. Equality and hashing only
+/// consider the raw address: "1/0/7", "1/7" and "2055" are the same group.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupAddress {
+    raw: u16,
+    style: AddressStyle,
+}
+
+/// Error returned when a group address cannot be parsed or is out of range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GroupAddressError {
+    /// Not a `main/middle/sub`, `main/sub` or plain 16-bit number string
+    InvalidFormat,
+    /// Main group is above 31
+    MainOutOfRange,
+    /// Middle group is above 7 (3-level notation)
+    MiddleOutOfRange,
+    /// Sub group is above 255 (3-level) or 2047 (2-level)
+    SubOutOfRange,
+}
+
+/// KNX individual (physical) address of a device, e.g. "1.1.25"
+///
+/// 4-bit area, 4-bit line and 8-bit device, serialized as `area.line.device`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IndividualAddress {
+    raw: u16,
+}
+
+/// Error returned when an individual address cannot be parsed or is out of range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IndividualAddressError {
+    /// Not an `area.line.device` string
+    InvalidFormat,
+    /// Area is above 15
+    AreaOutOfRange,
+    /// Line is above 15
+    LineOutOfRange,
+    /// Device is above 255
+    DeviceOutOfRange,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl GroupAddress {
+    /// Maximum length of a formatted group address ("31/7/255", "65535", ...)
+    pub const MAX_LEN: usize = 16;
+
+    /// Create a 3-level group address (`main/middle/sub`)
+    pub const fn three_level(main: u8, middle: u8, sub: u8) -> Result<Self, GroupAddressError> {
+        if main > 31 {
+            return Err(GroupAddressError::MainOutOfRange);
+        }
+        if middle > 7 {
+            return Err(GroupAddressError::MiddleOutOfRange);
+        }
+        Ok(Self {
+            raw: ((main as u16) << 11) | ((middle as u16) << 8) | sub as u16,
+            style: AddressStyle::ThreeLevel,
+        })
+    }
+
+    /// Create a 3-level group address, panicking if a part is out of range
+    ///
+    /// Intended for constants, where an invalid address becomes a compile error:
+    /// `const TV: GroupAddress = GroupAddress::new(1, 0, 7);`
+    pub const fn new(main: u8, middle: u8, sub: u8) -> Self {
+        match Self::three_level(main, middle, sub) {
+            Ok(address) => address,
+            Err(_) => panic!("KNX group address out of range"),
+        }
+    }
+
+    /// Create a 2-level group address (`main/sub`)
+    pub const fn two_level(main: u8, sub: u16) -> Result<Self, GroupAddressError> {
+        if main > 31 {
+            return Err(GroupAddressError::MainOutOfRange);
+        }
+        if sub > 2047 {
+            return Err(GroupAddressError::SubOutOfRange);
+        }
+        Ok(Self {
+            raw: ((main as u16) << 11) | sub,
+            style: AddressStyle::TwoLevel,
+        })
+    }
+
+    /// Create a group address from its raw 16-bit value (free notation)
+    pub const fn from_raw(raw: u16) -> Self {
+        Self {
+            raw,
+            style: AddressStyle::Free,
+        }
+    }
+
+    /// Raw 16-bit address as sent on the bus
+    pub const fn raw(&self) -> u16 {
+        self.raw
+    }
+
+    /// Notation used when formatting
+    pub const fn style(&self) -> AddressStyle {
+        self.style
+    }
+
+    /// Same address rendered in another notation
+    pub const fn with_style(self, style: AddressStyle) -> Self {
+        Self {
+            raw: self.raw,
+            style,
+        }
+    }
+
+    /// Main group (upper 5 bits)
+    pub const fn main(&self) -> u8 {
+        (self.raw >> 11) as u8
+    }
+
+    /// Middle group (3-level notation)
+    pub const fn middle(&self) -> u8 {
+        ((self.raw >> 8) & 0x07) as u8
+    }
+
+    /// Sub group (8 bits in 3-level, 11 bits in 2-level notation)
+    pub const fn sub(&self) -> u16 {
+        match self.style {
+            AddressStyle::TwoLevel => self.raw & 0x07FF,
+            _ => self.raw & 0x00FF,
+        }
+    }
+
+    /// Format into a fixed-size string without allocating
+    pub fn to_heapless(&self) -> HeaplessString<{ Self::MAX_LEN }> {
+        use core::fmt::Write;
+        let mut s = HeaplessString::new();
+        // Longest form is "31/7/255" or "65535", always fits
+        let _ = write!(s, "{}", self);
+        s
+    }
+}
+
+impl IndividualAddress {
+    /// Maximum length of a formatted individual address ("15.15.255")
+    pub const MAX_LEN: usize = 9;
+
+    /// Create an individual address (`area.line.device`)
+    pub const fn new(area: u8, line: u8, device: u8) -> Result<Self, IndividualAddressError> {
+        if area > 15 {
+            return Err(IndividualAddressError::AreaOutOfRange);
+        }
+        if line > 15 {
+            return Err(IndividualAddressError::LineOutOfRange);
+        }
+        Ok(Self {
+            raw: ((area as u16) << 12) | ((line as u16) << 8) | device as u16,
+        })
+    }
+
+    /// Create an individual address from its raw 16-bit value
+    pub const fn from_raw(raw: u16) -> Self {
+        Self { raw }
+    }
+
+    /// Raw 16-bit address as sent on the bus
+    pub const fn raw(&self) -> u16 {
+        self.raw
+    }
+
+    /// Area (upper 4 bits)
+    pub const fn area(&self) -> u8 {
+        (self.raw >> 12) as u8
+    }
+
+    /// Line (middle 4 bits)
+    pub const fn line(&self) -> u8 {
+        ((self.raw >> 8) & 0x0F) as u8
+    }
+
+    /// Device (lower 8 bits)
+    pub const fn device(&self) -> u8 {
+        (self.raw & 0xFF) as u8
+    }
+
+    /// Format into a fixed-size string without allocating
+    pub fn to_heapless(&self) -> HeaplessString<{ Self::MAX_LEN }> {
+        use core::fmt::Write;
+        let mut s = HeaplessString::new();
+        // Longest form is "15.15.255", always fits
+        let _ = write!(s, "{}", self);
+        s
+    }
+}
+
+// ============================================================================
+// PARSING / FORMATTING
+// ============================================================================
+
+/// Parse one `/`-separated part as a decimal number
+///
+/// Parts too large for `u32` saturate, so that callers range-check every
+/// number and report which part is out of range.
+fn parse_part(part: &str) -> Result<u32, GroupAddressError> {
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(GroupAddressError::InvalidFormat);
+    }
+    Ok(part.parse::<u32>().unwrap_or(u32::MAX))
+}
+
+impl FromStr for GroupAddress {
+    type Err = GroupAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        let first = parts.next().ok_or(GroupAddressError::InvalidFormat)?;
+        let second = parts.next();
+        let third = parts.next();
+        if parts.next().is_some() {
+            return Err(GroupAddressError::InvalidFormat);
+        }
+
+        match (second, third) {
+            (None, _) => u16::try_from(parse_part(first)?)
+                .map(Self::from_raw)
+                .map_err(|_| GroupAddressError::InvalidFormat),
+            (Some(sub), None) => {
+                let main = parse_part(first)?;
+                let sub = parse_part(sub)?;
+                if main > 31 {
+                    return Err(GroupAddressError::MainOutOfRange);
+                }
+                if sub > 2047 {
+                    return Err(GroupAddressError::SubOutOfRange);
+                }
+                Self::two_level(main as u8, sub as u16)
+            }
+            (Some(middle), Some(sub)) => {
+                let main = parse_part(first)?;
+                let middle = parse_part(middle)?;
+                let sub = parse_part(sub)?;
+                if main > 31 {
+                    return Err(GroupAddressError::MainOutOfRange);
+                }
+                if middle > 7 {
+                    return Err(GroupAddressError::MiddleOutOfRange);
+                }
+                if sub > 255 {
+                    return Err(GroupAddressError::SubOutOfRange);
+                }
+                Self::three_level(main as u8, middle as u8, sub as u8)
+            }
+        }
+    }
+}
+
+impl fmt::Display for GroupAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            AddressStyle::ThreeLevel => {
+                write!(f, "{}/{}/{}", self.main(), self.middle(), self.sub())
+            }
+            AddressStyle::TwoLevel => write!(f, "{}/{}", self.main(), self.sub()),
+            AddressStyle::Free => write!(f, "{}", self.raw),
+        }
+    }
+}
+
+impl fmt::Display for GroupAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "invalid group address format"),
+            Self::MainOutOfRange => write!(f, "main group out of range (0-31)"),
+            Self::MiddleOutOfRange => write!(f, "middle group out of range (0-7)"),
+            Self::SubOutOfRange => write!(f, "sub group out of range"),
+        }
+    }
+}
+
+impl core::error::Error for GroupAddressError {}
+
+impl FromStr for IndividualAddress {
+    type Err = IndividualAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s
+            .split('.')
+            .map(|part| parse_part(part).map_err(|_| IndividualAddressError::InvalidFormat));
+        let (Some(area), Some(line), Some(device), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(IndividualAddressError::InvalidFormat);
+        };
+
+        let (area, line, device) = (area?, line?, device?);
+        if area > 15 {
+            return Err(IndividualAddressError::AreaOutOfRange);
+        }
+        if line > 15 {
+            return Err(IndividualAddressError::LineOutOfRange);
+        }
+        if device > 255 {
+            return Err(IndividualAddressError::DeviceOutOfRange);
+        }
+        Self::new(area as u8, line as u8, device as u8)
+    }
+}
+
+impl fmt::Display for IndividualAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.area(), self.line(), self.device())
+    }
+}
+
+impl fmt::Display for IndividualAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "invalid individual address format"),
+            Self::AreaOutOfRange => write!(f, "area out of range (0-15)"),
+            Self::LineOutOfRange => write!(f, "line out of range (0-15)"),
+            Self::DeviceOutOfRange => write!(f, "device out of range (0-255)"),
+        }
+    }
+}
+
+impl core::error::Error for IndividualAddressError {}
+
+impl PartialEq for GroupAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl Eq for GroupAddress {}
+
+impl core::hash::Hash for GroupAddress {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.raw.hash(state);
+    }
+}
+
+/// Ordered by raw address, i.e. by main, middle and sub group
+impl Ord for GroupAddress {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.raw.cmp(&other.raw)
+    }
+}
+
+impl PartialOrd for GroupAddress {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ============================================================================
+// SERIALIZATION - same string form as before ("1/0/7")
+// ============================================================================
+
+impl Serialize for GroupAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_heapless())
+    }
+}
+
+impl<'de> Deserialize<'de> for GroupAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AddressVisitor;
+
+        impl Visitor<'_> for AddressVisitor {
+            type Value = GroupAddress;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a KNX group address such as \"1/0/7\"")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(AddressVisitor)
+    }
+}
+
+impl Serialize for IndividualAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_heapless())
+    }
+}
+
+impl<'de> Deserialize<'de> for IndividualAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AddressVisitor;
+
+        impl Visitor<'_> for AddressVisitor {
+            type Value = IndividualAddress;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a KNX individual address such as \"1.1.25\"")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(AddressVisitor)
+    }
+}
@@ -0,0 +1,382 @@
+//! KNX Blind/Shutter Records
+//!
+//! Contains all blind-related data structures and utilities:
+//! - BlindState: Position, slat angle and movement of a shutter actuator
+//! - BlindControl: Move, stop/step and absolute position commands
+//!
+//! A KNX shutter channel spreads its objects over several group addresses:
+//! long-time move (DPT 1.008), stop/step (DPT 1.007), absolute position and
+//! slat angle (DPT 5.001), plus the matching status objects. Records name the
+//! blind by its position group address; the gateway knows the others.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// KNX blind state (DPT 5.001 position/slat, DPT 1.xxx moving)
+///
+/// Represents the current state of a shutter actuator, assembled from its
+/// status objects. Values not yet reported by the actuator are `None`.
+/// Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlindState {
+    /// KNX group address of the position status object (e.g., "2/1/4")
+    pub address: GroupAddress,
+
+    /// Position in percent (0 = fully open, 100 = fully closed)
+    pub position: Option<u8>,
+
+    /// Slat angle in percent (0 = open, 100 = closed)
+    pub slat_angle: Option<u8>,
+
+    /// Whether the blind is currently moving
+    pub moving: bool,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// Blind command carried by [`BlindControl`]
+///
+/// Serialized as `"up"`, `"down"`, `"stop"`, `"step_up"`, `"step_down"`,
+/// `{"position":50}` or `{"slat_angle":30}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlindCommand {
+    /// Move fully up (DPT 1.008)
+    Up,
+    /// Move fully down (DPT 1.008)
+    Down,
+    /// Stop a moving blind (DPT 1.007)
+    Stop,
+    /// Step slats up one increment (DPT 1.007)
+    StepUp,
+    /// Step slats down one increment (DPT 1.007)
+    StepDown,
+    /// Move to absolute position in percent (DPT 5.001)
+    Position(u8),
+    /// Set absolute slat angle in percent (DPT 5.001)
+    SlatAngle(u8),
+}
+
+/// KNX blind control command (DPT 1.008 / DPT 1.007 / DPT 5.001)
+///
+/// Represents a control command to be sent to a shutter actuator.
+/// Consumed by the gateway to control KNX devices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlindControl {
+    /// KNX group address of the absolute position object (e.g., "2/1/2")
+    pub address: GroupAddress,
+
+    /// Movement or positioning command
+    pub command: BlindCommand,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl BlindState {
+    /// Create a new BlindState
+    pub fn new(
+        address: GroupAddress,
+        position: Option<u8>,
+        slat_angle: Option<u8>,
+        moving: bool,
+    ) -> Self {
+        Self {
+            address,
+            position,
+            slat_angle,
+            moving,
+            meta: None,
+        }
+    }
+}
+
+impl BlindControl {
+    /// Create a new BlindControl command
+    pub fn new(address: GroupAddress, command: BlindCommand) -> Self {
+        Self { address, command }
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(BlindState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(BlindControl);
+crate::impl_timestamped!(BlindState);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for BlindState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{boolean, group_address, nullable, percent, record, record_meta};
+
+        record(
+            "BlindState",
+            "KNX blind/shutter status, published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the position status object"),
+                ),
+                (
+                    "position",
+                    nullable(percent("Position (0 = fully open, 100 = fully closed)")),
+                ),
+                (
+                    "slat_angle",
+                    nullable(percent("Slat angle (0 = open, 100 = closed)")),
+                ),
+                ("moving", boolean("Whether the blind is currently moving")),
+                ("meta", record_meta()),
+            ],
+            &["address", "moving"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for BlindControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, one_of, percent, record, string_enum, tagged};
+
+        record(
+            "BlindControl",
+            "KNX blind/shutter command, sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the absolute position object"),
+                ),
+                (
+                    "command",
+                    one_of(
+                        "Movement or positioning command",
+                        alloc::vec![
+                            string_enum(
+                                "Move fully up/down (DPT 1.008), stop or step slats (DPT 1.007)",
+                                &["up", "down", "stop", "step_up", "step_down"],
+                            ),
+                            tagged("position", percent("Absolute position")),
+                            tagged("slat_angle", percent("Absolute slat angle")),
+                        ],
+                    ),
+                ),
+            ],
+            &["address", "command"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for BlindState changes
+    ///
+    /// Logs all incoming blind state updates.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<BlindState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🪟 Blind state monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to BlindState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!(
+                "🪟 Blind: {} position={:?}% slats={:?}%{}",
+                state.address,
+                state.position,
+                state.slat_angle,
+                if state.moving { " (moving)" } else { "" }
+            ));
+        }
+    }
+
+    /// Monitor for BlindControl commands
+    ///
+    /// Logs all outgoing blind control commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<BlindControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Blind control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to BlindControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            log.info(&format!(
+                "📤 Blind: {} {:?}",
+                control.address, control.command
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use crate::dpt::{DptCodec, Percent};
+    use crate::error::RecordError;
+    use alloc::vec::Vec;
+    use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+    /// Marker for a status value the actuator has not reported yet
+    const UNKNOWN: u8 = u8::MAX;
+
+    /// Assembles [`BlindState`] from the actuator's separate status objects
+    ///
+    /// Each status telegram only carries one value, so the gateway keeps the
+    /// last known values here and publishes the merged state. Lock-free, so
+    /// it can live in a `static` and be shared by deserializer callbacks.
+    pub struct BlindStatus {
+        address: GroupAddress,
+        position: AtomicU8,
+        slat_angle: AtomicU8,
+        moving: AtomicBool,
+    }
+
+    impl BlindStatus {
+        /// Create an empty status cache for the blind with this position status address
+        pub const fn new(address: GroupAddress) -> Self {
+            Self {
+                address,
+                position: AtomicU8::new(UNKNOWN),
+                slat_angle: AtomicU8::new(UNKNOWN),
+                moving: AtomicBool::new(false),
+            }
+        }
+
+        /// Current merged state
+        pub fn state(&self) -> BlindState {
+            let known = |v: u8| (v != UNKNOWN).then_some(v);
+            BlindState::new(
+                self.address,
+                known(self.position.load(Ordering::Relaxed)),
+                known(self.slat_angle.load(Ordering::Relaxed)),
+                self.moving.load(Ordering::Relaxed),
+            )
+        }
+
+        /// Apply a position status telegram (DPT 5.001)
+        pub fn position_from_knx(&self, data: &[u8]) -> Result<BlindState, RecordError> {
+            self.position
+                .store(Percent::decode(data)?, Ordering::Relaxed);
+            Ok(self.state())
+        }
+
+        /// Apply a slat angle status telegram (DPT 5.001)
+        pub fn slat_angle_from_knx(&self, data: &[u8]) -> Result<BlindState, RecordError> {
+            self.slat_angle
+                .store(Percent::decode(data)?, Ordering::Relaxed);
+            Ok(self.state())
+        }
+
+        /// Apply a movement status telegram (DPT 1.xxx, 1 = moving)
+        pub fn moving_from_knx(&self, data: &[u8]) -> Result<BlindState, RecordError> {
+            use aimdb_knx_connector::dpt::{Dpt1, DptDecode};
+
+            RecordError::check_dpt_length(data, 1)?;
+            if data[0] > 1 {
+                return Err(RecordError::ValueOutOfRange);
+            }
+
+            let moving = Dpt1::State
+                .decode(data)
+                .map_err(|_| RecordError::ValueOutOfRange)?;
+            self.moving.store(moving, Ordering::Relaxed);
+            Ok(self.state())
+        }
+    }
+
+    /// Encode a DPT 1.xxx boolean
+    fn encode_bool(value: bool) -> Result<Vec<u8>, RecordError> {
+        use aimdb_knx_connector::dpt::{Dpt1, DptEncode};
+
+        let mut buf = [0u8; 1];
+        let len = Dpt1::UpDown
+            .encode(value, &mut buf)
+            .map_err(|_| RecordError::ValueOutOfRange)?;
+        Ok(buf[..len].to_vec())
+    }
+
+    /// Serialize an up/down command to KNX DPT 1.008 (0 = up, 1 = down)
+    ///
+    /// Returns `Ok(None)` for commands that belong on another group address.
+    pub fn move_to_knx(control: &BlindControl) -> Result<Option<Vec<u8>>, RecordError> {
+        match control.command {
+            BlindCommand::Up => encode_bool(false).map(Some),
+            BlindCommand::Down => encode_bool(true).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Serialize a stop/step command to KNX DPT 1.007 (0 = up, 1 = down)
+    ///
+    /// A step telegram stops a moving blind, so `Stop` is sent as a step up.
+    /// Returns `Ok(None)` for commands that belong on another group address.
+    pub fn step_to_knx(control: &BlindControl) -> Result<Option<Vec<u8>>, RecordError> {
+        match control.command {
+            BlindCommand::Stop | BlindCommand::StepUp => encode_bool(false).map(Some),
+            BlindCommand::StepDown => encode_bool(true).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Serialize an absolute position command to KNX DPT 5.001
+    ///
+    /// Returns `Ok(None)` for commands that belong on another group address.
+    pub fn position_to_knx(control: &BlindControl) -> Result<Option<Vec<u8>>, RecordError> {
+        match control.command {
+            BlindCommand::Position(percent) => Percent::encode(percent).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Serialize an absolute slat angle command to KNX DPT 5.001
+    ///
+    /// Returns `Ok(None)` for commands that belong on another group address.
+    pub fn slat_angle_to_knx(control: &BlindControl) -> Result<Option<Vec<u8>>, RecordError> {
+        match control.command {
+            BlindCommand::SlatAngle(percent) => Percent::encode(percent).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,708 @@
+//! KNX Date and Time Records
+//!
+//! Contains date/time data structures and utilities:
+//! - TimeOfDay: Time with optional weekday (DPT 10.001)
+//! - Date: Calendar date (DPT 11.001)
+//! - DateTime: Date and time with quality flags (DPT 19.001)
+//! - ClockState: Clock telegram observed on the bus (any of the above)
+//! - ClockControl: Clock to publish onto the bus
+//!
+//! Master clocks commonly send time and date on separate group addresses,
+//! and DPT 19.001 on a third. All three decode into a [`DateTime`]; a
+//! DPT 10.001 telegram simply carries no date and a DPT 11.001 telegram no
+//! time, just as DPT 19.001 expresses with its "no date"/"no time" flags.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Day of the week (KNX numbering: Monday = 1 .. Sunday = 7)
+///
+/// Serialized in snake_case, e.g. `"monday"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Time of day with optional weekday (DPT 10.001)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeOfDay {
+    /// Day of the week, `None` for "no day"
+    pub weekday: Option<Weekday>,
+
+    /// Hour (0..23; DPT 19.001 also allows 24:00:00)
+    pub hour: u8,
+
+    /// Minute (0..59)
+    pub minute: u8,
+
+    /// Second (0..59)
+    pub second: u8,
+}
+
+/// Calendar date (DPT 11.001)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Date {
+    /// Year (1990..2089 for DPT 11.001, 1900..2155 for DPT 19.001)
+    pub year: u16,
+
+    /// Month (1..12)
+    pub month: u8,
+
+    /// Day of the month (1..31)
+    pub day: u8,
+}
+
+/// DPT 19.001 status flags
+///
+/// All flags default to "not set", so clock sources may omit them in JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DateTimeFlags {
+    /// The sending clock reports a fault
+    pub fault: bool,
+
+    /// Whether the day is a working day, `None` if unknown
+    pub working_day: Option<bool>,
+
+    /// The year of the date is not valid
+    pub no_year: bool,
+
+    /// Summer (daylight saving) time is in effect
+    pub summer_time: bool,
+
+    /// The clock is synchronised to an external time signal
+    pub external_sync: bool,
+
+    /// The synchronisation source is reliable
+    pub reliable_source: bool,
+}
+
+/// Date and time (DPT 19.001)
+///
+/// Either part may be missing, matching the DPT 19.001 "no date" and
+/// "no time" flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateTime {
+    /// Calendar date, `None` if not known
+    pub date: Option<Date>,
+
+    /// Time of day, `None` if not known
+    pub time: Option<TimeOfDay>,
+
+    /// Status flags
+    #[serde(default)]
+    pub flags: DateTimeFlags,
+}
+
+/// KNX clock state (DPT 10.001 / 11.001 / 19.001)
+///
+/// Represents a date and/or time telegram observed on the bus, typically
+/// from a master clock. Published by the gateway when monitoring KNX bus
+/// activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockState {
+    /// KNX group address the telegram was received on (e.g., "7/0/0")
+    pub address: GroupAddress,
+
+    /// Received date and/or time
+    pub datetime: DateTime,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX clock command
+///
+/// Represents a local date and time to broadcast onto the bus. Consumed by
+/// the gateway, which sends the time, date and date-time group addresses
+/// it is configured with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockControl {
+    /// Date and time to publish
+    pub datetime: DateTime,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl Weekday {
+    /// Convert to the KNX day number (Monday = 1 .. Sunday = 7)
+    pub fn to_raw(self) -> u8 {
+        match self {
+            Self::Monday => 1,
+            Self::Tuesday => 2,
+            Self::Wednesday => 3,
+            Self::Thursday => 4,
+            Self::Friday => 5,
+            Self::Saturday => 6,
+            Self::Sunday => 7,
+        }
+    }
+
+    /// Convert from the KNX day number, `None` for 0 ("no day") or invalid values
+    pub fn from_raw(raw: u8) -> Option<Self> {
+        match raw {
+            1 => Some(Self::Monday),
+            2 => Some(Self::Tuesday),
+            3 => Some(Self::Wednesday),
+            4 => Some(Self::Thursday),
+            5 => Some(Self::Friday),
+            6 => Some(Self::Saturday),
+            7 => Some(Self::Sunday),
+            _ => None,
+        }
+    }
+
+    /// Three-letter abbreviation (e.g. "Mon")
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Monday => "Mon",
+            Self::Tuesday => "Tue",
+            Self::Wednesday => "Wed",
+            Self::Thursday => "Thu",
+            Self::Friday => "Fri",
+            Self::Saturday => "Sat",
+            Self::Sunday => "Sun",
+        }
+    }
+}
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TimeOfDay {
+    /// Create a new TimeOfDay
+    pub fn new(weekday: Option<Weekday>, hour: u8, minute: u8, second: u8) -> Self {
+        Self {
+            weekday,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    /// Check that hour, minute and second are within 00:00:00..23:59:59
+    pub fn is_valid(&self) -> bool {
+        self.hour < 24 && self.minute < 60 && self.second < 60
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(weekday) = self.weekday {
+            write!(f, "{} ", weekday)?;
+        }
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+impl Date {
+    /// Create a new Date
+    pub fn new(year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Check that month and day are in range (the day is not checked against the month)
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month) && (1..=31).contains(&self.day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl DateTime {
+    /// Create a new DateTime with no flags set
+    pub fn new(date: Option<Date>, time: Option<TimeOfDay>) -> Self {
+        Self {
+            date,
+            time,
+            flags: DateTimeFlags::default(),
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.date, self.time) {
+            (Some(date), Some(time)) => write!(f, "{} {}", date, time),
+            (Some(date), None) => write!(f, "{}", date),
+            (None, Some(time)) => write!(f, "{}", time),
+            (None, None) => f.write_str("(no date/time)"),
+        }
+    }
+}
+
+impl ClockState {
+    /// Create a new ClockState
+    pub fn new(address: GroupAddress, datetime: DateTime) -> Self {
+        Self {
+            address,
+            datetime,
+            meta: None,
+        }
+    }
+}
+
+impl ClockControl {
+    /// Create a new ClockControl command
+    pub fn new(datetime: DateTime) -> Self {
+        Self { datetime }
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+// A ClockState with date, time and all flags set needs up to 260 bytes
+crate::impl_record_codec!(ClockState => 320 + crate::meta::RecordMeta::MAX_JSON_LEN);
+crate::impl_record_codec!(ClockControl => 320);
+crate::impl_timestamped!(ClockState);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+/// Schema of [`DateTime`], shared by both clock records
+#[cfg(feature = "std")]
+fn datetime_schema() -> serde_json::Value {
+    use crate::schema::{boolean, integer, nullable, object, string_enum};
+
+    let date = object(
+        "Calendar date (DPT 11.001)",
+        [
+            (
+                "year",
+                integer(
+                    "Year (1990..2089 for DPT 11.001, 1900..2155 for DPT 19.001)",
+                    1900,
+                    2155,
+                ),
+            ),
+            ("month", integer("Month", 1, 12)),
+            ("day", integer("Day of the month", 1, 31)),
+        ],
+        &["year", "month", "day"],
+    );
+    let weekday = string_enum(
+        "Day of the week",
+        &[
+            "monday",
+            "tuesday",
+            "wednesday",
+            "thursday",
+            "friday",
+            "saturday",
+            "sunday",
+        ],
+    );
+    let time = object(
+        "Time of day with optional weekday (DPT 10.001)",
+        [
+            ("weekday", nullable(weekday)),
+            (
+                "hour",
+                integer("Hour (24 only as 24:00:00 in DPT 19.001)", 0, 24),
+            ),
+            ("minute", integer("Minute", 0, 59)),
+            ("second", integer("Second", 0, 59)),
+        ],
+        &["hour", "minute", "second"],
+    );
+    let flags = object(
+        "DPT 19.001 status flags, all unset by default",
+        [
+            ("fault", boolean("The sending clock reports a fault")),
+            (
+                "working_day",
+                nullable(boolean("Whether the day is a working day")),
+            ),
+            ("no_year", boolean("The year of the date is not valid")),
+            (
+                "summer_time",
+                boolean("Summer (daylight saving) time is in effect"),
+            ),
+            (
+                "external_sync",
+                boolean("The clock is synchronised to an external time signal"),
+            ),
+            (
+                "reliable_source",
+                boolean("The synchronisation source is reliable"),
+            ),
+        ],
+        &[],
+    );
+
+    object(
+        "Date and time (DPT 19.001); either part may be null",
+        [
+            ("date", nullable(date)),
+            ("time", nullable(time)),
+            ("flags", flags),
+        ],
+        &[],
+    )
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for ClockState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record, record_meta};
+
+        record(
+            "ClockState",
+            "KNX clock telegram (DPT 10.001, 11.001 or 19.001), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address the telegram was received on"),
+                ),
+                ("datetime", datetime_schema()),
+                ("meta", record_meta()),
+            ],
+            &["address", "datetime"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for ClockControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::record;
+
+        record(
+            "ClockControl",
+            "Local date and time to broadcast onto the bus, sent by the gateway",
+            [("datetime", datetime_schema())],
+            &["datetime"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for ClockState changes
+    ///
+    /// Logs all clock telegrams observed on the bus.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<ClockState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🕒 Clock state monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to ClockState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!("🕒 Clock: {} = {}", state.address, state.datetime));
+        }
+    }
+
+    /// Monitor for ClockControl commands
+    ///
+    /// Logs all outgoing clock commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<ClockControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Clock control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to ClockControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            log.info(&format!("📤 Clock: {}", control.datetime));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use crate::error::RecordError;
+    use alloc::vec::Vec;
+
+    // DPT 19.001 status byte (octet 2)
+    const FAULT: u8 = 0x80;
+    const WORKING_DAY: u8 = 0x40;
+    const NO_WORKING_DAY: u8 = 0x20;
+    const NO_YEAR: u8 = 0x10;
+    const NO_DATE: u8 = 0x08;
+    const NO_DAY_OF_WEEK: u8 = 0x04;
+    const NO_TIME: u8 = 0x02;
+    const SUMMER_TIME: u8 = 0x01;
+
+    // DPT 19.001 quality byte (octet 1)
+    const EXTERNAL_SYNC: u8 = 0x80;
+    const RELIABLE_SOURCE: u8 = 0x40;
+
+    /// Decode the shared day/hour, minute and second octets of DPT 10.001 and 19.001
+    fn decode_time(data: &[u8], max_hour: u8) -> Result<TimeOfDay, RecordError> {
+        let time = TimeOfDay::new(
+            Weekday::from_raw(data[0] >> 5),
+            data[0] & 0x1F,
+            data[1] & 0x3F,
+            data[2] & 0x3F,
+        );
+        if time.hour > max_hour || time.minute > 59 || time.second > 59 {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        Ok(time)
+    }
+
+    /// Encode the shared day/hour, minute and second octets
+    fn encode_time(time: &TimeOfDay) -> [u8; 3] {
+        let day = time.weekday.map_or(0, Weekday::to_raw);
+        [(day << 5) | time.hour, time.minute, time.second]
+    }
+
+    /// Check a DPT 19.001 time (00:00:00..24:00:00)
+    fn is_valid_dpt19_time(time: &TimeOfDay) -> bool {
+        time.is_valid() || (time.hour == 24 && time.minute == 0 && time.second == 0)
+    }
+
+    /// Deserialize a time of day from KNX DPT 10.001
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (3 bytes for DPT 10.001)
+    pub fn time_from_knx(data: &[u8]) -> Result<TimeOfDay, RecordError> {
+        RecordError::check_dpt_length(data, 3)?;
+        decode_time(data, 23)
+    }
+
+    /// Serialize a time of day to KNX DPT 10.001
+    pub fn time_to_knx(time: &TimeOfDay) -> Result<Vec<u8>, RecordError> {
+        if !time.is_valid() {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        Ok(encode_time(time).to_vec())
+    }
+
+    /// Deserialize a date from KNX DPT 11.001
+    ///
+    /// Two-digit years 90..99 map to 1990..1999 and 0..89 to 2000..2089.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (3 bytes for DPT 11.001)
+    pub fn date_from_knx(data: &[u8]) -> Result<Date, RecordError> {
+        RecordError::check_dpt_length(data, 3)?;
+
+        let year = u16::from(data[2] & 0x7F);
+        if year > 99 {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        let year = if year >= 90 { 1900 + year } else { 2000 + year };
+
+        let date = Date::new(year, data[1] & 0x0F, data[0] & 0x1F);
+        if !date.is_valid() {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        Ok(date)
+    }
+
+    /// Serialize a date to KNX DPT 11.001
+    ///
+    /// Years outside 1990..2089 are rejected.
+    pub fn date_to_knx(date: &Date) -> Result<Vec<u8>, RecordError> {
+        if !date.is_valid() || !(1990..=2089).contains(&date.year) {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        Ok(alloc::vec![date.day, date.month, (date.year % 100) as u8])
+    }
+
+    /// Deserialize a date and time from KNX DPT 19.001
+    ///
+    /// Fields flagged as invalid (no date, no time, no day of week) come back
+    /// as `None`; a telegram whose valid fields are out of range is rejected.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (8 bytes for DPT 19.001)
+    pub fn datetime_from_knx(data: &[u8]) -> Result<DateTime, RecordError> {
+        RecordError::check_dpt_length(data, 8)?;
+
+        let status = data[6];
+        let quality = data[7];
+
+        let date = if status & NO_DATE == 0 {
+            let date = Date::new(1900 + u16::from(data[0]), data[1] & 0x0F, data[2] & 0x1F);
+            if !date.is_valid() {
+                return Err(RecordError::ValueOutOfRange);
+            }
+            Some(date)
+        } else {
+            None
+        };
+
+        let time = if status & NO_TIME == 0 {
+            let mut time = decode_time(&data[3..6], 24)?;
+            if !is_valid_dpt19_time(&time) {
+                return Err(RecordError::ValueOutOfRange);
+            }
+            if status & NO_DAY_OF_WEEK != 0 {
+                time.weekday = None;
+            }
+            Some(time)
+        } else {
+            None
+        };
+
+        let flags = DateTimeFlags {
+            fault: status & FAULT != 0,
+            working_day: if status & NO_WORKING_DAY != 0 {
+                None
+            } else {
+                Some(status & WORKING_DAY != 0)
+            },
+            no_year: status & NO_YEAR != 0,
+            summer_time: status & SUMMER_TIME != 0,
+            external_sync: quality & EXTERNAL_SYNC != 0,
+            reliable_source: quality & RELIABLE_SOURCE != 0,
+        };
+
+        Ok(DateTime { date, time, flags })
+    }
+
+    /// Serialize a date and time to KNX DPT 19.001
+    ///
+    /// Missing parts are sent with their "no date"/"no time"/"no day of
+    /// week" flags set. Years outside 1900..2155 are rejected.
+    pub fn datetime_to_knx(datetime: &DateTime) -> Result<Vec<u8>, RecordError> {
+        let flags = &datetime.flags;
+        let mut status = 0;
+        if flags.fault {
+            status |= FAULT;
+        }
+        match flags.working_day {
+            Some(true) => status |= WORKING_DAY,
+            Some(false) => {}
+            None => status |= NO_WORKING_DAY,
+        }
+        if flags.no_year {
+            status |= NO_YEAR;
+        }
+        if flags.summer_time {
+            status |= SUMMER_TIME;
+        }
+
+        let mut quality = 0;
+        if flags.external_sync {
+            quality |= EXTERNAL_SYNC;
+        }
+        if flags.reliable_source {
+            quality |= RELIABLE_SOURCE;
+        }
+
+        let date = match datetime.date {
+            Some(date) => {
+                if !date.is_valid() || !(1900..=2155).contains(&date.year) {
+                    return Err(RecordError::ValueOutOfRange);
+                }
+                [(date.year - 1900) as u8, date.month, date.day]
+            }
+            None => {
+                status |= NO_DATE;
+                [0, 1, 1]
+            }
+        };
+
+        let time = match datetime.time {
+            Some(time) => {
+                if !is_valid_dpt19_time(&time) {
+                    return Err(RecordError::ValueOutOfRange);
+                }
+                if time.weekday.is_none() {
+                    status |= NO_DAY_OF_WEEK;
+                }
+                encode_time(&time)
+            }
+            None => {
+                status |= NO_TIME | NO_DAY_OF_WEEK;
+                [0, 0, 0]
+            }
+        };
+
+        Ok(alloc::vec![
+            date[0], date[1], date[2], time[0], time[1], time[2], status, quality
+        ])
+    }
+
+    /// Deserialize ClockState from a KNX DPT 10.001 time telegram
+    pub fn state_from_time_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<ClockState, RecordError> {
+        let time = time_from_knx(data)?;
+        Ok(ClockState::new(
+            group_address,
+            DateTime::new(None, Some(time)),
+        ))
+    }
+
+    /// Deserialize ClockState from a KNX DPT 11.001 date telegram
+    pub fn state_from_date_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<ClockState, RecordError> {
+        let date = date_from_knx(data)?;
+        Ok(ClockState::new(
+            group_address,
+            DateTime::new(Some(date), None),
+        ))
+    }
+
+    /// Deserialize ClockState from a KNX DPT 19.001 date-time telegram
+    pub fn state_from_datetime_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<ClockState, RecordError> {
+        let datetime = datetime_from_knx(data)?;
+        Ok(ClockState::new(group_address, datetime))
+    }
+}
@@ -0,0 +1,312 @@
+//! Record Wire Codecs
+//!
+//! Provides one JSON encoding for every record type:
+//! - [`RecordCodec`]: `to_json` / `from_json` methods on each record
+//! - [`encode`] / [`decode`]: the generic functions behind them
+//! - [`impl_record_codec!`](crate::impl_record_codec): implements the trait
+//!   for record types that derive `Serialize` and `Deserialize`
+//! - [`Envelope`]: schema-versioned wire form of a record
+//! - [`Encoding`]: wire format of an MQTT link, JSON or (with the `postcard`
+//!   feature) compact binary postcard
+//!
+//! Postcard payloads are about half the size of JSON and skip float
+//! formatting on the MCU. They travel on the JSON topic with a `/postcard`
+//! suffix (see [`Encoding::topic`]), so both formats can share a broker.
+//!
+//! ## Schema Versions
+//!
+//! Records travel in a versioned [`Envelope`], `{"v":2,"record":{...}}` in
+//! JSON, so a gateway and a tower built from different releases can tell
+//! which layout they received instead of failing on a field mismatch.
+//! Decoders accept payloads of older schema versions and migrate them
+//! through [`RecordCodec::migrate_json`]:
+//!
+//! | Version | Layout                                                     |
+//! |---------|------------------------------------------------------------|
+//! | 1       | Bare record JSON without envelope and without `"meta"`     |
+//! | 2       | Envelope; state records carry an optional `"meta"` field   |
+//!
+//! Newer versions are rejected with [`RecordError::UnsupportedSchemaVersion`].
+//! postcard is not self-describing, so postcard payloads carry the version
+//! but are only decoded at the current one.
+//!
+//! With the `std` feature JSON goes through `serde_json`. Without it,
+//! `serde-json-core` encodes into a stack buffer whose size each record
+//! chooses (128 bytes unless configured otherwise), so registration code in
+//! `ground` and `tower` can stay generic over the record type.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::error::RecordError;
+use crate::meta::RecordMeta;
+use alloc::string::String;
+use core::fmt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// Used by `impl_record_codec!` in crates without `extern crate alloc`
+#[doc(hidden)]
+pub use alloc::vec::Vec;
+
+/// Schema version written by the encoders
+pub const SCHEMA_VERSION: u16 = 2;
+
+/// Upper bound of the JSON the [`Envelope`] adds around a record
+///
+/// `impl_record_codec!` adds this to the configured stack buffer size.
+pub const ENVELOPE_JSON_LEN: usize = 24;
+
+/// Stack buffer size used by records that do not configure their own
+pub const DEFAULT_JSON_BUFFER_SIZE: usize = 128;
+
+/// Stack buffer size of state records, leaving room for their `"meta"` envelope
+pub const STATE_JSON_BUFFER_SIZE: usize = DEFAULT_JSON_BUFFER_SIZE + RecordMeta::MAX_JSON_LEN;
+
+/// Versioned wire form of a record
+///
+/// Serialized as `{"v":2,"record":{...}}` in JSON and as the version
+/// followed by the record in postcard.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Envelope<T> {
+    /// Schema version of `record`
+    pub v: u16,
+
+    /// The record itself
+    pub record: T,
+}
+
+/// Schema version of a JSON payload, ignoring the record
+#[derive(Deserialize)]
+struct Header {
+    #[serde(default)]
+    v: Option<u16>,
+}
+
+/// Wire format of the records on an MQTT link
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Encoding {
+    /// JSON, published on the device's MQTT topic
+    #[default]
+    Json,
+    /// postcard binary, published on the device's topic + `/postcard`
+    #[cfg(feature = "postcard")]
+    Postcard,
+}
+
+impl Encoding {
+    /// Parse an encoding name ("json" or "postcard")
+    ///
+    /// Returns `None` for unknown names and for "postcard" without the
+    /// `postcard` feature.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(Self::Json),
+            #[cfg(feature = "postcard")]
+            "postcard" => Some(Self::Postcard),
+            _ => None,
+        }
+    }
+
+    /// Encoding name ("json" or "postcard")
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            #[cfg(feature = "postcard")]
+            Self::Postcard => "postcard",
+        }
+    }
+
+    /// MIME content type of the payloads
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            #[cfg(feature = "postcard")]
+            Self::Postcard => "application/x-postcard",
+        }
+    }
+
+    /// MQTT topic carrying a record in this encoding
+    ///
+    /// JSON keeps the device's topic (e.g. `knx/living-room/tv/state`); other
+    /// encodings append their name (e.g. `knx/living-room/tv/state/postcard`).
+    pub fn topic(self, base: &str) -> String {
+        match self {
+            Self::Json => String::from(base),
+            #[cfg(feature = "postcard")]
+            Self::Postcard => alloc::format!("{}/{}", base, self.name()),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// JSON encoding and decoding of a record type
+///
+/// Implement it with [`impl_record_codec!`](crate::impl_record_codec),
+/// which picks the stack buffer size for no_std builds:
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// pub struct DoorState {
+///     pub address: GroupAddress,
+///     pub is_open: bool,
+/// }
+///
+/// records::impl_record_codec!(DoorState);
+///
+/// let json = state.to_json()?;
+/// let state = DoorState::from_json(&json)?;
+/// ```
+pub trait RecordCodec: Serialize + DeserializeOwned {
+    /// Size of the stack buffer the no_std encoder serializes into
+    ///
+    /// Encoding fails with [`RecordError::BufferTooSmall`] if the JSON form
+    /// of a value, envelope included, is longer. Unused with the `std` feature.
+    const JSON_BUFFER_SIZE: usize;
+
+    /// Serialize the record to versioned JSON
+    fn to_json(&self) -> Result<Vec<u8>, RecordError>;
+
+    /// Deserialize the record from JSON of the current or an older schema version
+    fn from_json(data: &[u8]) -> Result<Self, RecordError> {
+        match decode::<Header>(data)?.v.unwrap_or(1) {
+            SCHEMA_VERSION => decode::<Envelope<Self>>(data).map(|envelope| envelope.record),
+            version if version > SCHEMA_VERSION => {
+                Err(RecordError::UnsupportedSchemaVersion(version))
+            }
+            version => Self::migrate_json(version, data),
+        }
+    }
+
+    /// Decode a JSON payload written with an older schema version
+    ///
+    /// Version 1 differs from the current layout only by the missing
+    /// envelope and optional fields added since, so the default decodes it
+    /// as the current record. Records whose fields change override this,
+    /// decoding the old layout with [`decode_version`] and converting it.
+    fn migrate_json(version: u16, data: &[u8]) -> Result<Self, RecordError> {
+        match version {
+            1 => decode_version(version, data),
+            _ => Err(RecordError::UnsupportedSchemaVersion(version)),
+        }
+    }
+
+    /// Serialize the record in the given wire format
+    fn encode(&self, encoding: Encoding) -> Result<Vec<u8>, RecordError> {
+        match encoding {
+            Encoding::Json => self.to_json(),
+            #[cfg(feature = "postcard")]
+            Encoding::Postcard => {
+                let envelope = Envelope {
+                    v: SCHEMA_VERSION,
+                    record: self,
+                };
+                postcard::to_allocvec(&envelope).map_err(|e| match e {
+                    postcard::Error::SerializeBufferFull => RecordError::BufferTooSmall,
+                    _ => RecordError::PostcardEncode,
+                })
+            }
+        }
+    }
+
+    /// Deserialize the record from the given wire format
+    fn decode(data: &[u8], encoding: Encoding) -> Result<Self, RecordError> {
+        match encoding {
+            Encoding::Json => Self::from_json(data),
+            #[cfg(feature = "postcard")]
+            Encoding::Postcard => {
+                let (version, record) = postcard::take_from_bytes::<u16>(data)
+                    .map_err(|_| RecordError::MalformedPostcard)?;
+                if version != SCHEMA_VERSION {
+                    return Err(RecordError::UnsupportedSchemaVersion(version));
+                }
+                postcard::from_bytes(record).map_err(|_| RecordError::MalformedPostcard)
+            }
+        }
+    }
+}
+
+/// Serialize a value to JSON, using a stack buffer of `N` bytes without `std`
+pub fn encode<T: Serialize, const N: usize>(value: &T) -> Result<Vec<u8>, RecordError> {
+    #[cfg(feature = "std")]
+    {
+        serde_json::to_vec(value).map_err(|_| RecordError::JsonEncode)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let mut buf = [0u8; N];
+        serde_json_core::to_slice(value, &mut buf)
+            .map(|len| buf[..len].to_vec())
+            .map_err(|_| RecordError::BufferTooSmall)
+    }
+}
+
+/// Serialize a record in the current [`Envelope`], using a stack buffer of `N` bytes without `std`
+pub fn encode_record<T: Serialize, const N: usize>(record: &T) -> Result<Vec<u8>, RecordError> {
+    encode::<_, N>(&Envelope {
+        v: SCHEMA_VERSION,
+        record,
+    })
+}
+
+/// Deserialize a record of layout `T` from a JSON payload of schema `version`
+///
+/// Version 1 payloads are the bare record; later ones are wrapped in an
+/// [`Envelope`]. Used by [`RecordCodec::migrate_json`] with the record's
+/// old layout as `T`.
+pub fn decode_version<T: DeserializeOwned>(version: u16, data: &[u8]) -> Result<T, RecordError> {
+    if version == 1 {
+        decode(data)
+    } else {
+        decode::<Envelope<T>>(data).map(|envelope| envelope.record)
+    }
+}
+
+/// Deserialize a value from JSON
+pub fn decode<T: DeserializeOwned>(data: &[u8]) -> Result<T, RecordError> {
+    #[cfg(feature = "std")]
+    {
+        serde_json::from_slice(data).map_err(|_| RecordError::MalformedJson)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        serde_json_core::from_slice(data)
+            .map(|(value, _)| value)
+            .map_err(|_| RecordError::MalformedJson)
+    }
+}
+
+/// Implement [`RecordCodec`] for record types
+///
+/// - `impl_record_codec!(SwitchState, SwitchControl);` uses the default
+///   128-byte stack buffer.
+/// - `impl_record_codec!(ClockState => 320);` configures the buffer size.
+///
+/// The buffer is sized for the record alone; room for the [`Envelope`] is
+/// added on top.
+#[macro_export]
+macro_rules! impl_record_codec {
+    ($ty:ty => $size:expr) => {
+        impl $crate::codec::RecordCodec for $ty {
+            const JSON_BUFFER_SIZE: usize = $size + $crate::codec::ENVELOPE_JSON_LEN;
+
+            fn to_json(&self) -> Result<$crate::codec::Vec<u8>, $crate::RecordError> {
+                $crate::codec::encode_record::<
+                    Self,
+                    { $size + $crate::codec::ENVELOPE_JSON_LEN },
+                >(self)
+            }
+        }
+    };
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            $crate::impl_record_codec!($ty => $crate::codec::DEFAULT_JSON_BUFFER_SIZE);
+        )+
+    };
+}
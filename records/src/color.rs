@@ -0,0 +1,567 @@
+//! KNX Color Light Records
+//!
+//! Contains color-related data structures and utilities:
+//! - ColorLightState: Current color of an RGB/RGBW light
+//! - ColorLightControl: Color commands in hex or HSV form
+//!
+//! Colors travel on KNX as DPT 232.600 (RGB, 3 bytes) or DPT 251.600
+//! (RGBW, 6 bytes with a validity mask). On MQTT they are hex strings such
+//! as `"#ff8000"` or `"#ff8000c0"` (with white channel); commands may also
+//! use HSV, which is easier for an LLM to reason about ("make it warmer").
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::error::RecordError;
+use crate::meta::RecordMeta;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+use heapless::String as HeaplessString;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// RGB color with optional white channel
+///
+/// Serialized as a hex string: `"#rrggbb"`, or `"#rrggbbww"` when the
+/// white channel is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgbw {
+    /// Red channel (0..255)
+    pub red: u8,
+    /// Green channel (0..255)
+    pub green: u8,
+    /// Blue channel (0..255)
+    pub blue: u8,
+    /// White channel (0..255), `None` for RGB lights or when unchanged
+    pub white: Option<u8>,
+}
+
+/// Color in HSV form
+///
+/// Serialized as `{"hue":30.0,"saturation":100.0,"value":100.0}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hsv {
+    /// Hue in degrees (0..360)
+    pub hue: f32,
+    /// Saturation in percent (0..100)
+    pub saturation: f32,
+    /// Value (brightness) in percent (0..100)
+    pub value: f32,
+}
+
+/// Color carried by [`ColorLightControl`]
+///
+/// Serialized as `{"hex":"#ff8000"}` or
+/// `{"hsv":{"hue":30.0,"saturation":100.0,"value":100.0}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorCommand {
+    /// Hex color, optionally with white channel
+    Hex(Rgbw),
+
+    /// HSV color; leaves the white channel of RGBW lights unchanged
+    Hsv(Hsv),
+}
+
+/// KNX color light state (DPT 232.600 / DPT 251.600)
+///
+/// Represents the current color reported by an RGB or RGBW actuator.
+/// Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorLightState {
+    /// KNX group address of the color status object (e.g., "4/0/1")
+    pub address: GroupAddress,
+
+    /// Current color as hex
+    pub color: Rgbw,
+
+    /// Current color as HSV (derived from `color`)
+    pub hsv: Hsv,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX color light control command (DPT 232.600 / DPT 251.600)
+///
+/// Represents a color command to be sent to an RGB or RGBW actuator.
+/// Consumed by the gateway to control KNX devices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorLightControl {
+    /// KNX group address of the color object (e.g., "4/0/0")
+    pub address: GroupAddress,
+
+    /// Requested color
+    pub color: ColorCommand,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl Rgbw {
+    /// Length of the longest hex form (`"#rrggbbww"`)
+    pub const MAX_LEN: usize = 9;
+
+    /// Create an RGB color without white channel
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            white: None,
+        }
+    }
+
+    /// Create an RGBW color
+    pub const fn rgb_white(red: u8, green: u8, blue: u8, white: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            white: Some(white),
+        }
+    }
+
+    /// Format as a stack-allocated hex string
+    pub fn to_heapless(&self) -> HeaplessString<{ Self::MAX_LEN }> {
+        let mut s = HeaplessString::new();
+        // Cannot fail: "#rrggbbww" is exactly MAX_LEN bytes
+        let _ = write!(s, "{}", self);
+        s
+    }
+
+    /// Convert to HSV (the white channel is ignored)
+    pub fn to_hsv(&self) -> Hsv {
+        let r = f32::from(self.red) / 255.0;
+        let g = f32::from(self.green) / 255.0;
+        let b = f32::from(self.blue) / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            let h = 60.0 * ((g - b) / delta);
+            if h < 0.0 {
+                h + 360.0
+            } else {
+                h
+            }
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        let saturation = if max == 0.0 { 0.0 } else { delta / max * 100.0 };
+
+        Hsv {
+            hue,
+            saturation,
+            value: max * 100.0,
+        }
+    }
+}
+
+impl Hsv {
+    /// Convert to RGB without white channel
+    ///
+    /// Returns `ValueOutOfRange` unless hue is in 0..=360 and saturation
+    /// and value are in 0..=100.
+    pub fn to_rgbw(&self) -> Result<Rgbw, RecordError> {
+        if !(0.0..=360.0).contains(&self.hue)
+            || !(0.0..=100.0).contains(&self.saturation)
+            || !(0.0..=100.0).contains(&self.value)
+        {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        let v = self.value / 100.0;
+        let chroma = v * self.saturation / 100.0;
+        let sector_pos = if self.hue >= 360.0 {
+            0.0
+        } else {
+            self.hue / 60.0
+        };
+        let sector = sector_pos as u8;
+        let frac = sector_pos - f32::from(sector);
+        let x = chroma
+            * if sector.is_multiple_of(2) {
+                frac
+            } else {
+                1.0 - frac
+            };
+
+        let (r, g, b) = match sector {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let m = v - chroma;
+        let channel = |c: f32| ((c + m) * 255.0 + 0.5) as u8;
+
+        Ok(Rgbw::rgb(channel(r), channel(g), channel(b)))
+    }
+}
+
+impl ColorCommand {
+    /// Resolve the command to the color sent on KNX
+    pub fn to_rgbw(&self) -> Result<Rgbw, RecordError> {
+        match self {
+            Self::Hex(color) => Ok(*color),
+            Self::Hsv(hsv) => hsv.to_rgbw(),
+        }
+    }
+}
+
+impl ColorLightState {
+    /// Create a new ColorLightState (HSV is derived from the color)
+    pub fn new(address: GroupAddress, color: Rgbw) -> Self {
+        Self {
+            address,
+            color,
+            hsv: color.to_hsv(),
+            meta: None,
+        }
+    }
+}
+
+impl ColorLightControl {
+    /// Create a new ColorLightControl command
+    pub fn new(address: GroupAddress, color: ColorCommand) -> Self {
+        Self { address, color }
+    }
+
+    /// Create a command setting a hex color
+    pub fn hex(address: GroupAddress, color: Rgbw) -> Self {
+        Self::new(address, ColorCommand::Hex(color))
+    }
+}
+
+// ============================================================================
+// HEX FORMAT
+// ============================================================================
+
+impl fmt::Display for Rgbw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)?;
+        if let Some(white) = self.white {
+            write!(f, "{:02x}", white)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rgbw {
+    type Err = RecordError;
+
+    /// Parse `"#rrggbb"` or `"#rrggbbww"` (case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('#').ok_or(RecordError::ValueOutOfRange)?;
+        if !digits.is_ascii() {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        let channel = |i: usize| {
+            u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| RecordError::ValueOutOfRange)
+        };
+
+        match digits.len() {
+            6 => Ok(Self::rgb(channel(0)?, channel(2)?, channel(4)?)),
+            8 => Ok(Self::rgb_white(
+                channel(0)?,
+                channel(2)?,
+                channel(4)?,
+                channel(6)?,
+            )),
+            _ => Err(RecordError::ValueOutOfRange),
+        }
+    }
+}
+
+impl Serialize for Rgbw {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_heapless())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgbw {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HexVisitor;
+
+        impl Visitor<'_> for HexVisitor {
+            type Value = Rgbw;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a hex color such as \"#ff8000\" or \"#ff8000c0\"")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(HexVisitor)
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(ColorLightState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(ColorLightControl);
+crate::impl_timestamped!(ColorLightState);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+/// Schema of a hex [`Rgbw`] color
+#[cfg(feature = "std")]
+fn hex_schema(description: &str) -> serde_json::Value {
+    serde_json::json!({
+        "description": alloc::format!("{} as \"#rrggbb\" or, with white channel, \"#rrggbbww\"", description),
+        "type": "string",
+        "pattern": "^#[0-9A-Fa-f]{6}([0-9A-Fa-f]{2})?$",
+        "examples": ["#ff8000", "#ff8000c0"],
+    })
+}
+
+/// Schema of an [`Hsv`] color
+#[cfg(feature = "std")]
+fn hsv_schema(description: &str) -> serde_json::Value {
+    use crate::schema::{number, object};
+
+    object(
+        description,
+        [
+            ("hue", number("Hue in degrees", 0.0, 360.0)),
+            ("saturation", number("Saturation in percent", 0.0, 100.0)),
+            ("value", number("Value (brightness) in percent", 0.0, 100.0)),
+        ],
+        &["hue", "saturation", "value"],
+    )
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for ColorLightState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record, record_meta};
+
+        record(
+            "ColorLightState",
+            "KNX color light status (DPT 232.600 RGB / 251.600 RGBW), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the color status object"),
+                ),
+                ("color", hex_schema("Current color")),
+                (
+                    "hsv",
+                    hsv_schema("Current color as HSV (derived from `color`)"),
+                ),
+                ("meta", record_meta()),
+            ],
+            &["address", "color", "hsv"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for ColorLightControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, one_of, record, tagged};
+
+        record(
+            "ColorLightControl",
+            "KNX color light command (DPT 232.600 RGB / 251.600 RGBW), sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the color object"),
+                ),
+                (
+                    "color",
+                    one_of(
+                        "Color to set",
+                        alloc::vec![
+                            tagged("hex", hex_schema("Color")),
+                            tagged(
+                                "hsv",
+                                hsv_schema(
+                                    "Color; leaves the white channel of RGBW lights unchanged"
+                                ),
+                            ),
+                        ],
+                    ),
+                ),
+            ],
+            &["address", "color"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for ColorLightState changes
+    ///
+    /// Logs all incoming color updates.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<ColorLightState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🌈 Color light state monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to ColorLightState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!("🌈 Color: {} = {}", state.address, state.color));
+        }
+    }
+
+    /// Monitor for ColorLightControl commands
+    ///
+    /// Logs all outgoing color commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<ColorLightControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Color light control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to ColorLightControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            match control.color.to_rgbw() {
+                Ok(color) => log.info(&format!("📤 Color: {} → {}", control.address, color)),
+                Err(e) => log.warn(&format!("📤 Color: {} → {}", control.address, e)),
+            }
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// DPT 251.600 validity mask bits (last octet)
+    const MASK_RED: u8 = 0x08;
+    const MASK_GREEN: u8 = 0x04;
+    const MASK_BLUE: u8 = 0x02;
+    const MASK_WHITE: u8 = 0x01;
+
+    /// Deserialize ColorLightState from KNX DPT 232.600 (3-byte RGB)
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (R, G, B)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn rgb_from_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<ColorLightState, RecordError> {
+        RecordError::check_dpt_length(data, 3)?;
+
+        Ok(ColorLightState::new(
+            group_address,
+            Rgbw::rgb(data[0], data[1], data[2]),
+        ))
+    }
+
+    /// Deserialize ColorLightState from KNX DPT 251.600 (6-byte RGBW)
+    ///
+    /// Channels the validity mask marks as invalid decode as 0 (or `None`
+    /// for white).
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (R, G, B, W, reserved, mask)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn rgbw_from_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<ColorLightState, RecordError> {
+        RecordError::check_dpt_length(data, 6)?;
+
+        let mask = data[5];
+        let channel = |value: u8, bit: u8| if mask & bit != 0 { value } else { 0 };
+        let color = Rgbw {
+            red: channel(data[0], MASK_RED),
+            green: channel(data[1], MASK_GREEN),
+            blue: channel(data[2], MASK_BLUE),
+            white: (mask & MASK_WHITE != 0).then_some(data[3]),
+        };
+
+        Ok(ColorLightState::new(group_address, color))
+    }
+
+    /// Serialize ColorLightControl to KNX DPT 232.600 (3-byte RGB)
+    ///
+    /// A white channel in the command is rejected, since an RGB light
+    /// cannot show it.
+    pub fn rgb_to_knx(control: &ColorLightControl) -> Result<Vec<u8>, RecordError> {
+        let color = control.color.to_rgbw()?;
+        if color.white.is_some() {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        Ok(alloc::vec![color.red, color.green, color.blue])
+    }
+
+    /// Serialize ColorLightControl to KNX DPT 251.600 (6-byte RGBW)
+    ///
+    /// Without a white channel the mask marks white as invalid, so the
+    /// actuator keeps its current white level.
+    pub fn rgbw_to_knx(control: &ColorLightControl) -> Result<Vec<u8>, RecordError> {
+        let color = control.color.to_rgbw()?;
+        let mut mask = MASK_RED | MASK_GREEN | MASK_BLUE;
+        if color.white.is_some() {
+            mask |= MASK_WHITE;
+        }
+
+        Ok(alloc::vec![
+            color.red,
+            color.green,
+            color.blue,
+            color.white.unwrap_or(0),
+            0,
+            mask
+        ])
+    }
+}
@@ -0,0 +1,325 @@
+//! KNX Dimmer Records
+//!
+//! Contains all dimming-related data structures and utilities:
+//! - DimmerState: Current brightness of a KNX dimming actuator
+//! - DimmerControl: Absolute brightness and relative dim commands
+//!
+//! A KNX dimmer channel uses separate group addresses for the absolute value
+//! (DPT 5.001) and relative dimming (DPT 3.007). `DimmerControl::address`
+//! names the absolute value object; the gateway knows which relative dim
+//! object belongs to it.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// KNX dimmer state (DPT 5.001 - percentage 0..100)
+///
+/// Represents the current brightness reported by a dimming actuator.
+/// Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimmerState {
+    /// KNX group address of the brightness status object (e.g., "1/2/2")
+    pub address: GroupAddress,
+
+    /// Brightness in percent (0 = off, 100 = full)
+    pub brightness: u8,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// Dimming command carried by [`DimmerControl`]
+///
+/// Serialized as `{"brightness":40}`, `{"step":{"increase":true,"intervals":4}}`
+/// or `"stop"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DimmerCommand {
+    /// Set absolute brightness in percent (DPT 5.001)
+    Brightness(u8),
+
+    /// Dim relatively by a number of intervals (DPT 3.007)
+    Step {
+        /// `true` = brighter, `false` = darker
+        increase: bool,
+        /// Number of intervals: 1, 2, 4, 8, 16, 32 or 64
+        intervals: u8,
+    },
+
+    /// Stop an ongoing relative dim (DPT 3.007 break)
+    Stop,
+}
+
+/// KNX dimmer control command (DPT 5.001 / DPT 3.007)
+///
+/// Represents a control command to be sent to a KNX dimming actuator.
+/// Consumed by the gateway to control KNX devices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimmerControl {
+    /// KNX group address of the absolute brightness object (e.g., "1/2/1")
+    pub address: GroupAddress,
+
+    /// Brightness or relative dim command
+    pub command: DimmerCommand,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl DimmerState {
+    /// Create a new DimmerState
+    pub fn new(address: GroupAddress, brightness: u8) -> Self {
+        Self {
+            address,
+            brightness,
+            meta: None,
+        }
+    }
+}
+
+impl DimmerControl {
+    /// Create a new DimmerControl command
+    pub fn new(address: GroupAddress, command: DimmerCommand) -> Self {
+        Self { address, command }
+    }
+
+    /// Create a command setting absolute brightness in percent
+    pub fn brightness(address: GroupAddress, percent: u8) -> Self {
+        Self::new(address, DimmerCommand::Brightness(percent))
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(DimmerState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(DimmerControl);
+crate::impl_timestamped!(DimmerState);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for DimmerState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, percent, record, record_meta};
+
+        record(
+            "DimmerState",
+            "KNX dimmer brightness status, published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the brightness status object"),
+                ),
+                ("brightness", percent("Brightness (0 = off, 100 = full)")),
+                ("meta", record_meta()),
+            ],
+            &["address", "brightness"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for DimmerControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{
+            boolean, group_address, object, one_of, percent, record, string_enum, tagged,
+        };
+
+        let step = object(
+            "Dim relatively by a number of intervals (DPT 3.007)",
+            [
+                ("increase", boolean("`true` = brighter, `false` = darker")),
+                (
+                    "intervals",
+                    serde_json::json!({
+                        "description": "Number of intervals",
+                        "type": "integer",
+                        "enum": [1, 2, 4, 8, 16, 32, 64],
+                    }),
+                ),
+            ],
+            &["increase", "intervals"],
+        );
+
+        record(
+            "DimmerControl",
+            "KNX dimmer command, sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the absolute brightness object"),
+                ),
+                (
+                    "command",
+                    one_of(
+                        "Brightness or relative dim command",
+                        alloc::vec![
+                            tagged("brightness", percent("Absolute brightness")),
+                            tagged("step", step),
+                            string_enum("Stop an ongoing relative dim", &["stop"]),
+                        ],
+                    ),
+                ),
+            ],
+            &["address", "command"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for DimmerState changes
+    ///
+    /// Logs all incoming dimmer state updates.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<DimmerState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🔆 Dimmer state monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to DimmerState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!(
+                "🔆 Dimmer: {} = {}%",
+                state.address, state.brightness
+            ));
+        }
+    }
+
+    /// Monitor for DimmerControl commands
+    ///
+    /// Logs all outgoing dimmer control commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<DimmerControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Dimmer control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to DimmerControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            match control.command {
+                DimmerCommand::Brightness(percent) => {
+                    log.info(&format!("📤 Dim: {} = {}%", control.address, percent))
+                }
+                DimmerCommand::Step {
+                    increase,
+                    intervals,
+                } => log.info(&format!(
+                    "📤 Dim: {} {} by 1/{}",
+                    control.address,
+                    if increase { "up" } else { "down" },
+                    intervals
+                )),
+                DimmerCommand::Stop => log.info(&format!("📤 Dim: {} stop", control.address)),
+            }
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use crate::dpt::{DptCodec, Percent};
+    use crate::error::RecordError;
+    use alloc::vec::Vec;
+
+    /// Deserialize DimmerState from KNX DPT 5.001 (percentage)
+    ///
+    /// Decodes the raw KNX telegram bytes using DPT 5.001 format.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (1 byte for DPT 5.001)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn from_knx(data: &[u8], group_address: GroupAddress) -> Result<DimmerState, RecordError> {
+        let brightness = Percent::decode(data)?;
+
+        Ok(DimmerState::new(group_address, brightness))
+    }
+
+    /// Serialize an absolute brightness command to KNX DPT 5.001
+    ///
+    /// Returns `Ok(None)` for relative dim commands, which belong on the
+    /// DPT 3.007 group address (see [`step_to_knx`]).
+    pub fn brightness_to_knx(control: &DimmerControl) -> Result<Option<Vec<u8>>, RecordError> {
+        let DimmerCommand::Brightness(percent) = control.command else {
+            return Ok(None);
+        };
+
+        Percent::encode(percent).map(Some)
+    }
+
+    /// Serialize a relative dim command to KNX DPT 3.007
+    ///
+    /// Returns `Ok(None)` for absolute brightness commands, which belong on
+    /// the DPT 5.001 group address (see [`brightness_to_knx`]).
+    pub fn step_to_knx(control: &DimmerControl) -> Result<Option<Vec<u8>>, RecordError> {
+        use aimdb_knx_connector::dpt::{Dpt3, DptEncode, StepCode};
+
+        let (increase, step) = match control.command {
+            DimmerCommand::Brightness(_) => return Ok(None),
+            DimmerCommand::Stop => (false, StepCode::Break),
+            DimmerCommand::Step {
+                increase,
+                intervals,
+            } => {
+                let step = match intervals {
+                    1 => StepCode::Intervals1,
+                    2 => StepCode::Intervals2,
+                    4 => StepCode::Intervals4,
+                    8 => StepCode::Intervals8,
+                    16 => StepCode::Intervals16,
+                    32 => StepCode::Intervals32,
+                    64 => StepCode::Intervals64,
+                    _ => return Err(RecordError::ValueOutOfRange),
+                };
+                (increase, step)
+            }
+        };
+
+        let mut buf = [0u8; 1];
+        let len = Dpt3::Dimming
+            .encode((increase, step), &mut buf)
+            .map_err(|_| RecordError::ValueOutOfRange)?;
+
+        Ok(Some(buf[..len].to_vec()))
+    }
+}
@@ -0,0 +1,277 @@
+//! KNX Datapoint Type Codecs
+//!
+//! Contains one codec per DPT value format, used by records declared with
+//! `#[derive(KnxRecord)]`:
+//! - [`Boolean`]: DPT 1.xxx (1 bit)
+//! - [`Percent`]: DPT 5.001 (0..100 %)
+//! - [`Unsigned8`]: DPT 5.004 / 5.010 (1-byte unsigned)
+//! - [`Unsigned16`]: DPT 7.xxx (2-byte unsigned)
+//! - [`Float16`]: DPT 9.xxx (2-byte float)
+//! - [`Signed32`]: DPT 13.xxx (4-byte signed)
+//! - [`Float32`]: DPT 14.xxx (4-byte IEEE 754 float)
+//!
+//! Sub-types of a main number share their wire format, so e.g. DPT 9.001
+//! and DPT 9.007 both use [`Float16`].
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+#[cfg(feature = "knx")]
+use crate::error::RecordError;
+#[cfg(feature = "knx")]
+use alloc::vec::Vec;
+
+/// Wire format of a KNX datapoint type
+pub trait DptCodec {
+    /// Value carried by the datapoint
+    type Value: Copy;
+
+    /// Payload length in bytes
+    const LEN: usize;
+
+    /// Decode a raw KNX payload, rejecting wrong lengths and out-of-range values
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<Self::Value, RecordError>;
+
+    /// Encode a value into a raw KNX payload
+    #[cfg(feature = "knx")]
+    fn encode(value: Self::Value) -> Result<Vec<u8>, RecordError>;
+}
+
+/// DPT 1.xxx boolean (switch, open/close, alarm, ...)
+#[derive(Debug, Clone, Copy)]
+pub struct Boolean;
+
+/// DPT 5.001 percentage (0..100 %, scaled to 0..255 on the bus)
+#[derive(Debug, Clone, Copy)]
+pub struct Percent;
+
+/// DPT 5.004 / DPT 5.010 unsigned byte (0..255)
+#[derive(Debug, Clone, Copy)]
+pub struct Unsigned8;
+
+/// DPT 7.xxx 2-byte unsigned value (0..65535)
+#[derive(Debug, Clone, Copy)]
+pub struct Unsigned16;
+
+/// DPT 9.xxx 2-byte float (temperature, lux, humidity, ...)
+#[derive(Debug, Clone, Copy)]
+pub struct Float16;
+
+/// DPT 13.xxx 4-byte signed value (counters, energy)
+#[derive(Debug, Clone, Copy)]
+pub struct Signed32;
+
+/// DPT 14.xxx 4-byte IEEE 754 float (power, voltage, ...)
+#[derive(Debug, Clone, Copy)]
+pub struct Float32;
+
+impl DptCodec for Boolean {
+    type Value = bool;
+    const LEN: usize = 1;
+
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<bool, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+        match data[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(RecordError::ValueOutOfRange),
+        }
+    }
+
+    #[cfg(feature = "knx")]
+    fn encode(value: bool) -> Result<Vec<u8>, RecordError> {
+        Ok(alloc::vec![u8::from(value)])
+    }
+}
+
+impl DptCodec for Percent {
+    type Value = u8;
+    const LEN: usize = 1;
+
+    // Scaled here rather than with `Dpt5::Percentage`, which truncates in both
+    // directions so that e.g. 1 % is sent as 2 and read back as 0 %. Rounding
+    // to the nearest step makes every percentage survive a round trip.
+
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<u8, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+
+        Ok(((u16::from(data[0]) * 100 + 127) / 255) as u8)
+    }
+
+    #[cfg(feature = "knx")]
+    fn encode(value: u8) -> Result<Vec<u8>, RecordError> {
+        if value > 100 {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        Ok(alloc::vec![((u16::from(value) * 255 + 50) / 100) as u8])
+    }
+}
+
+impl DptCodec for Unsigned8 {
+    type Value = u8;
+    const LEN: usize = 1;
+
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<u8, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+        Ok(data[0])
+    }
+
+    #[cfg(feature = "knx")]
+    fn encode(value: u8) -> Result<Vec<u8>, RecordError> {
+        Ok(alloc::vec![value])
+    }
+}
+
+impl DptCodec for Unsigned16 {
+    type Value = u16;
+    const LEN: usize = 2;
+
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<u16, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+        Ok(u16::from_be_bytes([data[0], data[1]]))
+    }
+
+    #[cfg(feature = "knx")]
+    fn encode(value: u16) -> Result<Vec<u8>, RecordError> {
+        Ok(value.to_be_bytes().to_vec())
+    }
+}
+
+impl Float16 {
+    /// Smallest DPT 9 value (0xF800)
+    #[allow(clippy::excessive_precision)]
+    pub const MIN: f32 = -671_088.64;
+
+    /// Largest DPT 9 value
+    ///
+    /// Its encoding is the invalid marker [`Float16::INVALID`], so the
+    /// largest value that can actually be sent is 670433.28 (0x7FFE).
+    pub const MAX: f32 = 670_760.96;
+
+    /// Payload marking a value as invalid, e.g. from a faulty sensor
+    pub const INVALID: [u8; 2] = [0x7F, 0xFF];
+}
+
+// Implemented here rather than with `Dpt9`, which treats the mantissa as
+// 11-bit two's complement and ignores the sign bit: 21.5 (0x0C33) decodes
+// as -19.46 and nothing above 335544.32 can be encoded.
+//
+// Wire format: `0.01 * M * 2^E` with sign bit 15, exponent E in bits 11..14
+// and the low 11 bits of the 12-bit two's complement mantissa M in bits 0..10.
+impl DptCodec for Float16 {
+    type Value = f32;
+    const LEN: usize = 2;
+
+    /// Decode a DPT 9 payload, rejecting the invalid marker 0x7FFF
+    ///
+    /// All DPT 9 sub-types share the encoding; only units differ.
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<f32, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+        if data == Self::INVALID {
+            return Err(RecordError::InvalidDptValue);
+        }
+
+        let raw = u16::from_be_bytes([data[0], data[1]]);
+        let exponent = (raw >> 11) & 0x0F;
+        let mut mantissa = i32::from(raw & 0x07FF);
+        if raw & 0x8000 != 0 {
+            mantissa -= 0x0800;
+        }
+
+        // Exact integer, so the division rounds once to the nearest f32
+        Ok((mantissa << exponent) as f32 / 100.0)
+    }
+
+    /// Encode a value as DPT 9 with the smallest exponent that fits
+    ///
+    /// The value is rounded to the nearest step of `0.01 * 2^E` (ties away
+    /// from zero), so the error is at most half a step: 0.005 up to 20.47,
+    /// doubling with every exponent. NaN, infinities and values outside
+    /// [`Float16::MIN`]..[`Float16::MAX`] are rejected, as are values that
+    /// would round onto the invalid marker.
+    #[cfg(feature = "knx")]
+    fn encode(value: f32) -> Result<Vec<u8>, RecordError> {
+        // Also rejects NaN
+        if !(Self::MIN..=Self::MAX).contains(&value) {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        let hundredths = value * 100.0;
+        for exponent in 0..16u16 {
+            let scaled = hundredths / (1u32 << exponent) as f32;
+            // Round half away from zero; the cast truncates towards zero
+            let mantissa = if scaled < 0.0 {
+                (scaled - 0.5) as i32
+            } else {
+                (scaled + 0.5) as i32
+            };
+            if !(-0x0800..0x0800).contains(&mantissa) {
+                continue;
+            }
+            // Rounding past -2048 at E-1 lands on -1024 at E, the same value
+            // as -2048 at E-1; keep the smallest exponent
+            let (mantissa, exponent) = if mantissa == -0x0400 && exponent > 0 {
+                (-0x0800, exponent - 1)
+            } else {
+                (mantissa, exponent)
+            };
+
+            let sign = if mantissa < 0 { 0x8000 } else { 0 };
+            let raw = sign | (exponent << 11) | (mantissa as u16 & 0x07FF);
+            let data = raw.to_be_bytes();
+            if data == Self::INVALID {
+                return Err(RecordError::ValueOutOfRange);
+            }
+            return Ok(data.to_vec());
+        }
+
+        Err(RecordError::ValueOutOfRange)
+    }
+}
+
+impl DptCodec for Signed32 {
+    type Value = i32;
+    const LEN: usize = 4;
+
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<i32, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+        Ok(i32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+    }
+
+    #[cfg(feature = "knx")]
+    fn encode(value: i32) -> Result<Vec<u8>, RecordError> {
+        Ok(value.to_be_bytes().to_vec())
+    }
+}
+
+impl DptCodec for Float32 {
+    type Value = f32;
+    const LEN: usize = 4;
+
+    #[cfg(feature = "knx")]
+    fn decode(data: &[u8]) -> Result<f32, RecordError> {
+        RecordError::check_dpt_length(data, Self::LEN)?;
+
+        let value = f32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(RecordError::ValueOutOfRange)
+        }
+    }
+
+    #[cfg(feature = "knx")]
+    fn encode(value: f32) -> Result<Vec<u8>, RecordError> {
+        if !value.is_finite() {
+            return Err(RecordError::ValueOutOfRange);
+        }
+        Ok(value.to_be_bytes().to_vec())
+    }
+}
@@ -0,0 +1,317 @@
+//! KNX Energy Metering Records
+//!
+//! Contains energy and power metering data structures and utilities:
+//! - EnergyCounter: Active energy meter reading (DPT 13.010 - Wh)
+//! - PowerReading: Active power (DPT 14.056 - W)
+//! - EnergyTotal: Continuous energy total derived from counter readings
+//!
+//! A DPT 13 counter is a signed 32-bit value that wraps around after
+//! `i32::MAX` Wh and drops back to zero when a meter is reset or replaced.
+//! [`EnergyTracker`] turns raw counter readings into a monotonic total that
+//! survives both, which is what energy dashboards need.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use records_derive::KnxRecord;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// KNX active energy meter reading (DPT 13.010 - 4-byte signed counter)
+///
+/// Represents the raw counter value reported by an energy meter.
+/// Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnergyCounter {
+    /// KNX group address (e.g., "5/0/0")
+    pub address: GroupAddress,
+
+    /// Meter counter in watt hours
+    pub watt_hours: i32,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX active power reading (DPT 14.056 - 4-byte IEEE float)
+///
+/// Represents the instantaneous power reported by an energy meter. Declared
+/// with `#[derive(KnxRecord)]`, which provides its constructor, codecs and
+/// monitor; NaN and infinite values are rejected on the bus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, KnxRecord)]
+#[knx(dpt = "14.056")]
+pub struct PowerReading {
+    /// KNX group address (e.g., "5/0/1")
+    pub address: GroupAddress,
+
+    /// Active power in watts
+    pub watts: f32,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// Continuous energy total of one meter
+///
+/// Derived by the tower from [`EnergyCounter`] readings using an
+/// [`EnergyTracker`]. Unlike the raw counter it never wraps or decreases.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnergyTotal {
+    /// KNX group address of the meter's counter object
+    pub address: GroupAddress,
+
+    /// Total energy in watt hours
+    pub watt_hours: i64,
+
+    /// Number of meter resets detected since tracking started
+    pub resets: u32,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl EnergyCounter {
+    /// Create a new EnergyCounter reading
+    pub fn new(address: GroupAddress, watt_hours: i32) -> Self {
+        Self {
+            address,
+            watt_hours,
+            meta: None,
+        }
+    }
+}
+
+impl EnergyTotal {
+    /// Create a new EnergyTotal
+    pub fn new(address: GroupAddress, watt_hours: i64, resets: u32) -> Self {
+        Self {
+            address,
+            watt_hours,
+            resets,
+        }
+    }
+}
+
+// ============================================================================
+// COUNTER TRACKING
+// ============================================================================
+
+/// Accumulates raw DPT 13 counter readings of one meter into a total
+///
+/// The total starts at the first reading and then grows by the difference
+/// between consecutive readings:
+/// - A step of 0..=[`MAX_STEP`](Self::MAX_STEP) in wrapping 32-bit
+///   arithmetic is consumption, including the step across the
+///   `i32::MAX` → `i32::MIN` wraparound.
+/// - Any other step (backwards, or an implausibly large jump such as a
+///   wrapped counter returning to zero) is a meter reset; the new reading
+///   is counted as the energy consumed since the reset.
+#[derive(Debug, Clone, Default)]
+pub struct EnergyTracker {
+    last: Option<i32>,
+    total: i64,
+    resets: u32,
+}
+
+impl EnergyTracker {
+    /// Largest step between two readings accepted as consumption (~1 TWh)
+    pub const MAX_STEP: i32 = 1 << 30;
+
+    /// Create a tracker that has not seen any reading yet
+    pub const fn new() -> Self {
+        Self {
+            last: None,
+            total: 0,
+            resets: 0,
+        }
+    }
+
+    /// Number of meter resets detected so far
+    pub fn resets(&self) -> u32 {
+        self.resets
+    }
+
+    /// Feed a counter reading and return the updated total
+    pub fn update(&mut self, counter: &EnergyCounter) -> EnergyTotal {
+        let reading = counter.watt_hours;
+
+        match self.last {
+            None => self.total = i64::from(reading),
+            Some(last) => {
+                let step = reading.wrapping_sub(last);
+                if (0..=Self::MAX_STEP).contains(&step) {
+                    self.total += i64::from(step);
+                } else {
+                    self.resets += 1;
+                    self.total += i64::from(reading.max(0));
+                }
+            }
+        }
+        self.last = Some(reading);
+
+        EnergyTotal::new(counter.address, self.total, self.resets)
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(EnergyCounter => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(EnergyTotal);
+crate::impl_timestamped!(EnergyCounter);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for EnergyCounter {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, integer, record, record_meta};
+
+        record(
+            "EnergyCounter",
+            "KNX energy meter counter (DPT 13.010), published by the gateway",
+            [
+                ("address", group_address("KNX group address (e.g. \"5/0/0\")")),
+                (
+                    "watt_hours",
+                    integer(
+                        "Meter counter in watt hours (Wh); wraps around and drops to 0 on meter reset",
+                        i32::MIN.into(),
+                        i32::MAX.into(),
+                    ),
+                ),
+                ("meta", record_meta()),
+            ],
+            &["address", "watt_hours"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for PowerReading {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{finite_number, group_address, record, record_meta};
+
+        record(
+            "PowerReading",
+            "KNX active power reading (DPT 14.056), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address (e.g. \"5/0/1\")"),
+                ),
+                ("watts", finite_number("Active power in watts (W)")),
+                ("meta", record_meta()),
+            ],
+            &["address", "watts"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for EnergyTotal {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, integer, record};
+
+        record(
+            "EnergyTotal",
+            "Continuous energy total derived by the tower from EnergyCounter",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the meter's counter object"),
+                ),
+                (
+                    "watt_hours",
+                    integer("Total energy in watt hours (Wh)", i64::MIN, i64::MAX),
+                ),
+                (
+                    "resets",
+                    integer(
+                        "Number of meter resets detected since tracking started",
+                        0,
+                        u32::MAX.into(),
+                    ),
+                ),
+            ],
+            &["address", "watt_hours", "resets"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for EnergyCounter changes
+    ///
+    /// Logs all incoming energy counter readings.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn counter_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<EnergyCounter, R>,
+    ) {
+        let log = ctx.log();
+        log.info("⚡ Energy counter monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to EnergyCounter buffer");
+            return;
+        };
+
+        while let Ok(counter) = reader.recv().await {
+            log.info(&format!(
+                "⚡ Energy: {} = {} Wh",
+                counter.address, counter.watt_hours
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC DESERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use crate::error::RecordError;
+
+    /// Deserialize EnergyCounter from KNX DPT 13.010 (4-byte signed, Wh)
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (4 bytes for DPT 13.010)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn counter_from_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<EnergyCounter, RecordError> {
+        use aimdb_knx_connector::dpt::{Dpt13, DptDecode};
+
+        RecordError::check_dpt_length(data, 4)?;
+
+        let watt_hours = Dpt13::ActiveEnergy
+            .decode(data)
+            .map_err(|_| RecordError::ValueOutOfRange)?;
+
+        Ok(EnergyCounter::new(group_address, watt_hours))
+    }
+}
@@ -0,0 +1,105 @@
+//! Record Codec Errors
+//!
+//! Contains the error type shared by all record codecs (JSON, postcard and KNX).
+//!
+//! The enum is `Copy` and never allocates, so failures can be matched,
+//! counted and logged (via `defmt` on embedded targets) without a heap.
+
+use crate::address::GroupAddressError;
+use core::fmt;
+
+/// Error returned by record serialization and KNX DPT codecs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecordError {
+    /// Output buffer is too small for the encoded record
+    BufferTooSmall,
+
+    /// JSON payload could not be parsed into the record type
+    MalformedJson,
+
+    /// Record could not be serialized to JSON
+    JsonEncode,
+
+    /// postcard payload could not be parsed into the record type
+    MalformedPostcard,
+
+    /// Record could not be serialized to postcard
+    PostcardEncode,
+
+    /// KNX payload length does not match the DPT
+    InvalidDptLength {
+        /// Payload length required by the DPT
+        expected: usize,
+        /// Payload length received
+        actual: usize,
+    },
+
+    /// Group address is malformed or out of range
+    InvalidAddress(GroupAddressError),
+
+    /// Value cannot be represented by the DPT
+    ValueOutOfRange,
+
+    /// KNX payload is the DPT's marker for an invalid value
+    InvalidDptValue,
+
+    /// Payload was written with a schema version this build cannot read
+    UnsupportedSchemaVersion(u16),
+}
+
+impl RecordError {
+    /// Check that a KNX payload has exactly the length its DPT requires
+    pub fn check_dpt_length(data: &[u8], expected: usize) -> Result<(), Self> {
+        if data.len() == expected {
+            Ok(())
+        } else {
+            Err(Self::InvalidDptLength {
+                expected,
+                actual: data.len(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => write!(f, "serialization buffer too small"),
+            Self::MalformedJson => write!(f, "malformed JSON payload"),
+            Self::JsonEncode => write!(f, "JSON serialization failed"),
+            Self::MalformedPostcard => write!(f, "malformed postcard payload"),
+            Self::PostcardEncode => write!(f, "postcard serialization failed"),
+            Self::InvalidDptLength { expected, actual } => {
+                write!(
+                    f,
+                    "invalid DPT length: expected {} bytes, got {}",
+                    expected, actual
+                )
+            }
+            Self::InvalidAddress(e) => write!(f, "invalid group address: {}", e),
+            Self::ValueOutOfRange => write!(f, "value out of range for DPT"),
+            Self::InvalidDptValue => write!(f, "DPT value marked invalid by sender"),
+            Self::UnsupportedSchemaVersion(v) => write!(f, "unsupported schema version {}", v),
+        }
+    }
+}
+
+impl core::error::Error for RecordError {}
+
+impl From<GroupAddressError> for RecordError {
+    fn from(e: GroupAddressError) -> Self {
+        Self::InvalidAddress(e)
+    }
+}
+
+/// Map onto the connector error used by aimdb serializer callbacks
+#[cfg(feature = "aimdb-core")]
+impl From<RecordError> for aimdb_core::connector::SerializeError {
+    fn from(e: RecordError) -> Self {
+        match e {
+            RecordError::BufferTooSmall => Self::BufferTooSmall,
+            _ => Self::InvalidData,
+        }
+    }
+}
@@ -0,0 +1,611 @@
+//! ETS Import
+//!
+//! Builds a device [`Registry`] from what the integrator exports from ETS
+//! (`ets` feature, std only):
+//! - `.knxproj` project archives (ETS 5/6, without project password)
+//! - Group address exports as XML or CSV (1/1 and 3/1 layouts)
+//!
+//! Each group address gets a device kind and role from its DPT (and, where
+//! a DPT is ambiguous, from its name: "status"/"feedback" marks status
+//! objects, "setpoint" a thermostat setpoint, "slat" a slat angle). Group
+//! addresses are then gathered into devices:
+//! - ETS 6 functions: one device per function, in the function's room
+//! - Otherwise: by name within the middle group, which is taken as the room
+//!   ("TV switch" and "TV status" both belong to device "tv")
+//!
+//! Group addresses without a DPT, or with one no record carries, are listed
+//! in [`Import::skipped`] instead of being dropped silently.
+
+use crate::address::{AddressStyle, GroupAddress};
+use crate::registry::{Datapoint, Device, DeviceKind, Dpt, Registry, RegistryError, Role};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use roxmltree::{Document, Node};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// One group address from an ETS export
+#[derive(Debug, Clone, PartialEq)]
+pub struct EtsGroupAddress {
+    /// KNX group address
+    pub address: GroupAddress,
+    /// Name given in ETS (e.g. "TV switch")
+    pub name: String,
+    /// Datapoint type, `None` if ETS has none assigned
+    pub dpt: Option<Dpt>,
+    /// Names of the enclosing group ranges, outermost first (main, middle group)
+    pub ranges: Vec<String>,
+}
+
+/// One ETS 6 function: the group addresses of a device in a room
+#[derive(Debug, Clone, PartialEq)]
+pub struct EtsFunction {
+    /// Name given in ETS (e.g. "Ceiling light")
+    pub name: String,
+    /// Names of the enclosing spaces, outermost first; the last is the room
+    pub spaces: Vec<String>,
+    /// Group addresses of the function
+    pub addresses: Vec<GroupAddress>,
+}
+
+/// Group addresses and functions read from an ETS export
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EtsProject {
+    /// Group addresses in export order
+    pub group_addresses: Vec<EtsGroupAddress>,
+    /// Functions (`.knxproj` from ETS 6 only)
+    pub functions: Vec<EtsFunction>,
+}
+
+/// Result of [`EtsProject::to_registry`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    /// Devices built from the supported group addresses
+    pub registry: Registry,
+    /// Group addresses that are not in the registry, with the reason
+    pub skipped: Vec<Skipped>,
+}
+
+/// A group address left out of the registry
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    /// KNX group address
+    pub address: GroupAddress,
+    /// Name given in ETS
+    pub name: String,
+    /// Datapoint type, `None` if ETS has none assigned
+    pub dpt: Option<Dpt>,
+}
+
+// ============================================================================
+// PARSING
+// ============================================================================
+
+impl EtsProject {
+    /// Read an ETS export, by extension: `.knxproj`, `.xml` or `.csv`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        let path = path.as_ref();
+        let io = |e: std::io::Error| RegistryError::Io(format!("{}: {}", path.display(), e));
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("knxproj") => Self::from_knxproj(std::fs::File::open(path).map_err(io)?),
+            Some("xml") => Self::from_xml(&decode_text(&std::fs::read(path).map_err(io)?)),
+            Some("csv") => Self::from_csv(&decode_text(&std::fs::read(path).map_err(io)?)),
+            _ => Err(RegistryError::Parse(format!(
+                "{}: expected a .knxproj, .xml or .csv ETS export",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Read a `.knxproj` project archive
+    ///
+    /// Password-protected projects are encrypted inside the archive and are
+    /// rejected; export the group addresses as XML or CSV instead.
+    pub fn from_knxproj(archive: impl Read + Seek) -> Result<Self, RegistryError> {
+        let zip_error = |e: zip::result::ZipError| RegistryError::Parse(format!("knxproj: {}", e));
+        let mut archive = zip::ZipArchive::new(archive).map_err(zip_error)?;
+
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        // Installation data lives in P-XXXX/0.xml, project settings in P-XXXX/project.xml
+        let Some(installation) = names.iter().find(|name| is_project_file(name, "0.xml")) else {
+            let reason = if names.iter().any(|name| is_project_file(name, ".zip")) {
+                "project is password-protected; export the group addresses as XML or CSV instead"
+            } else {
+                "no project found in archive"
+            };
+            return Err(RegistryError::Parse(format!("knxproj: {}", reason)));
+        };
+
+        let read = |archive: &mut zip::ZipArchive<_>, name: &str| {
+            let mut data = Vec::new();
+            archive
+                .by_name(name)
+                .map_err(zip_error)?
+                .read_to_end(&mut data)
+                .map_err(|e| RegistryError::Io(format!("knxproj {}: {}", name, e)))?;
+            Ok::<_, RegistryError>(decode_text(&data))
+        };
+
+        let style = match names
+            .iter()
+            .find(|name| is_project_file(name, "project.xml"))
+        {
+            Some(name) => address_style(&read(&mut archive, name)?)?,
+            None => AddressStyle::ThreeLevel,
+        };
+        let installation = read(&mut archive, installation)?;
+        Self::parse_xml(&installation, style)
+    }
+
+    /// Read an ETS group address XML export, or the `0.xml` of a project
+    pub fn from_xml(text: &str) -> Result<Self, RegistryError> {
+        Self::parse_xml(text, AddressStyle::ThreeLevel)
+    }
+
+    fn parse_xml(text: &str, style: AddressStyle) -> Result<Self, RegistryError> {
+        let document = Document::parse(text).map_err(|e| RegistryError::Parse(e.to_string()))?;
+        let mut project = Self::default();
+        let mut ids = BTreeMap::new();
+
+        // Group ranges nest as main and middle groups; addresses are raw numbers
+        // in projects and "1/0/7" strings in exports
+        for ranges in document.descendants().filter(|node| {
+            node.has_tag_name("GroupRanges") || node.has_tag_name("GroupAddress-Export")
+        }) {
+            collect_group_addresses(ranges, &mut Vec::new(), style, &mut project, &mut ids)?;
+        }
+
+        // ETS 6 locations: Space elements (BuildingPart in ETS 5) holding functions
+        for function in document
+            .descendants()
+            .filter(|node| node.has_tag_name("Function"))
+        {
+            let spaces = function
+                .ancestors()
+                .filter(|node| node.has_tag_name("Space") || node.has_tag_name("BuildingPart"))
+                .filter_map(|node| node.attribute("Name"))
+                .map(String::from)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect();
+            let addresses = function
+                .children()
+                .filter(|node| node.has_tag_name("GroupAddressRef"))
+                .filter_map(|node| ids.get(node.attribute("RefId")?).copied())
+                .collect();
+
+            project.functions.push(EtsFunction {
+                name: function.attribute("Name").unwrap_or_default().to_string(),
+                spaces,
+                addresses,
+            });
+        }
+
+        Ok(project)
+    }
+
+    /// Read an ETS group address CSV export
+    ///
+    /// Accepts the 1/1 layout (name, address, ...) and the 3/1 layout
+    /// (main, middle, sub, address, ...), with or without header, separated
+    /// by commas, semicolons or tabs. Main and middle group rows (`1/-/-`,
+    /// `1/0/-`) name the ranges of the addresses below them.
+    pub fn from_csv(text: &str) -> Result<Self, RegistryError> {
+        let first_line = text.lines().next().unwrap_or_default();
+        let delimiter = [b';', b'\t', b',']
+            .into_iter()
+            .max_by_key(|d| first_line.bytes().filter(|b| b == d).count())
+            .unwrap_or(b',');
+
+        let mut rows = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes())
+            .into_records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| RegistryError::Parse(format!("csv: {}", e)))?;
+
+        let header = rows.first().and_then(|row| {
+            let column = |names: &[&str]| {
+                row.iter()
+                    .position(|field| names.iter().any(|n| field.eq_ignore_ascii_case(n)))
+            };
+            Some((
+                column(&["Address"])?,
+                column(&["DatapointType", "DPT", "DPTs"]),
+            ))
+        });
+        let (names, address_column, dpt_column) = match header {
+            Some((address, dpt)) => {
+                rows.remove(0);
+                (0..address, address, dpt)
+            }
+            // 1/1 when the second column holds the address, 3/1 otherwise
+            None => match rows.first().and_then(|row| row.get(1)) {
+                Some(field) if field.contains('/') => (0..1, 1, Some(5)),
+                _ => (0..3, 3, Some(7)),
+            },
+        };
+
+        let mut project = Self::default();
+        let mut mains = BTreeMap::new();
+        let mut middles = BTreeMap::new();
+
+        for row in &rows {
+            let Some(address) = row.get(address_column).map(str::trim) else {
+                continue;
+            };
+            let name = names
+                .clone()
+                .filter_map(|i| row.get(i))
+                .rfind(|field| !field.trim().is_empty())
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            let parts: Vec<&str> = address.split('/').collect();
+
+            match parts.as_slice() {
+                [main, "-"] | [main, "-", "-"] => {
+                    mains.insert(main.to_string(), name);
+                }
+                [main, middle, "-"] => {
+                    middles.insert((main.to_string(), middle.to_string()), name);
+                }
+                _ => {
+                    let Ok(address) = address.parse::<GroupAddress>() else {
+                        continue;
+                    };
+                    let mut ranges: Vec<String> = Vec::new();
+                    if let [main, rest @ ..] = parts.as_slice() {
+                        ranges.extend(mains.get(*main).cloned());
+                        if let [middle, _] = rest {
+                            ranges.extend(
+                                middles
+                                    .get(&(main.to_string(), middle.to_string()))
+                                    .cloned(),
+                            );
+                        }
+                    }
+
+                    project.group_addresses.push(EtsGroupAddress {
+                        address,
+                        name,
+                        dpt: dpt_column.and_then(|i| row.get(i)).and_then(parse_dpt_list),
+                        ranges,
+                    });
+                }
+            }
+        }
+
+        Ok(project)
+    }
+}
+
+fn collect_group_addresses(
+    node: Node,
+    ranges: &mut Vec<String>,
+    style: AddressStyle,
+    project: &mut EtsProject,
+    ids: &mut BTreeMap<String, GroupAddress>,
+) -> Result<(), RegistryError> {
+    for child in node.children().filter(Node::is_element) {
+        if child.has_tag_name("GroupRange") {
+            ranges.push(child.attribute("Name").unwrap_or_default().to_string());
+            collect_group_addresses(child, ranges, style, project, ids)?;
+            ranges.pop();
+        } else if child.has_tag_name("GroupAddress") {
+            let value = child.attribute("Address").unwrap_or_default();
+            let address = match value.parse::<u16>() {
+                Ok(raw) => GroupAddress::from_raw(raw).with_style(style),
+                Err(_) => value.parse().map_err(|e| {
+                    RegistryError::Parse(format!("group address {:?}: {}", value, e))
+                })?,
+            };
+            if let Some(id) = child.attribute("Id") {
+                ids.insert(id.to_string(), address);
+            }
+
+            project.group_addresses.push(EtsGroupAddress {
+                address,
+                name: child.attribute("Name").unwrap_or_default().to_string(),
+                dpt: child
+                    .attribute("DatapointType")
+                    .or(child.attribute("DPTs"))
+                    .and_then(parse_dpt_list),
+                ranges: ranges.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `name` is `P-XXXX/<file>` (or `P-XXXX<file>` for `.zip`)
+fn is_project_file(name: &str, file: &str) -> bool {
+    name.starts_with("P-") && name.ends_with(file) && name.matches('/').count() <= 1
+}
+
+/// Group address notation from the project's `project.xml`
+fn address_style(text: &str) -> Result<AddressStyle, RegistryError> {
+    let document = Document::parse(text).map_err(|e| RegistryError::Parse(e.to_string()))?;
+    let style = document
+        .descendants()
+        .find_map(|node| node.attribute("GroupAddressStyle"));
+
+    Ok(match style {
+        Some("TwoLevel") => AddressStyle::TwoLevel,
+        Some("Free") => AddressStyle::Free,
+        _ => AddressStyle::ThreeLevel,
+    })
+}
+
+/// First DPT of an ETS list (e.g. "DPST-1-1 DPST-1-2"), `None` if empty
+fn parse_dpt_list(value: &str) -> Option<Dpt> {
+    value
+        .split([' ', ','])
+        .find(|dpt| !dpt.is_empty())
+        .and_then(|dpt| dpt.parse().ok())
+}
+
+/// UTF-8 (with or without BOM), falling back to Latin-1 for older CSV exports
+fn decode_text(data: &[u8]) -> String {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    match core::str::from_utf8(data) {
+        Ok(text) => text.to_string(),
+        Err(_) => data.iter().map(|&b| char::from(b)).collect(),
+    }
+}
+
+// ============================================================================
+// DEVICE MAPPING
+// ============================================================================
+
+/// Name words marking a status (feedback) object
+const STATUS_WORDS: &[&str] = &["status", "state", "feedback", "rückmeldung", "rm", "actual"];
+
+/// Name words describing a datapoint's role rather than its device
+const ROLE_WORDS: &[&str] = &[
+    "switch",
+    "switching",
+    "on/off",
+    "value",
+    "brightness",
+    "dimming",
+    "dim",
+    "relative",
+    "absolute",
+    "step",
+    "stop",
+    "stop/step",
+    "move",
+    "up/down",
+    "position",
+    "slat",
+    "angle",
+    "moving",
+    "control",
+    "command",
+    "time",
+    "date",
+];
+
+impl EtsProject {
+    /// Map the group addresses to registry devices
+    ///
+    /// Device and room names become lowercase MQTT topic levels (e.g.
+    /// "Living room" → "living-room"); the original space or range names are
+    /// kept as the device's location.
+    pub fn to_registry(&self) -> Result<Import, RegistryError> {
+        let mut group_addresses: Vec<&EtsGroupAddress> = self.group_addresses.iter().collect();
+        group_addresses.sort_by_key(|ga| ga.address);
+
+        // Functions claim their group addresses first
+        let mut owner: BTreeMap<GroupAddress, &EtsFunction> = BTreeMap::new();
+        for function in &self.functions {
+            for address in &function.addresses {
+                owner.entry(*address).or_insert(function);
+            }
+        }
+
+        // Group by (location, room, device name), in address order
+        let mut groups: Vec<(Vec<String>, String, String, Vec<&EtsGroupAddress>)> = Vec::new();
+        for ga in group_addresses {
+            let (mut location, name) = match owner.get(&ga.address) {
+                Some(function) => (function.spaces.clone(), function.name.clone()),
+                None => (ga.ranges.clone(), base_name(&ga.name)),
+            };
+            let room = location.pop().unwrap_or_else(|| "house".to_string());
+
+            match groups
+                .iter_mut()
+                .find(|(l, r, n, _)| *l == location && *r == room && *n == name)
+            {
+                Some((_, _, _, members)) => members.push(ga),
+                None => groups.push((location, room, name, alloc::vec![ga])),
+            }
+        }
+
+        let mut registry = Registry::default();
+        let mut skipped = Vec::new();
+        let mut ids = BTreeSet::new();
+
+        for (location, room, name, members) in groups {
+            let blind = members.iter().any(|ga| {
+                matches!(
+                    ga.dpt,
+                    Some(Dpt {
+                        main: 1,
+                        sub: Some(7 | 8)
+                    })
+                )
+            });
+
+            let mut kinds: Vec<(DeviceKind, Vec<Datapoint>)> = Vec::new();
+            for ga in members {
+                let classified = ga
+                    .dpt
+                    .and_then(|dpt| Some((dpt, classify(&ga.name, dpt, blind)?)));
+                let Some((dpt, (kind, role))) = classified else {
+                    skipped.push(Skipped {
+                        address: ga.address,
+                        name: ga.name.clone(),
+                        dpt: ga.dpt,
+                    });
+                    continue;
+                };
+                let datapoint = Datapoint {
+                    role,
+                    address: ga.address,
+                    dpt,
+                };
+                match kinds.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, datapoints)) => datapoints.push(datapoint),
+                    None => kinds.push((kind, alloc::vec![datapoint])),
+                }
+            }
+
+            let room = slug(&room).unwrap_or_else(|| "house".to_string());
+            let several = kinds.len() > 1;
+            for (kind, datapoints) in kinds {
+                let base = match slug(&name) {
+                    Some(name) if several => format!("{}-{}", name, kind),
+                    Some(name) => name,
+                    None => kind.name().to_string(),
+                };
+                // Keep room/name unique (e.g. two "Light" functions in one room)
+                let mut name = base.clone();
+                let mut n = 1;
+                while !ids.insert(format!("{}/{}", room, name)) {
+                    n += 1;
+                    name = format!("{}-{}", base, n);
+                }
+
+                registry.devices.push(Device {
+                    name,
+                    room: room.clone(),
+                    location: location.clone(),
+                    kind,
+                    datapoints,
+                });
+            }
+        }
+
+        registry.validate()?;
+        Ok(Import { registry, skipped })
+    }
+}
+
+/// Device kind and role of a group address, `None` if no record carries its DPT
+///
+/// `blind` is set when the device has blind move/step objects, which turns
+/// percentages into positions and 1-bit status into the moving flag.
+fn classify(name: &str, dpt: Dpt, blind: bool) -> Option<(DeviceKind, Role)> {
+    let name = name.to_lowercase();
+    let words: Vec<&str> = name
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .collect();
+    let status = words.iter().any(|word| STATUS_WORDS.contains(word));
+    let has = |hints: &[&str]| hints.iter().any(|hint| name.contains(hint));
+    let state_or = |control: Role| if status { Role::State } else { control };
+
+    Some(match (dpt.main, dpt.sub) {
+        (1, Some(8)) => (DeviceKind::Blind, Role::Move),
+        (1, Some(7)) => (DeviceKind::Blind, Role::Step),
+        (1, _) if blind && status => (DeviceKind::Blind, Role::Moving),
+        (1, _) => (DeviceKind::Switch, state_or(Role::Control)),
+        (3, Some(7)) => (DeviceKind::Dimmer, Role::Step),
+        (5, Some(1)) if blind => {
+            let slat = has(&["slat", "lamell", "angle"]);
+            let role = match (slat, status) {
+                (true, true) => Role::SlatAngleState,
+                (true, false) => Role::SlatAngle,
+                (false, true) => Role::PositionState,
+                (false, false) => Role::Position,
+            };
+            (DeviceKind::Blind, role)
+        }
+        (5, Some(1)) => (DeviceKind::Dimmer, state_or(Role::Value)),
+        (9, Some(1)) if has(&["setpoint", "set point", "soll", "target"]) => {
+            (DeviceKind::Setpoint, state_or(Role::Control))
+        }
+        (9, Some(1)) => (DeviceKind::Temperature, Role::State),
+        (9, Some(4)) => (DeviceKind::Illuminance, Role::State),
+        (9, Some(5)) => (DeviceKind::WindSpeed, Role::State),
+        (9, Some(7)) => (DeviceKind::Humidity, Role::State),
+        (9, Some(8)) => (DeviceKind::Co2, Role::State),
+        (10, Some(1)) => (DeviceKind::Clock, Role::Time),
+        (11, Some(1)) => (DeviceKind::Clock, Role::Date),
+        (13, Some(10)) => (DeviceKind::EnergyCounter, Role::State),
+        (14, Some(56)) => (DeviceKind::Power, Role::State),
+        (16, Some(0 | 1)) => (DeviceKind::Text, state_or(Role::Control)),
+        (17, Some(1)) => (DeviceKind::Scene, Role::State),
+        (18, Some(1)) => (DeviceKind::Scene, state_or(Role::Control)),
+        (19, Some(1)) => (DeviceKind::Clock, Role::DateTime),
+        (20, Some(102)) => (DeviceKind::HvacMode, state_or(Role::Control)),
+        (232, Some(600)) | (251, Some(600)) => (DeviceKind::ColorLight, state_or(Role::Control)),
+        _ => return None,
+    })
+}
+
+/// Group address name without status and role words ("TV switch" → "TV")
+fn base_name(name: &str) -> String {
+    let words: Vec<&str> = name
+        .split_whitespace()
+        .filter(|word| {
+            let word = word.to_lowercase();
+            !STATUS_WORDS.contains(&word.as_str()) && !ROLE_WORDS.contains(&word.as_str())
+        })
+        .collect();
+    words.join(" ")
+}
+
+/// Lowercase MQTT topic level ("Living room" → "living-room"), `None` if empty
+fn slug(name: &str) -> Option<String> {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    (!slug.is_empty()).then_some(slug)
+}
+
+// ============================================================================
+// REPORTING
+// ============================================================================
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.dpt {
+            Some(dpt) => write!(
+                f,
+                "{} {:?}: DPT {} is not supported",
+                self.address, self.name, dpt
+            ),
+            None => write!(
+                f,
+                "{} {:?}: no DPT assigned in ETS",
+                self.address, self.name
+            ),
+        }
+    }
+}
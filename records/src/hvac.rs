@@ -0,0 +1,443 @@
+//! KNX HVAC Records
+//!
+//! Contains thermostat-related data structures and utilities:
+//! - HvacModeState / HvacModeControl: Operating mode (DPT 20.102)
+//! - SetpointState / SetpointControl: Temperature setpoint (DPT 9.001)
+//!
+//! A room thermostat uses separate group addresses for commands and status
+//! feedback, so each value has a state record (KNX → MQTT) and a control
+//! record (MQTT → KNX).
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// HVAC operating mode (DPT 20.102)
+///
+/// Serialized in snake_case, e.g. `"comfort"` or `"building_protection"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HvacMode {
+    /// Mode selected by the thermostat itself
+    Auto,
+    /// Room in use
+    Comfort,
+    /// Room briefly unused
+    Standby,
+    /// Night or absence setback
+    Economy,
+    /// Frost/heat protection
+    BuildingProtection,
+}
+
+impl HvacMode {
+    /// DPT 20.102 field value of this mode
+    pub const fn to_raw(self) -> u8 {
+        match self {
+            Self::Auto => 0,
+            Self::Comfort => 1,
+            Self::Standby => 2,
+            Self::Economy => 3,
+            Self::BuildingProtection => 4,
+        }
+    }
+
+    /// Mode for a DPT 20.102 field value, `None` for reserved values
+    pub const fn from_raw(raw: u8) -> Option<Self> {
+        match raw {
+            0 => Some(Self::Auto),
+            1 => Some(Self::Comfort),
+            2 => Some(Self::Standby),
+            3 => Some(Self::Economy),
+            4 => Some(Self::BuildingProtection),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HvacMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Comfort => "comfort",
+            Self::Standby => "standby",
+            Self::Economy => "economy",
+            Self::BuildingProtection => "building protection",
+        })
+    }
+}
+
+/// KNX HVAC mode state (DPT 20.102)
+///
+/// Represents the operating mode reported by a thermostat.
+/// Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HvacModeState {
+    /// KNX group address of the mode status object (e.g., "3/1/1")
+    pub address: GroupAddress,
+
+    /// Current operating mode
+    pub mode: HvacMode,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX HVAC mode control command (DPT 20.102)
+///
+/// Represents a mode change to be sent to a thermostat.
+/// Consumed by the gateway to control KNX devices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HvacModeControl {
+    /// KNX group address of the mode object (e.g., "3/1/0")
+    pub address: GroupAddress,
+
+    /// Requested operating mode
+    pub mode: HvacMode,
+}
+
+/// KNX temperature setpoint state (DPT 9.001)
+///
+/// Represents the active setpoint reported by a thermostat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetpointState {
+    /// KNX group address of the setpoint status object (e.g., "3/1/3")
+    pub address: GroupAddress,
+
+    /// Setpoint in Celsius
+    pub celsius: f32,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX temperature setpoint control command (DPT 9.001)
+///
+/// Represents a new setpoint to be sent to a thermostat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetpointControl {
+    /// KNX group address of the setpoint object (e.g., "3/1/2")
+    pub address: GroupAddress,
+
+    /// Requested setpoint in Celsius
+    pub celsius: f32,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl HvacModeState {
+    /// Create a new HvacModeState
+    pub fn new(address: GroupAddress, mode: HvacMode) -> Self {
+        Self {
+            address,
+            mode,
+            meta: None,
+        }
+    }
+}
+
+impl HvacModeControl {
+    /// Create a new HvacModeControl command
+    pub fn new(address: GroupAddress, mode: HvacMode) -> Self {
+        Self { address, mode }
+    }
+}
+
+impl SetpointState {
+    /// Create a new SetpointState
+    pub fn new(address: GroupAddress, celsius: f32) -> Self {
+        Self {
+            address,
+            celsius,
+            meta: None,
+        }
+    }
+}
+
+impl SetpointControl {
+    /// Create a new SetpointControl command
+    pub fn new(address: GroupAddress, celsius: f32) -> Self {
+        Self { address, celsius }
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(HvacModeState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(SetpointState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(HvacModeControl, SetpointControl);
+crate::impl_timestamped!(HvacModeState, SetpointState);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+fn hvac_mode_schema(description: &str) -> serde_json::Value {
+    crate::schema::string_enum(
+        &alloc::format!("{} (DPT 20.102)", description),
+        &[
+            "auto",
+            "comfort",
+            "standby",
+            "economy",
+            "building_protection",
+        ],
+    )
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for HvacModeState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record, record_meta};
+
+        record(
+            "HvacModeState",
+            "KNX thermostat operating mode status, published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the mode status object"),
+                ),
+                ("mode", hvac_mode_schema("Current operating mode")),
+                ("meta", record_meta()),
+            ],
+            &["address", "mode"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for HvacModeControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record};
+
+        record(
+            "HvacModeControl",
+            "KNX thermostat operating mode command, sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the mode object"),
+                ),
+                ("mode", hvac_mode_schema("Requested operating mode")),
+            ],
+            &["address", "mode"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for SetpointState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{celsius, group_address, record, record_meta};
+
+        record(
+            "SetpointState",
+            "KNX thermostat setpoint status (DPT 9.001), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the setpoint status object"),
+                ),
+                ("celsius", celsius("Setpoint")),
+                ("meta", record_meta()),
+            ],
+            &["address", "celsius"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for SetpointControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{celsius, group_address, record};
+
+        record(
+            "SetpointControl",
+            "KNX thermostat setpoint command (DPT 9.001), sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the setpoint object"),
+                ),
+                ("celsius", celsius("Requested setpoint")),
+            ],
+            &["address", "celsius"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for HvacModeState changes
+    ///
+    /// Logs all incoming HVAC mode updates.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn mode_state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<HvacModeState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🏠 HVAC mode monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to HvacModeState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!("🏠 HVAC mode: {} = {}", state.address, state.mode));
+        }
+    }
+
+    /// Monitor for HvacModeControl commands
+    ///
+    /// Logs all outgoing HVAC mode commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn mode_control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<HvacModeControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 HVAC mode control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to HvacModeControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            log.info(&format!(
+                "📤 HVAC mode: {} → {}",
+                control.address, control.mode
+            ));
+        }
+    }
+
+    /// Monitor for SetpointState changes
+    ///
+    /// Logs all incoming setpoint updates.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn setpoint_state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<SetpointState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🎯 Setpoint monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to SetpointState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!(
+                "🎯 Setpoint: {} = {:.1}°C",
+                state.address, state.celsius
+            ));
+        }
+    }
+
+    /// Monitor for SetpointControl commands
+    ///
+    /// Logs all outgoing setpoint commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn setpoint_control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<SetpointControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Setpoint control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to SetpointControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            log.info(&format!(
+                "📤 Setpoint: {} → {:.1}°C",
+                control.address, control.celsius
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use crate::dpt::{DptCodec, Float16};
+    use crate::error::RecordError;
+    use alloc::vec::Vec;
+
+    /// Deserialize HvacModeState from KNX DPT 20.102 (1-byte enum)
+    ///
+    /// Reserved mode values (5..255) are rejected.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (1 byte for DPT 20.102)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn mode_from_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<HvacModeState, RecordError> {
+        RecordError::check_dpt_length(data, 1)?;
+
+        let mode = HvacMode::from_raw(data[0]).ok_or(RecordError::ValueOutOfRange)?;
+
+        Ok(HvacModeState::new(group_address, mode))
+    }
+
+    /// Serialize HvacModeControl to KNX DPT 20.102 (1-byte enum)
+    pub fn mode_to_knx(control: &HvacModeControl) -> Result<Vec<u8>, RecordError> {
+        Ok(alloc::vec![control.mode.to_raw()])
+    }
+
+    /// Deserialize SetpointState from KNX DPT 9.001 (2-byte float)
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (2 bytes for DPT 9.001)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn setpoint_from_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<SetpointState, RecordError> {
+        let celsius = Float16::decode(data)?;
+
+        Ok(SetpointState::new(group_address, celsius))
+    }
+
+    /// Serialize SetpointControl to KNX DPT 9.001 (2-byte float)
+    pub fn setpoint_to_knx(control: &SetpointControl) -> Result<Vec<u8>, RecordError> {
+        Float16::encode(control.celsius)
+    }
+}
@@ -8,26 +8,48 @@
 //!
 //! Each record type is organized in its own module containing:
 //! - Data structures
-//! - Serialization/deserialization functions
+//! - A [`RecordCodec`] implementation for JSON encoding
 //! - Monitoring utilities (for logging/debugging)
 //!
 //! ## Modules
 //!
+//! - [`address`]: Typed KNX group addresses shared by all records
+//! - [`error`]: Error type returned by all record codecs
+//! - [`codec`]: Codec trait implemented by every record (JSON, optional postcard)
+//! - [`dpt`]: KNX datapoint type codecs for declared records
+//! - [`meta`]: Receive time and origin envelope carried by state records
+//! - [`record`]: KnxRecord trait behind `#[derive(KnxRecord)]`
+//! - [`routing`]: Allow-list checks of MQTT commands and the rejections reported for them
+//! - `schema`: JSON Schema documents of every record (`std` only)
 //! - [`switch`]: Switch-related records (SwitchState, SwitchControl)
+//! - [`text`]: Text message records (TextMessage, TextMessageControl)
 //! - [`temperature`]: Temperature sensor records
+//! - [`clock`]: Date and time records (ClockState, ClockControl)
+//! - [`color`]: RGB/RGBW color light records (ColorLightState, ColorLightControl)
+//! - [`dimmer`]: Dimming records (DimmerState, DimmerControl)
+//! - [`blinds`]: Blind/shutter records (BlindState, BlindControl)
+//! - [`energy`]: Energy metering records (EnergyCounter, PowerReading, EnergyTotal)
+//! - [`hvac`]: Thermostat records (HvacModeState/Control, SetpointState/Control)
+//! - [`scene`]: Scene records (SceneState, SceneControl)
+//! - [`sensor`]: DPT 9 sensor readings (humidity, illuminance, wind, CO2)
+//! - [`topic`]: Per-device MQTT topics (`knx/<room>/<name>/state`)
+//! - `registry`: Devices, group addresses and topics loaded from a TOML/JSON file (`registry` only)
+//! - `ets`: Device registry import from ETS projects and group address exports (`ets` only)
 //!
 //! ## Example Usage
 //!
 //! ```ignore
 //! use records::switch::{SwitchState, SwitchControl};
-//! use records::switch::json::{serialize_state, deserialize_state};
+//! use records::RecordCodec;
 //! use records::temperature::Temperature;
+//! use records::GroupAddress;
 //!
-//! // Create a switch state
-//! let state = SwitchState::new("1/0/7".into(), true);
+//! // Create a switch state (bad addresses are rejected here)
+//! let address: GroupAddress = "1/0/7".parse()?;
+//! let state = SwitchState::new(address, true);
 //!
 //! // Serialize to JSON
-//! let json = serialize_state(&state)?;
+//! let json = state.to_json()?;
 //!
 //! // Use monitor in aimdb tap
 //! builder.configure::<SwitchState>(|reg| {
@@ -40,14 +62,65 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+// Lets `#[derive(KnxRecord)]` output refer to `::records` inside this crate
+extern crate self as records;
 
 // Re-export serde so derive macros work properly in submodules
 pub use serde;
 
+// Shared building blocks
+pub mod address;
+pub mod codec;
+pub mod dpt;
+pub mod error;
+#[cfg(feature = "ets")]
+pub mod ets;
+pub mod meta;
+pub mod record;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod routing;
+#[cfg(feature = "std")]
+pub mod schema;
+pub mod topic;
+
 // Per-record modules
+pub mod blinds;
+pub mod clock;
+pub mod color;
+pub mod dimmer;
+pub mod energy;
+pub mod hvac;
+pub mod scene;
+pub mod sensor;
 pub mod switch;
 pub mod temperature;
+pub mod text;
 
 // Re-export commonly used types for convenience
+pub use address::{GroupAddress, GroupAddressError, IndividualAddress, IndividualAddressError};
+pub use blinds::{BlindCommand, BlindControl, BlindState};
+pub use clock::{ClockControl, ClockState, Date, DateTime, DateTimeFlags, TimeOfDay, Weekday};
+pub use codec::{Encoding, RecordCodec};
+pub use color::{ColorCommand, ColorLightControl, ColorLightState, Hsv, Rgbw};
+pub use dimmer::{DimmerCommand, DimmerControl, DimmerState};
+pub use energy::{EnergyCounter, EnergyTotal, EnergyTracker, PowerReading};
+pub use error::RecordError;
+pub use hvac::{HvacMode, HvacModeControl, HvacModeState, SetpointControl, SetpointState};
+pub use meta::{RecordMeta, Timestamped};
+pub use record::KnxRecord;
+pub use records_derive::KnxRecord;
+#[cfg(feature = "registry")]
+pub use registry::{Datapoint, Device, DeviceKind, Dpt, Registry, RegistryError, Role};
+pub use routing::{AllowList, CommandRejection, RejectReason, Route};
+pub use scene::{SceneControl, SceneState};
+#[cfg(feature = "std")]
+pub use schema::{RecordSchema, RecordSchemas};
+pub use sensor::{Co2, Humidity, Illuminance, Quantity, SensorReading, SensorUnit, WindSpeed};
 pub use switch::{SwitchControl, SwitchState};
 pub use temperature::Temperature;
+pub use text::{KnxText, TextEncoding, TextMessage, TextMessageControl};
+pub use topic::DeviceTopic;
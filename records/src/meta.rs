@@ -0,0 +1,140 @@
+//! Record Metadata
+//!
+//! Contains the optional envelope carried by state records:
+//! - RecordMeta: When, where and by which gateway a value was received
+//! - Timestamped: Access to the metadata of a state record
+//!
+//! The gateway stamps each state decoded from a KNX telegram, and the
+//! envelope travels with the record as a `"meta"` JSON field so consumers
+//! can tell a fresh reading from a retained, stale one. Records published
+//! without it (older gateways, manual `mosquitto_pub`) have `"meta": null`.
+//!
+//! The gateway has no wall clock, so `uptime_ms` is its primary timestamp.
+//! Uptimes are only comparable within one `boot` of one `gateway`, and the
+//! highest `boot` is the gateway's current one; `utc_ms` is filled only when
+//! the gateway clock is synchronized.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+use crate::address::IndividualAddress;
+use crate::error::RecordError;
+use core::fmt;
+use heapless::String as HeaplessString;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Identifier of the gateway that received a telegram (e.g. "knx-gateway-001")
+pub type GatewayId = HeaplessString<{ RecordMeta::MAX_GATEWAY_ID_LEN }>;
+
+/// Origin and receive time of a state record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordMeta {
+    /// Gateway that received the telegram
+    pub gateway: GatewayId,
+
+    /// Boot counter of the gateway, one higher after every reboot
+    pub boot: u32,
+
+    /// Milliseconds since the gateway booted when the telegram arrived
+    pub uptime_ms: u64,
+
+    /// Unix time in milliseconds, when the gateway clock is synchronized
+    #[serde(default)]
+    pub utc_ms: Option<u64>,
+
+    /// KNX individual address of the sending device, when known
+    #[serde(default)]
+    pub source: Option<IndividualAddress>,
+}
+
+/// A state record that can carry a [`RecordMeta`] envelope
+pub trait Timestamped {
+    /// Metadata of the record, if it was stamped
+    fn meta(&self) -> Option<&RecordMeta>;
+
+    /// Attach metadata to the record
+    fn set_meta(&mut self, meta: RecordMeta);
+
+    /// Attach metadata to the record, builder style
+    fn with_meta(mut self, meta: RecordMeta) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_meta(meta);
+        self
+    }
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl RecordMeta {
+    /// Maximum length of a gateway id
+    pub const MAX_GATEWAY_ID_LEN: usize = 24;
+
+    /// Upper bound of the JSON added to a record by its `"meta"` field
+    ///
+    /// Records carrying metadata add this to their no_std JSON buffer.
+    pub const MAX_JSON_LEN: usize = 160;
+
+    /// Create metadata for a telegram received `uptime_ms` after boot
+    ///
+    /// Fails with [`RecordError::ValueOutOfRange`] if `gateway` is longer
+    /// than 24 bytes.
+    pub fn new(gateway: &str, boot: u32, uptime_ms: u64) -> Result<Self, RecordError> {
+        let mut id = GatewayId::new();
+        id.push_str(gateway)
+            .map_err(|_| RecordError::ValueOutOfRange)?;
+        Ok(Self {
+            gateway: id,
+            boot,
+            uptime_ms,
+            utc_ms: None,
+            source: None,
+        })
+    }
+
+    /// Set the Unix time of the telegram
+    pub fn with_utc_ms(mut self, utc_ms: u64) -> Self {
+        self.utc_ms = Some(utc_ms);
+        self
+    }
+
+    /// Set the individual address of the sending device
+    pub fn with_source(mut self, source: IndividualAddress) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl fmt::Display for RecordMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} @ {} ms", self.gateway, self.uptime_ms)?;
+        if let Some(source) = self.source {
+            write!(f, " from {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+/// Implement [`Timestamped`] for records with a `meta: Option<RecordMeta>` field
+#[macro_export]
+macro_rules! impl_timestamped {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::meta::Timestamped for $ty {
+                fn meta(&self) -> Option<&$crate::meta::RecordMeta> {
+                    self.meta.as_ref()
+                }
+
+                fn set_meta(&mut self, meta: $crate::meta::RecordMeta) {
+                    self.meta = Some(meta);
+                }
+            }
+        )+
+    };
+}
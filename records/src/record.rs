@@ -0,0 +1,120 @@
+//! Declarative KNX Records
+//!
+//! Contains the [`KnxRecord`] trait implemented by `#[derive(KnxRecord)]`
+//! (see the `records-derive` crate, re-exported as `records::KnxRecord`).
+//! A declared record is a struct with an `address: GroupAddress` field and
+//! one value field whose type matches the DPT:
+//!
+//! ```ignore
+//! use records::{GroupAddress, KnxRecord};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, KnxRecord)]
+//! #[knx(dpt = "1.019")]
+//! pub struct WindowState {
+//!     pub address: GroupAddress,
+//!     pub is_open: bool,
+//! }
+//!
+//! let state = WindowState::new("3/1/0".parse()?, true);
+//! let json = state.to_json()?;                       // RecordCodec
+//! let bytes = state.to_knx()?;                       // KnxRecord (knx feature)
+//! builder.configure::<WindowState>(|reg| {
+//!     reg.tap(records::record::monitors::monitor::<WindowState, _>)
+//!     // ...
+//! });
+//! ```
+//!
+//! [`PowerReading`](crate::PowerReading) is declared this way. Records whose
+//! KNX mapping is not a single DPT value (dimmers, blinds, clocks) keep
+//! their hand-written modules.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+use crate::address::GroupAddress;
+use crate::codec::RecordCodec;
+use crate::dpt::DptCodec;
+use core::fmt::Debug;
+
+#[cfg(feature = "knx")]
+use crate::error::RecordError;
+#[cfg(feature = "knx")]
+use alloc::vec::Vec;
+
+/// A record carrying one DPT value for one group address
+pub trait KnxRecord: RecordCodec + Debug + Clone {
+    /// Wire format of the value (e.g. [`Float16`](crate::dpt::Float16) for DPT 9.xxx)
+    type Dpt: DptCodec;
+
+    /// Record type name used in logs
+    const NAME: &'static str;
+
+    /// KNX datapoint type identifier (e.g. "9.001")
+    const DPT: &'static str;
+
+    /// Create a record from its address and value
+    fn from_value(address: GroupAddress, value: <Self::Dpt as DptCodec>::Value) -> Self;
+
+    /// KNX group address of the record
+    fn address(&self) -> GroupAddress;
+
+    /// Value carried by the record
+    fn value(&self) -> <Self::Dpt as DptCodec>::Value;
+
+    /// Deserialize the record from a raw KNX telegram
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes
+    /// * `group_address` - KNX group address the telegram was received on
+    #[cfg(feature = "knx")]
+    fn from_knx(data: &[u8], group_address: GroupAddress) -> Result<Self, RecordError> {
+        Self::Dpt::decode(data).map(|value| Self::from_value(group_address, value))
+    }
+
+    /// Serialize the record's value to a raw KNX telegram
+    #[cfg(feature = "knx")]
+    fn to_knx(&self) -> Result<Vec<u8>, RecordError> {
+        Self::Dpt::encode(self.value())
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for any declared record
+    ///
+    /// Logs every value with the record's address and DPT.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn monitor<T, R>(ctx: RuntimeContext<R>, consumer: Consumer<T, R>)
+    where
+        T: KnxRecord + Send + Sync + 'static,
+        <T::Dpt as DptCodec>::Value: Debug,
+        R: Runtime,
+    {
+        let log = ctx.log();
+        log.info(&format!("📡 {} monitor started", T::NAME));
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error(&format!("Failed to subscribe to {} buffer", T::NAME));
+            return;
+        };
+
+        while let Ok(record) = reader.recv().await {
+            log.info(&format!(
+                "📡 {}: {} = {:?} (DPT {})",
+                T::NAME,
+                record.address(),
+                record.value(),
+                T::DPT
+            ));
+        }
+    }
+}
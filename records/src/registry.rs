@@ -0,0 +1,715 @@
+//! Device Registry
+//!
+//! Contains the devices of a house, loaded from a TOML or JSON file
+//! (`registry` feature, std only):
+//! - Registry: All devices, validated on load
+//! - Device: One KNX device with its name, room, record kind and topics
+//! - Datapoint: One group address of a device, with its role and DPT
+//! - DeviceKind / Role: Record family of a device and the datapoints it may use
+//!
+//! Group addresses and topics thereby live in a file rather than in Rust
+//! constants:
+//!
+//! ```toml
+//! [[devices]]
+//! name = "tv"
+//! room = "living-room"
+//! kind = "switch"
+//! datapoints = [
+//!     { role = "state", address = "1/0/7", dpt = "1.001" },
+//!     { role = "control", address = "1/0/6", dpt = "1.001" },
+//! ]
+//! ```
+//!
+//! Every device has its own topics, `knx/<room>/<name>/state` and `/control`
+//! (see [`DeviceTopic`]), which the gateway derives the same way.
+
+use crate::address::GroupAddress;
+use crate::topic::DeviceTopic;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::Path;
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Record family of a device, selecting its state and control records
+///
+/// Written in kebab-case in the registry file (e.g. `"color-light"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeviceKind {
+    /// [`SwitchState`](crate::SwitchState) / [`SwitchControl`](crate::SwitchControl)
+    Switch,
+    /// [`Temperature`](crate::Temperature)
+    Temperature,
+    /// [`DimmerState`](crate::DimmerState) / [`DimmerControl`](crate::DimmerControl)
+    Dimmer,
+    /// [`BlindState`](crate::BlindState) / [`BlindControl`](crate::BlindControl)
+    Blind,
+    /// [`SensorReading<Illuminance>`](crate::SensorReading)
+    Illuminance,
+    /// [`SensorReading<WindSpeed>`](crate::SensorReading)
+    WindSpeed,
+    /// [`SensorReading<Humidity>`](crate::SensorReading)
+    Humidity,
+    /// [`SensorReading<Co2>`](crate::SensorReading)
+    Co2,
+    /// [`EnergyCounter`](crate::EnergyCounter)
+    EnergyCounter,
+    /// [`PowerReading`](crate::PowerReading)
+    Power,
+    /// [`ColorLightState`](crate::ColorLightState) / [`ColorLightControl`](crate::ColorLightControl)
+    ColorLight,
+    /// [`SceneState`](crate::SceneState) / [`SceneControl`](crate::SceneControl)
+    Scene,
+    /// [`ClockState`](crate::ClockState)
+    Clock,
+    /// [`TextMessage`](crate::TextMessage) / [`TextMessageControl`](crate::TextMessageControl)
+    Text,
+    /// [`HvacModeState`](crate::HvacModeState) / [`HvacModeControl`](crate::HvacModeControl)
+    HvacMode,
+    /// [`SetpointState`](crate::SetpointState) / [`SetpointControl`](crate::SetpointControl)
+    Setpoint,
+}
+
+/// Function of a datapoint within its device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Role {
+    /// Status feedback of the device's main value
+    State,
+    /// Command object of the device's main value
+    Control,
+    /// Absolute value command (dimmer brightness)
+    Value,
+    /// Relative step command (dimmer DPT 3.007, blind stop/step DPT 1.007)
+    Step,
+    /// Long-time up/down command (blind)
+    Move,
+    /// Absolute position command (blind)
+    Position,
+    /// Absolute slat angle command (blind)
+    SlatAngle,
+    /// Position status (blind)
+    PositionState,
+    /// Slat angle status (blind)
+    SlatAngleState,
+    /// Movement status (blind)
+    Moving,
+    /// Time of day (clock)
+    Time,
+    /// Date (clock)
+    Date,
+    /// Date and time (clock)
+    DateTime,
+}
+
+/// KNX datapoint type, e.g. `9.001`
+///
+/// A main number without sub-type (`9`, written `9.xxx`) stands for any
+/// sub-type. Parses `9.001`, `9.xxx`, `9` and the ETS forms `DPST-9-1` and
+/// `DPT-9`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dpt {
+    /// Main number (format), e.g. 9 for 2-byte floats
+    pub main: u16,
+    /// Sub-type (unit/meaning), `None` for any
+    pub sub: Option<u16>,
+}
+
+/// One group address of a device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Datapoint {
+    /// Function within the device
+    pub role: Role,
+    /// KNX group address
+    pub address: GroupAddress,
+    /// Datapoint type of the group address
+    pub dpt: Dpt,
+}
+
+/// One KNX device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Device {
+    /// Device name, unique within its room (e.g. "tv")
+    pub name: String,
+
+    /// Room the device is in (e.g. "living-room")
+    pub room: String,
+
+    /// Spaces enclosing the room, outermost first (e.g. building, floor)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub location: Vec<String>,
+
+    /// Record family of the device
+    pub kind: DeviceKind,
+
+    /// Group addresses of the device
+    pub datapoints: Vec<Datapoint>,
+}
+
+/// All devices of a house
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Registry {
+    /// Devices in file order
+    #[serde(default)]
+    pub devices: Vec<Device>,
+}
+
+/// Error returned when a registry cannot be read or is inconsistent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// File could not be read or written
+    Io(String),
+    /// File is not valid TOML/JSON or does not match the registry layout
+    Parse(String),
+    /// Device name or room is empty or not usable in an MQTT topic
+    InvalidName(String),
+    /// Two devices share a room and name
+    DuplicateDevice(String),
+    /// Device has no datapoints
+    NoDatapoints(String),
+    /// Datapoint role is not used by the device kind
+    UnsupportedRole {
+        /// Device as `room/name`
+        device: String,
+        /// Role of the datapoint
+        role: Role,
+    },
+    /// Datapoint DPT does not match its role
+    UnsupportedDpt {
+        /// Device as `room/name`
+        device: String,
+        /// Role of the datapoint
+        role: Role,
+        /// DPT of the datapoint
+        dpt: Dpt,
+    },
+    /// Device has none of the roles carrying its kind's main value
+    MissingRole {
+        /// Device as `room/name`
+        device: String,
+        /// Roles of which the device needs at least one
+        roles: &'static [Role],
+    },
+    /// Group address is used by two devices
+    SharedAddress {
+        /// The group address
+        address: GroupAddress,
+        /// Device listed first, as `room/name`
+        first: String,
+        /// Device listed second, as `room/name`
+        second: String,
+    },
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl Dpt {
+    /// A DPT with sub-type, e.g. `Dpt::new(9, 1)` for 9.001
+    pub const fn new(main: u16, sub: u16) -> Self {
+        Self {
+            main,
+            sub: Some(sub),
+        }
+    }
+
+    /// Any sub-type of a main number, e.g. `Dpt::any(1)` for 1.xxx
+    pub const fn any(main: u16) -> Self {
+        Self { main, sub: None }
+    }
+
+    /// Whether `self`, used as a pattern, accepts `dpt`
+    ///
+    /// Sub-types only need to agree when both sides name one.
+    pub fn accepts(&self, dpt: &Dpt) -> bool {
+        self.main == dpt.main
+            && match (self.sub, dpt.sub) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+impl Registry {
+    /// Parse and validate a TOML registry
+    pub fn from_toml(text: &str) -> Result<Self, RegistryError> {
+        let registry: Self =
+            toml::from_str(text).map_err(|e| RegistryError::Parse(e.to_string()))?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    /// Parse and validate a JSON registry
+    pub fn from_json(text: &str) -> Result<Self, RegistryError> {
+        let registry: Self =
+            serde_json::from_str(text).map_err(|e| RegistryError::Parse(e.to_string()))?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    /// Load a registry file, JSON for `.json` and TOML otherwise
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| RegistryError::Io(format!("{}: {}", path.display(), e)))?;
+
+        if is_json(path) {
+            Self::from_json(&text)
+        } else {
+            Self::from_toml(&text)
+        }
+    }
+
+    /// Write the registry to a file, JSON for `.json` and TOML otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RegistryError> {
+        let path = path.as_ref();
+        let text = if is_json(path) {
+            serde_json::to_string_pretty(self).map_err(|e| RegistryError::Parse(e.to_string()))?
+        } else {
+            self.to_toml()?
+        };
+
+        std::fs::write(path, text)
+            .map_err(|e| RegistryError::Io(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Format the registry as TOML
+    pub fn to_toml(&self) -> Result<String, RegistryError> {
+        toml::to_string(self).map_err(|e| RegistryError::Parse(e.to_string()))
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+// ============================================================================
+// DEVICE KINDS
+// ============================================================================
+
+/// Accepted DPTs per role of a device kind
+type RoleTable = &'static [(Role, &'static [Dpt])];
+
+const BOOLEAN: &[Dpt] = &[Dpt::any(1)];
+const PERCENT: &[Dpt] = &[Dpt::new(5, 1)];
+const DIMMING_STEP: &[Dpt] = &[Dpt::new(3, 7)];
+const BLIND_MOVE: &[Dpt] = &[Dpt::new(1, 8)];
+const BLIND_STEP: &[Dpt] = &[Dpt::new(1, 7)];
+const CELSIUS: &[Dpt] = &[Dpt::new(9, 1)];
+const LUX: &[Dpt] = &[Dpt::new(9, 4)];
+const WIND: &[Dpt] = &[Dpt::new(9, 5)];
+const HUMIDITY: &[Dpt] = &[Dpt::new(9, 7)];
+const PPM: &[Dpt] = &[Dpt::new(9, 8)];
+const ACTIVE_ENERGY: &[Dpt] = &[Dpt::new(13, 10)];
+const POWER: &[Dpt] = &[Dpt::new(14, 56)];
+const COLOR: &[Dpt] = &[Dpt::new(232, 600), Dpt::new(251, 600)];
+const SCENE_STATE: &[Dpt] = &[Dpt::new(17, 1), Dpt::new(18, 1)];
+const SCENE_CONTROL: &[Dpt] = &[Dpt::new(18, 1)];
+const TIME: &[Dpt] = &[Dpt::new(10, 1)];
+const DATE: &[Dpt] = &[Dpt::new(11, 1)];
+const DATE_TIME: &[Dpt] = &[Dpt::new(19, 1)];
+const TEXT: &[Dpt] = &[Dpt::new(16, 0), Dpt::new(16, 1)];
+const HVAC_MODE: &[Dpt] = &[Dpt::new(20, 102)];
+
+impl DeviceKind {
+    /// Every device kind
+    pub const ALL: [DeviceKind; 16] = [
+        Self::Switch,
+        Self::Temperature,
+        Self::Dimmer,
+        Self::Blind,
+        Self::Illuminance,
+        Self::WindSpeed,
+        Self::Humidity,
+        Self::Co2,
+        Self::EnergyCounter,
+        Self::Power,
+        Self::ColorLight,
+        Self::Scene,
+        Self::Clock,
+        Self::Text,
+        Self::HvacMode,
+        Self::Setpoint,
+    ];
+
+    /// Name in the registry file (e.g. `"color-light"`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Switch => "switch",
+            Self::Temperature => "temperature",
+            Self::Dimmer => "dimmer",
+            Self::Blind => "blind",
+            Self::Illuminance => "illuminance",
+            Self::WindSpeed => "wind-speed",
+            Self::Humidity => "humidity",
+            Self::Co2 => "co2",
+            Self::EnergyCounter => "energy-counter",
+            Self::Power => "power",
+            Self::ColorLight => "color-light",
+            Self::Scene => "scene",
+            Self::Clock => "clock",
+            Self::Text => "text",
+            Self::HvacMode => "hvac-mode",
+            Self::Setpoint => "setpoint",
+        }
+    }
+
+    /// Roles a device of this kind may use, with the DPTs each accepts
+    pub fn roles(self) -> RoleTable {
+        match self {
+            Self::Switch => &[(Role::State, BOOLEAN), (Role::Control, BOOLEAN)],
+            Self::Temperature => &[(Role::State, CELSIUS)],
+            Self::Dimmer => &[
+                (Role::State, PERCENT),
+                (Role::Value, PERCENT),
+                (Role::Step, DIMMING_STEP),
+            ],
+            Self::Blind => &[
+                (Role::Move, BLIND_MOVE),
+                (Role::Step, BLIND_STEP),
+                (Role::Position, PERCENT),
+                (Role::SlatAngle, PERCENT),
+                (Role::PositionState, PERCENT),
+                (Role::SlatAngleState, PERCENT),
+                (Role::Moving, BOOLEAN),
+            ],
+            Self::Illuminance => &[(Role::State, LUX)],
+            Self::WindSpeed => &[(Role::State, WIND)],
+            Self::Humidity => &[(Role::State, HUMIDITY)],
+            Self::Co2 => &[(Role::State, PPM)],
+            Self::EnergyCounter => &[(Role::State, ACTIVE_ENERGY)],
+            Self::Power => &[(Role::State, POWER)],
+            Self::ColorLight => &[(Role::State, COLOR), (Role::Control, COLOR)],
+            Self::Scene => &[(Role::State, SCENE_STATE), (Role::Control, SCENE_CONTROL)],
+            Self::Clock => &[
+                (Role::Time, TIME),
+                (Role::Date, DATE),
+                (Role::DateTime, DATE_TIME),
+            ],
+            Self::Text => &[(Role::State, TEXT), (Role::Control, TEXT)],
+            Self::HvacMode => &[(Role::State, HVAC_MODE), (Role::Control, HVAC_MODE)],
+            Self::Setpoint => &[(Role::State, CELSIUS), (Role::Control, CELSIUS)],
+        }
+    }
+
+    /// Roles carrying the main value of this kind, as status or command
+    ///
+    /// A device needs at least one of them: a dimmer with only a step
+    /// object or a blind with only up/down has no value to report or set.
+    pub fn main_roles(self) -> &'static [Role] {
+        match self {
+            Self::Dimmer => &[Role::State, Role::Value],
+            Self::Blind => &[Role::Position, Role::PositionState],
+            Self::Clock => &[Role::Time, Role::Date, Role::DateTime],
+            Self::Switch
+            | Self::ColorLight
+            | Self::Scene
+            | Self::Text
+            | Self::HvacMode
+            | Self::Setpoint => &[Role::State, Role::Control],
+            Self::Temperature
+            | Self::Illuminance
+            | Self::WindSpeed
+            | Self::Humidity
+            | Self::Co2
+            | Self::EnergyCounter
+            | Self::Power => &[Role::State],
+        }
+    }
+}
+
+impl Role {
+    /// Whether the gateway writes to this datapoint (commands, not status)
+    pub fn is_writable(self) -> bool {
+        matches!(
+            self,
+            Self::Control
+                | Self::Value
+                | Self::Step
+                | Self::Move
+                | Self::Position
+                | Self::SlatAngle
+        )
+    }
+}
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+impl Device {
+    /// Room and name of the device, from which its topics derive
+    pub fn topic(&self) -> DeviceTopic<'_> {
+        DeviceTopic::new(&self.room, &self.name)
+    }
+
+    /// Device id used in messages, `room/name`
+    pub fn id(&self) -> String {
+        self.topic().id()
+    }
+
+    /// MQTT topic of the state record
+    pub fn state_topic(&self) -> String {
+        self.topic().state()
+    }
+
+    /// MQTT topic of the control record, `None` for read-only devices
+    pub fn control_topic(&self) -> Option<String> {
+        self.writable_addresses().next()?;
+        Some(self.topic().control())
+    }
+
+    /// Whether any datapoint of the device uses this group address
+    pub fn uses(&self, address: GroupAddress) -> bool {
+        self.datapoints
+            .iter()
+            .any(|datapoint| datapoint.address == address)
+    }
+
+    /// Group address of the first datapoint with this role
+    pub fn address(&self, role: Role) -> Option<GroupAddress> {
+        self.datapoints
+            .iter()
+            .find(|datapoint| datapoint.role == role)
+            .map(|datapoint| datapoint.address)
+    }
+
+    /// Group addresses the gateway may write to for this device
+    pub fn writable_addresses(&self) -> impl Iterator<Item = GroupAddress> + '_ {
+        self.datapoints
+            .iter()
+            .filter(|datapoint| datapoint.role.is_writable())
+            .map(|datapoint| datapoint.address)
+    }
+}
+
+impl Registry {
+    /// Devices of one kind, in file order
+    pub fn devices(&self, kind: DeviceKind) -> impl Iterator<Item = &Device> + '_ {
+        self.devices
+            .iter()
+            .filter(move |device| device.kind == kind)
+    }
+
+    /// First device of a kind using this group address
+    ///
+    /// Records carry the group address they were received on or are meant
+    /// for, which ties them to their device.
+    pub fn device_at(&self, kind: DeviceKind, address: GroupAddress) -> Option<&Device> {
+        self.devices(kind).find(|device| device.uses(address))
+    }
+
+    /// Kinds with at least one device
+    pub fn kinds(&self) -> BTreeSet<DeviceKind> {
+        self.devices.iter().map(|device| device.kind).collect()
+    }
+
+    /// Group addresses the gateway may write to, over all devices
+    pub fn writable_addresses(&self) -> BTreeSet<GroupAddress> {
+        self.devices
+            .iter()
+            .flat_map(Device::writable_addresses)
+            .collect()
+    }
+
+    /// Check names, roles, DPTs and group addresses of all devices
+    ///
+    /// A device may use one group address for several roles (a scene's
+    /// state and control), but no two devices may share one.
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        let mut ids = BTreeSet::new();
+        let mut owners = BTreeMap::new();
+
+        for device in &self.devices {
+            let id = device.id();
+            if !is_topic_level(&device.name) || !is_topic_level(&device.room) {
+                return Err(RegistryError::InvalidName(id));
+            }
+            if device.datapoints.is_empty() {
+                return Err(RegistryError::NoDatapoints(id));
+            }
+
+            let roles = device.kind.roles();
+            for datapoint in &device.datapoints {
+                let Some((_, dpts)) = roles.iter().find(|(role, _)| *role == datapoint.role) else {
+                    return Err(RegistryError::UnsupportedRole {
+                        device: id,
+                        role: datapoint.role,
+                    });
+                };
+                if !dpts.iter().any(|dpt| dpt.accepts(&datapoint.dpt)) {
+                    return Err(RegistryError::UnsupportedDpt {
+                        device: id,
+                        role: datapoint.role,
+                        dpt: datapoint.dpt,
+                    });
+                }
+            }
+
+            let roles = device.kind.main_roles();
+            if !roles.iter().any(|&role| device.address(role).is_some()) {
+                return Err(RegistryError::MissingRole { device: id, roles });
+            }
+
+            if !ids.insert(id.clone()) {
+                return Err(RegistryError::DuplicateDevice(id));
+            }
+
+            let addresses: BTreeSet<_> = device.datapoints.iter().map(|dp| dp.address).collect();
+            for address in addresses {
+                if let Some(first) = owners.insert(address, id.clone()) {
+                    return Err(RegistryError::SharedAddress {
+                        address,
+                        first,
+                        second: id,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A non-empty MQTT topic level without separators or wildcards
+fn is_topic_level(s: &str) -> bool {
+    !s.is_empty() && !s.contains(['/', '+', '#']) && !s.chars().any(char::is_whitespace)
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+impl fmt::Display for DeviceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::State => "state",
+            Self::Control => "control",
+            Self::Value => "value",
+            Self::Step => "step",
+            Self::Move => "move",
+            Self::Position => "position",
+            Self::SlatAngle => "slat-angle",
+            Self::PositionState => "position-state",
+            Self::SlatAngleState => "slat-angle-state",
+            Self::Moving => "moving",
+            Self::Time => "time",
+            Self::Date => "date",
+            Self::DateTime => "date-time",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for Dpt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.sub {
+            Some(sub) => write!(f, "{}.{:03}", self.main, sub),
+            None => write!(f, "{}.xxx", self.main),
+        }
+    }
+}
+
+impl FromStr for Dpt {
+    type Err = RegistryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RegistryError::Parse(format!("invalid DPT {:?}", s));
+        let number = |part: &str| part.parse::<u16>().map_err(|_| invalid());
+
+        // ETS: "DPST-9-1" (sub-type) or "DPT-9" (main number)
+        if let Some(rest) = s.strip_prefix("DPST-") {
+            let (main, sub) = rest.split_once('-').ok_or_else(invalid)?;
+            return Ok(Self::new(number(main)?, number(sub)?));
+        }
+        if let Some(main) = s.strip_prefix("DPT-") {
+            return Ok(Self::any(number(main)?));
+        }
+
+        match s.split_once('.') {
+            Some((main, "xxx")) => Ok(Self::any(number(main)?)),
+            Some((main, sub)) => Ok(Self::new(number(main)?, number(sub)?)),
+            None => Ok(Self::any(number(s)?)),
+        }
+    }
+}
+
+impl Serialize for Dpt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dpt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot access registry: {}", e),
+            Self::Parse(e) => write!(f, "invalid registry: {}", e),
+            Self::InvalidName(id) => write!(
+                f,
+                "device {}: name and room must be non-empty, without spaces, '/', '+' or '#'",
+                id
+            ),
+            Self::DuplicateDevice(id) => write!(f, "device {} is listed twice", id),
+            Self::NoDatapoints(id) => write!(f, "device {} has no datapoints", id),
+            Self::UnsupportedRole { device, role } => {
+                write!(
+                    f,
+                    "device {}: role {} is not used by its kind",
+                    device, role
+                )
+            }
+            Self::UnsupportedDpt { device, role, dpt } => {
+                write!(
+                    f,
+                    "device {}: DPT {} does not fit role {}",
+                    device, dpt, role
+                )
+            }
+            Self::MissingRole { device, roles } => {
+                write!(f, "device {}: needs a datapoint with role ", device)?;
+                for (i, role) in roles.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" or ")?;
+                    }
+                    write!(f, "{}", role)?;
+                }
+                Ok(())
+            }
+            Self::SharedAddress {
+                address,
+                first,
+                second,
+            } => write!(
+                f,
+                "group address {} is used by both {} and {}",
+                address, first, second
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
@@ -0,0 +1,171 @@
+//! Command Routing
+//!
+//! Contains the gateway's checks on control commands received from MQTT:
+//! - AllowList: Group addresses commands of one record type may write to
+//! - Route: Where a command goes, a group address or the error topic
+//! - CommandRejection: Record published for commands the gateway refuses
+//!
+//! A command names its target in its `address` field. [`AllowList::route`]
+//! sends it to that group address only if the address is on the allow-list;
+//! other commands never reach the bus and are answered with a
+//! [`CommandRejection`] on [`CommandRejection::MQTT_TOPIC`] instead.
+//!
+//! This module is no_std and works in both embedded and std environments.
+
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Group addresses commands of one record type may write to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowList<'a> {
+    addresses: &'a [GroupAddress],
+}
+
+/// Destination of one command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Write the command to this group address
+    Write(GroupAddress),
+    /// Refuse the command and report it on the error topic
+    Reject(RejectReason),
+}
+
+/// Why the gateway refused a command
+///
+/// Serialized in kebab-case, e.g. `"unknown-address"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[serde(rename_all = "kebab-case")]
+pub enum RejectReason {
+    /// The group address is not on the gateway's allow-list
+    UnknownAddress,
+}
+
+/// Control command refused by the gateway
+///
+/// Published on [`CommandRejection::MQTT_TOPIC`] so that the sender learns
+/// that its command did not reach the bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRejection {
+    /// Group address the command named (e.g., "1/0/9")
+    pub address: GroupAddress,
+
+    /// Why the command was refused
+    pub reason: RejectReason,
+
+    /// Gateway metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl<'a> AllowList<'a> {
+    /// Create an allow-list of these group addresses
+    pub const fn new(addresses: &'a [GroupAddress]) -> Self {
+        Self { addresses }
+    }
+
+    /// Allowed group addresses
+    pub fn addresses(&self) -> &'a [GroupAddress] {
+        self.addresses
+    }
+}
+
+impl CommandRejection {
+    /// MQTT topic for publishing refused commands
+    pub const MQTT_TOPIC: &'static str = "knx/errors";
+
+    /// Create a new CommandRejection
+    pub fn new(address: GroupAddress, reason: RejectReason) -> Self {
+        Self {
+            address,
+            reason,
+            meta: None,
+        }
+    }
+}
+
+// ============================================================================
+// ROUTING
+// ============================================================================
+
+impl AllowList<'_> {
+    /// Whether commands may write to this group address
+    pub fn contains(&self, address: GroupAddress) -> bool {
+        self.addresses.contains(&address)
+    }
+
+    /// Route a command naming this group address
+    pub fn route(&self, address: GroupAddress) -> Route {
+        if self.contains(address) {
+            Route::Write(address)
+        } else {
+            Route::Reject(RejectReason::UnknownAddress)
+        }
+    }
+}
+
+impl Route {
+    /// Rejection to publish for a command naming `address`, `None` if it is written
+    pub fn rejection(&self, address: GroupAddress) -> Option<CommandRejection> {
+        match *self {
+            Route::Write(_) => None,
+            Route::Reject(reason) => Some(CommandRejection::new(address, reason)),
+        }
+    }
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnknownAddress => "group address not on the allow-list",
+        })
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(CommandRejection => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_timestamped!(CommandRejection);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for CommandRejection {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record, record_meta, string_enum};
+
+        record(
+            "CommandRejection",
+            "Control command refused by the gateway, published on its error topic",
+            [
+                (
+                    "address",
+                    group_address("KNX group address the command named"),
+                ),
+                (
+                    "reason",
+                    string_enum(
+                        "Why the command was refused: `unknown-address` if the group address is not on the gateway's allow-list",
+                        &["unknown-address"],
+                    ),
+                ),
+                ("meta", record_meta()),
+            ],
+            &["address", "reason"],
+        )
+    }
+}
@@ -0,0 +1,296 @@
+//! KNX Scene Records
+//!
+//! Contains scene-related data structures and utilities:
+//! - SceneState: Last scene activated or learned on the bus (DPT 18.001)
+//! - SceneControl: Scene activate/learn commands (DPT 18.001)
+//!
+//! Scenes are numbered 1..64 as in ETS; on the bus they travel as 0..63.
+//! DPT 17.001 (plain scene number) is the activate-only subset of
+//! DPT 18.001, so one decoder handles both.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// KNX scene state (DPT 17.001 / DPT 18.001)
+///
+/// Represents the last scene telegram observed on the scene group address.
+/// Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SceneState {
+    /// KNX group address of the scene object (e.g., "6/0/0")
+    pub address: GroupAddress,
+
+    /// Scene number (1..64)
+    pub scene: u8,
+
+    /// `true` if the scene was learned (stored) rather than activated
+    pub learn: bool,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX scene control command (DPT 18.001)
+///
+/// Represents a scene to activate, or to learn from the actuators' current
+/// state. Consumed by the gateway to control KNX devices.
+///
+/// Serialized as `{"address":"6/0/0","scene":3}`; `learn` defaults to `false`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SceneControl {
+    /// KNX group address of the scene object (e.g., "6/0/0")
+    pub address: GroupAddress,
+
+    /// Scene number (1..64)
+    pub scene: u8,
+
+    /// `true` to store the current actuator state as the scene
+    #[serde(default)]
+    pub learn: bool,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl SceneState {
+    /// Create a new SceneState
+    pub fn new(address: GroupAddress, scene: u8, learn: bool) -> Self {
+        Self {
+            address,
+            scene,
+            learn,
+            meta: None,
+        }
+    }
+}
+
+impl SceneControl {
+    /// Highest scene number
+    pub const MAX_SCENE: u8 = 64;
+
+    /// Create a new SceneControl command
+    pub fn new(address: GroupAddress, scene: u8, learn: bool) -> Self {
+        Self {
+            address,
+            scene,
+            learn,
+        }
+    }
+
+    /// Create a command activating a scene
+    pub fn activate(address: GroupAddress, scene: u8) -> Self {
+        Self::new(address, scene, false)
+    }
+
+    /// Create a command learning a scene
+    pub fn learn(address: GroupAddress, scene: u8) -> Self {
+        Self::new(address, scene, true)
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(SceneState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(SceneControl);
+crate::impl_timestamped!(SceneState);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for SceneState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{boolean, group_address, integer, record, record_meta};
+
+        record(
+            "SceneState",
+            "KNX scene telegram (DPT 18.001), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the scene object"),
+                ),
+                (
+                    "scene",
+                    integer(
+                        "Scene number as numbered in ETS",
+                        1,
+                        SceneControl::MAX_SCENE.into(),
+                    ),
+                ),
+                (
+                    "learn",
+                    boolean("`true` if the scene was learned (stored) rather than activated"),
+                ),
+                ("meta", record_meta()),
+            ],
+            &["address", "scene", "learn"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for SceneControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{boolean, group_address, integer, record};
+
+        record(
+            "SceneControl",
+            "KNX scene command (DPT 18.001), sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address of the scene object"),
+                ),
+                (
+                    "scene",
+                    integer(
+                        "Scene number as numbered in ETS",
+                        1,
+                        SceneControl::MAX_SCENE.into(),
+                    ),
+                ),
+                (
+                    "learn",
+                    boolean(
+                        "`true` to store the current actuator state as the scene (default `false`)",
+                    ),
+                ),
+            ],
+            &["address", "scene"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for SceneState changes
+    ///
+    /// Logs all scene telegrams observed on the bus.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<SceneState, R>,
+    ) {
+        let log = ctx.log();
+        log.info("🎬 Scene state monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to SceneState buffer");
+            return;
+        };
+
+        while let Ok(state) = reader.recv().await {
+            log.info(&format!(
+                "🎬 Scene: {} {} scene {}",
+                state.address,
+                if state.learn { "learned" } else { "activated" },
+                state.scene
+            ));
+        }
+    }
+
+    /// Monitor for SceneControl commands
+    ///
+    /// Logs all outgoing scene commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<SceneControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Scene control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to SceneControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            log.info(&format!(
+                "📤 Scene: {} {} scene {}",
+                control.address,
+                if control.learn { "learn" } else { "activate" },
+                control.scene
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use crate::error::RecordError;
+    use alloc::vec::Vec;
+
+    /// DPT 18.001 learn flag (bit 7)
+    const LEARN: u8 = 0x80;
+    /// DPT 18.001 reserved bit (bit 6), always 0
+    const RESERVED: u8 = 0x40;
+    /// DPT 17.001 / 18.001 scene number field (bits 0..5)
+    const SCENE_MASK: u8 = 0x3F;
+
+    /// Deserialize SceneState from KNX DPT 18.001 (or DPT 17.001)
+    ///
+    /// Telegrams with the reserved bit set are rejected.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (1 byte)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn from_knx(data: &[u8], group_address: GroupAddress) -> Result<SceneState, RecordError> {
+        RecordError::check_dpt_length(data, 1)?;
+
+        let raw = data[0];
+        if raw & RESERVED != 0 {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        Ok(SceneState::new(
+            group_address,
+            (raw & SCENE_MASK) + 1,
+            raw & LEARN != 0,
+        ))
+    }
+
+    /// Serialize SceneControl to KNX DPT 18.001
+    ///
+    /// Scene numbers outside 1..64 are rejected.
+    pub fn to_knx(control: &SceneControl) -> Result<Vec<u8>, RecordError> {
+        if !(1..=SceneControl::MAX_SCENE).contains(&control.scene) {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        let mut raw = control.scene - 1;
+        if control.learn {
+            raw |= LEARN;
+        }
+
+        Ok(alloc::vec![raw])
+    }
+}
@@ -0,0 +1,322 @@
+//! JSON Schemas of the Records
+//!
+//! Contains JSON Schema (draft 2020-12) documents for every record (`std` only):
+//! - RecordSchema: Schema of one record type's JSON form
+//! - RecordSchemas: All record schemas, keyed by AimX record name
+//! - Building blocks shared by the per-module implementations (group
+//!   addresses, percentages, receive metadata, ...)
+//!
+//! Schemas describe the record itself, as read and written through AimX
+//! (`record.get` / `record.set`). On MQTT the record travels inside the
+//! versioned [`Envelope`](crate::codec::Envelope), described by
+//! [`RecordSchemas::envelope`]. Units and KNX datapoint types are given in
+//! the `description` of each property, ranges as `minimum` / `maximum`.
+//!
+//! Objects set `additionalProperties: false`, so a misspelled field fails
+//! validation instead of being dropped by the decoder.
+
+use crate::codec::SCHEMA_VERSION;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// JSON Schema dialect of all documents
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// A record type with a JSON Schema of its JSON form
+pub trait RecordSchema {
+    /// JSON Schema document of the record
+    fn json_schema() -> Value;
+}
+
+/// JSON Schemas of all records
+///
+/// Published by the tower as a record of its own, so MCP clients can fetch
+/// it with `record.get` and validate writes before sending them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordSchemas {
+    /// Schema version of the wire format (see [`crate::codec`])
+    pub schema_version: u16,
+
+    /// Schema of the MQTT envelope around each record
+    pub envelope: Value,
+
+    /// Schema per AimX record name (e.g. `records::switch::SwitchControl`)
+    pub records: BTreeMap<String, Value>,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl RecordSchemas {
+    /// Schemas of every record type in this crate
+    pub fn all() -> Self {
+        use crate::*;
+
+        Self::new()
+            .with::<SwitchState>()
+            .with::<SwitchControl>()
+            .with::<Temperature>()
+            .with::<DimmerState>()
+            .with::<DimmerControl>()
+            .with::<BlindState>()
+            .with::<BlindControl>()
+            .with::<SensorReading<Illuminance>>()
+            .with::<SensorReading<WindSpeed>>()
+            .with::<SensorReading<Humidity>>()
+            .with::<SensorReading<Co2>>()
+            .with::<EnergyCounter>()
+            .with::<PowerReading>()
+            .with::<EnergyTotal>()
+            .with::<ColorLightState>()
+            .with::<ColorLightControl>()
+            .with::<SceneState>()
+            .with::<SceneControl>()
+            .with::<ClockState>()
+            .with::<ClockControl>()
+            .with::<TextMessage>()
+            .with::<TextMessageControl>()
+            .with::<HvacModeState>()
+            .with::<HvacModeControl>()
+            .with::<SetpointState>()
+            .with::<SetpointControl>()
+            .with::<CommandRejection>()
+    }
+
+    /// Empty collection for the current schema version
+    pub fn new() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            envelope: Self::envelope(),
+            records: BTreeMap::new(),
+        }
+    }
+
+    /// Add the schema of record `T` under its AimX record name
+    pub fn with<T: RecordSchema>(mut self) -> Self {
+        self.records
+            .insert(core::any::type_name::<T>().to_string(), T::json_schema());
+        self
+    }
+
+    /// Schema of the MQTT envelope, `{"v":2,"record":{...}}`
+    pub fn envelope() -> Value {
+        json!({
+            "$schema": DIALECT,
+            "title": "Envelope",
+            "description": "Versioned MQTT payload; `record` follows the record's own schema",
+            "type": "object",
+            "properties": {
+                "v": {
+                    "description": "Schema version of `record`",
+                    "const": SCHEMA_VERSION,
+                },
+                "record": {
+                    "description": "The record",
+                    "type": "object",
+                },
+            },
+            "required": ["v", "record"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+// ============================================================================
+// BUILDING BLOCKS
+// ============================================================================
+
+/// Top-level schema of a record
+///
+/// `properties` are listed in wire order; fields not in `required` may be
+/// omitted (e.g. `meta`, defaulted flags).
+pub fn record(
+    title: &str,
+    description: &str,
+    properties: impl IntoIterator<Item = (&'static str, Value)>,
+    required: &[&str],
+) -> Value {
+    let mut schema = object(description, properties, required);
+    let map = schema.as_object_mut().expect("object schema");
+    map.insert("$schema".into(), DIALECT.into());
+    map.insert("title".into(), title.into());
+    schema
+}
+
+/// Object with the given properties and no others
+pub fn object(
+    description: &str,
+    properties: impl IntoIterator<Item = (&'static str, Value)>,
+    required: &[&str],
+) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+
+    json!({
+        "description": description,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// KNX group address in 3-level, 2-level or free notation
+pub fn group_address(description: &str) -> Value {
+    const U5: &str = "([0-9]|[12][0-9]|3[01])";
+    const U8: &str = "([0-9]|[1-9][0-9]|1[0-9]{2}|2[0-4][0-9]|25[0-5])";
+    const U11: &str = "([0-9]|[1-9][0-9]{1,2}|1[0-9]{3}|20[0-3][0-9]|204[0-7])";
+    const U16: &str =
+        "([0-9]|[1-9][0-9]{1,3}|[1-5][0-9]{4}|6[0-4][0-9]{3}|65[0-4][0-9]{2}|655[0-2][0-9]|6553[0-5])";
+
+    json!({
+        "description": description,
+        "type": "string",
+        "anyOf": [
+            {
+                "description": "3-level main/middle/sub (0..31/0..7/0..255)",
+                "pattern": format!("^{}/[0-7]/{}$", U5, U8),
+            },
+            {
+                "description": "2-level main/sub (0..31/0..2047)",
+                "pattern": format!("^{}/{}$", U5, U11),
+            },
+            {
+                "description": "Free 16-bit address (0..65535)",
+                "pattern": format!("^{}$", U16),
+            },
+        ],
+        "examples": ["1/0/7", "1/7", "2055"],
+    })
+}
+
+/// KNX individual (device) address, `area.line.device`
+pub fn individual_address(description: &str) -> Value {
+    json!({
+        "description": format!("{} (area.line.device, 0..15.0..15.0..255)", description),
+        "type": "string",
+        "pattern": "^([0-9]|1[0-5])\\.([0-9]|1[0-5])\\.([0-9]|[1-9][0-9]|1[0-9]{2}|2[0-4][0-9]|25[0-5])$",
+        "examples": ["1.1.20"],
+    })
+}
+
+/// Boolean flag
+pub fn boolean(description: &str) -> Value {
+    json!({ "description": description, "type": "boolean" })
+}
+
+/// Integer in `minimum..=maximum`
+pub fn integer(description: &str, minimum: i64, maximum: i64) -> Value {
+    json!({
+        "description": description,
+        "type": "integer",
+        "minimum": minimum,
+        "maximum": maximum,
+    })
+}
+
+/// Percentage in whole percent (DPT 5.001)
+pub fn percent(description: &str) -> Value {
+    integer(&format!("{} in percent (DPT 5.001)", description), 0, 100)
+}
+
+/// Temperature in degrees Celsius (DPT 9.001, -273..670760 °C)
+pub fn celsius(description: &str) -> Value {
+    number(
+        &format!("{} in degrees Celsius (DPT 9.001)", description),
+        -273.0,
+        670_760.0,
+    )
+}
+
+/// Number in `minimum..=maximum`
+pub fn number(description: &str, minimum: f64, maximum: f64) -> Value {
+    json!({
+        "description": description,
+        "type": "number",
+        "minimum": minimum,
+        "maximum": maximum,
+    })
+}
+
+/// Number without range limits beyond finiteness
+pub fn finite_number(description: &str) -> Value {
+    json!({ "description": description, "type": "number" })
+}
+
+/// String restricted to the given values
+pub fn string_enum(description: &str, values: &[&str]) -> Value {
+    json!({ "description": description, "type": "string", "enum": values })
+}
+
+/// String of at most `max_length` characters
+pub fn string(description: &str, max_length: usize) -> Value {
+    json!({ "description": description, "type": "string", "maxLength": max_length })
+}
+
+/// `schema` or `null`
+pub fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+/// Exactly one of `variants`
+pub fn one_of(description: &str, variants: Vec<Value>) -> Value {
+    json!({ "description": description, "oneOf": variants })
+}
+
+/// Object variant of an externally tagged enum, `{"<tag>": <schema>}`
+pub fn tagged(tag: &'static str, schema: Value) -> Value {
+    object(&format!("`{}` variant", tag), [(tag, schema)], &[tag])
+}
+
+/// Gateway receive metadata ([`RecordMeta`](crate::RecordMeta)), optional
+pub fn record_meta() -> Value {
+    nullable(object(
+        "Gateway receive metadata, `null` unless stamped by the gateway",
+        [
+            (
+                "gateway",
+                string(
+                    "Gateway that received the telegram",
+                    crate::RecordMeta::MAX_GATEWAY_ID_LEN,
+                ),
+            ),
+            (
+                "boot",
+                integer(
+                    "Boot counter of the gateway, one higher after every reboot",
+                    0,
+                    u32::MAX.into(),
+                ),
+            ),
+            (
+                "uptime_ms",
+                json!({
+                    "description": "Milliseconds since the gateway booted when the telegram arrived; comparable only within one `boot`",
+                    "type": "integer",
+                    "minimum": 0,
+                }),
+            ),
+            (
+                "utc_ms",
+                nullable(json!({
+                    "description": "Unix time in milliseconds, when the gateway clock is synchronized",
+                    "type": "integer",
+                    "minimum": 0,
+                })),
+            ),
+            ("source", nullable(individual_address("Sending device"))),
+        ],
+        &["gateway", "boot", "uptime_ms"],
+    ))
+}
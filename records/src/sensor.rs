@@ -0,0 +1,358 @@
+//! KNX Sensor Records (DPT 9.xxx)
+//!
+//! Contains the generic sensor reading record for the DPT 9 quantities
+//! reported by room sensors and weather stations:
+//! - [`Illuminance`]: brightness in lux (DPT 9.004)
+//! - [`WindSpeed`]: wind speed in m/s (DPT 9.005)
+//! - [`Humidity`]: relative humidity in percent (DPT 9.007)
+//! - [`Co2`]: air quality in ppm (DPT 9.008)
+//!
+//! `SensorReading<Q>` is a distinct record type per quantity, so each one
+//! gets its own aimdb buffer; each sensor publishes on its device's MQTT
+//! topic (e.g. `knx/living-room/humidity/state`).
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::codec::{self, RecordCodec, ENVELOPE_JSON_LEN, STATE_JSON_BUFFER_SIZE};
+use crate::error::RecordError;
+use crate::meta::{RecordMeta, Timestamped};
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Largest value representable by an unsigned DPT 9 sub-type
+const DPT9_MAX: f32 = 670_760.0;
+
+/// Physical unit carried in the JSON representation of a reading
+///
+/// Serialized as the unit symbol, e.g. `"%"` or `"lx"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SensorUnit {
+    /// Percent (relative humidity)
+    #[serde(rename = "%")]
+    Percent,
+
+    /// Lux (illuminance)
+    #[serde(rename = "lx")]
+    Lux,
+
+    /// Metres per second (wind speed)
+    #[serde(rename = "m/s")]
+    MetresPerSecond,
+
+    /// Parts per million (CO2 concentration)
+    #[serde(rename = "ppm")]
+    Ppm,
+}
+
+impl SensorUnit {
+    /// Unit symbol as used in JSON and logs
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Percent => "%",
+            Self::Lux => "lx",
+            Self::MetresPerSecond => "m/s",
+            Self::Ppm => "ppm",
+        }
+    }
+}
+
+impl fmt::Display for SensorUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Physical quantity measured by a DPT 9 sensor
+///
+/// Implemented by the marker types in this module. Selects the DPT
+/// sub-type, unit and valid range of a [`SensorReading`].
+pub trait Quantity: fmt::Debug + Clone + Copy + PartialEq + Send + Sync + 'static {
+    /// Human-readable name used in logs (e.g. "Humidity")
+    const NAME: &'static str;
+
+    /// KNX datapoint type identifier (e.g. "9.007")
+    const DPT: &'static str;
+
+    /// Unit of the reading
+    const UNIT: SensorUnit;
+
+    /// Smallest valid value
+    const MIN: f32;
+
+    /// Largest valid value
+    const MAX: f32;
+}
+
+/// Illuminance in lux (DPT 9.004)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Illuminance;
+
+impl Quantity for Illuminance {
+    const NAME: &'static str = "Illuminance";
+    const DPT: &'static str = "9.004";
+    const UNIT: SensorUnit = SensorUnit::Lux;
+    const MIN: f32 = 0.0;
+    const MAX: f32 = DPT9_MAX;
+}
+
+/// Wind speed in metres per second (DPT 9.005)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindSpeed;
+
+impl Quantity for WindSpeed {
+    const NAME: &'static str = "Wind speed";
+    const DPT: &'static str = "9.005";
+    const UNIT: SensorUnit = SensorUnit::MetresPerSecond;
+    const MIN: f32 = 0.0;
+    const MAX: f32 = DPT9_MAX;
+}
+
+/// Relative humidity in percent (DPT 9.007)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Humidity;
+
+impl Quantity for Humidity {
+    const NAME: &'static str = "Humidity";
+    const DPT: &'static str = "9.007";
+    const UNIT: SensorUnit = SensorUnit::Percent;
+    const MIN: f32 = 0.0;
+    const MAX: f32 = DPT9_MAX;
+}
+
+/// CO2 concentration in parts per million (DPT 9.008)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Co2;
+
+impl Quantity for Co2 {
+    const NAME: &'static str = "CO2";
+    const DPT: &'static str = "9.008";
+    const UNIT: SensorUnit = SensorUnit::Ppm;
+    const MIN: f32 = 0.0;
+    const MAX: f32 = DPT9_MAX;
+}
+
+/// KNX sensor reading (DPT 9.xxx - 2-byte float)
+///
+/// Represents a measurement of quantity `Q` from a KNX sensor.
+/// Serialized as `{"address":"9/1/3","value":45.5,"unit":"%"}`; payloads
+/// whose unit does not match `Q` or whose value is out of range are rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "", into = "WireReading", try_from = "WireReading")]
+pub struct SensorReading<Q: Quantity> {
+    /// KNX group address (e.g., "9/1/3")
+    pub address: GroupAddress,
+
+    /// Measured value in the unit of `Q`
+    pub value: f32,
+
+    quantity: PhantomData<Q>,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    pub meta: Option<RecordMeta>,
+}
+
+/// JSON layout shared by all quantities
+#[derive(Serialize, Deserialize)]
+struct WireReading {
+    address: GroupAddress,
+    value: f32,
+    unit: SensorUnit,
+    #[serde(default)]
+    meta: Option<RecordMeta>,
+}
+
+impl<Q: Quantity> From<SensorReading<Q>> for WireReading {
+    fn from(reading: SensorReading<Q>) -> Self {
+        Self {
+            address: reading.address,
+            value: reading.value,
+            unit: Q::UNIT,
+            meta: reading.meta,
+        }
+    }
+}
+
+impl<Q: Quantity> TryFrom<WireReading> for SensorReading<Q> {
+    type Error = RecordError;
+
+    fn try_from(wire: WireReading) -> Result<Self, Self::Error> {
+        if wire.unit != Q::UNIT {
+            return Err(RecordError::MalformedJson);
+        }
+        let mut reading = Self::checked(wire.address, wire.value)?;
+        reading.meta = wire.meta;
+        Ok(reading)
+    }
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl<Q: Quantity> SensorReading<Q> {
+    /// Create a new SensorReading
+    pub fn new(address: GroupAddress, value: f32) -> Self {
+        Self {
+            address,
+            value,
+            quantity: PhantomData,
+            meta: None,
+        }
+    }
+
+    /// Create a new SensorReading, rejecting values outside the DPT range
+    pub fn checked(address: GroupAddress, value: f32) -> Result<Self, RecordError> {
+        if (Q::MIN..=Q::MAX).contains(&value) {
+            Ok(Self::new(address, value))
+        } else {
+            Err(RecordError::ValueOutOfRange)
+        }
+    }
+
+    /// Unit of the reading
+    pub fn unit(&self) -> SensorUnit {
+        Q::UNIT
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+impl<Q: Quantity> RecordCodec for SensorReading<Q> {
+    const JSON_BUFFER_SIZE: usize = STATE_JSON_BUFFER_SIZE + ENVELOPE_JSON_LEN;
+
+    fn to_json(&self) -> Result<Vec<u8>, RecordError> {
+        codec::encode_record::<Self, { STATE_JSON_BUFFER_SIZE + ENVELOPE_JSON_LEN }>(self)
+    }
+}
+
+impl<Q: Quantity> Timestamped for SensorReading<Q> {
+    fn meta(&self) -> Option<&RecordMeta> {
+        self.meta.as_ref()
+    }
+
+    fn set_meta(&mut self, meta: RecordMeta) {
+        self.meta = Some(meta);
+    }
+}
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+impl<Q: Quantity> crate::schema::RecordSchema for SensorReading<Q> {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, number, record, record_meta};
+
+        record(
+            &alloc::format!("SensorReading ({})", Q::NAME),
+            &alloc::format!(
+                "KNX {} reading (DPT {}), published by the gateway",
+                Q::NAME,
+                Q::DPT
+            ),
+            [
+                (
+                    "address",
+                    group_address("KNX group address (e.g. \"9/1/3\")"),
+                ),
+                (
+                    "value",
+                    number(
+                        &alloc::format!("{} in {}", Q::NAME, Q::UNIT.as_str()),
+                        Q::MIN.into(),
+                        Q::MAX.into(),
+                    ),
+                ),
+                (
+                    "unit",
+                    serde_json::json!({
+                        "description": "Unit symbol",
+                        "const": Q::UNIT.as_str(),
+                    }),
+                ),
+                ("meta", record_meta()),
+            ],
+            &["address", "value", "unit"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for SensorReading changes
+    ///
+    /// Logs all incoming readings of quantity `Q`.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration, e.g.
+    /// `.tap(monitors::monitor::<Humidity, _>)`.
+    pub async fn monitor<Q: Quantity, R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<SensorReading<Q>, R>,
+    ) {
+        let log = ctx.log();
+        log.info(&format!("📈 {} monitor started", Q::NAME));
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error(&format!("Failed to subscribe to {} buffer", Q::NAME));
+            return;
+        };
+
+        while let Ok(reading) = reader.recv().await {
+            log.info(&format!(
+                "📈 {}: {} = {:.1} {}",
+                Q::NAME,
+                reading.address,
+                reading.value,
+                Q::UNIT
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC DESERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+
+    /// Deserialize SensorReading from KNX DPT 9.xxx (2-byte float)
+    ///
+    /// All DPT 9 sub-types share the 2-byte float encoding.
+    /// Payloads that are not exactly two bytes, or that decode outside the
+    /// sub-type's range (e.g. negative humidity), are rejected.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (2 bytes for DPT 9.xxx)
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn from_knx<Q: Quantity>(
+        data: &[u8],
+        group_address: GroupAddress,
+    ) -> Result<SensorReading<Q>, RecordError> {
+        use crate::dpt::{DptCodec, Float16};
+
+        let value = Float16::decode(data)?;
+
+        SensorReading::checked(group_address, value)
+    }
+}
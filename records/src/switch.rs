@@ -7,7 +7,8 @@
 //! This module is no_std by default and works in both embedded and std environments.
 
 extern crate alloc;
-use heapless::String as HeaplessString;
+use crate::address::GroupAddress;
+use crate::meta::RecordMeta;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -21,10 +22,14 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SwitchState {
     /// KNX group address (e.g., "1/0/7")
-    pub address: HeaplessString<16>,
+    pub address: GroupAddress,
 
     /// Switch on/off state
     pub is_on: bool,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
 }
 
 /// KNX switch control command (DPT 1.001)
@@ -34,7 +39,7 @@ pub struct SwitchState {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SwitchControl {
     /// KNX group address to control (e.g., "1/0/6")
-    pub address: HeaplessString<16>,
+    pub address: GroupAddress,
 
     /// Desired on/off state
     pub is_on: bool,
@@ -45,26 +50,20 @@ pub struct SwitchControl {
 // ============================================================================
 
 impl SwitchState {
-    /// MQTT topic for publishing switch state updates
-    pub const MQTT_TOPIC: &'static str = "knx/tv/state";
-
     /// Create a new SwitchState
-    pub fn new(address: &str, is_on: bool) -> Self {
-        let mut addr = HeaplessString::new();
-        let _ = addr.push_str(address);
-        Self { address: addr, is_on }
+    pub fn new(address: GroupAddress, is_on: bool) -> Self {
+        Self {
+            address,
+            is_on,
+            meta: None,
+        }
     }
 }
 
 impl SwitchControl {
-    /// MQTT topic for receiving switch control commands
-    pub const MQTT_TOPIC: &'static str = "knx/tv/control";
-
     /// Create a new SwitchControl command
-    pub fn new(address: &str, is_on: bool) -> Self {
-        let mut addr = HeaplessString::new();
-        let _ = addr.push_str(address);
-        Self { address: addr, is_on }
+    pub fn new(address: GroupAddress, is_on: bool) -> Self {
+        Self { address, is_on }
     }
 }
 
@@ -72,69 +71,52 @@ impl SwitchControl {
 // SERIALIZATION
 // ============================================================================
 
-pub mod json {
-    use super::*;
-    use alloc::vec::Vec;
-    use alloc::string::String;
-
-    /// Serialize SwitchState to JSON
-    pub fn serialize_state(state: &SwitchState) -> Result<Vec<u8>, String> {
-        #[cfg(feature = "std")]
-        {
-            serde_json::to_vec(state).map_err(|e| alloc::format!("Serialization failed: {}", e))
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            let mut buf = [0u8; 128];
-            serde_json_core::to_slice(state, &mut buf)
-                .map(|len| buf[..len].to_vec())
-                .map_err(|_| String::from("Serialization buffer too small"))
-        }
-    }
+crate::impl_record_codec!(SwitchState => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(SwitchControl);
+crate::impl_timestamped!(SwitchState);
 
-    /// Deserialize SwitchState from JSON
-    pub fn deserialize_state(data: &[u8]) -> Result<SwitchState, String> {
-        #[cfg(feature = "std")]
-        {
-            serde_json::from_slice(data)
-                .map_err(|e| alloc::format!("Deserialization failed: {}", e))
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            serde_json_core::from_slice(data)
-                .map(|(state, _)| state)
-                .map_err(|_| String::from("Deserialization failed"))
-        }
-    }
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
 
-    /// Serialize SwitchControl to JSON
-    pub fn serialize_control(control: &SwitchControl) -> Result<Vec<u8>, String> {
-        #[cfg(feature = "std")]
-        {
-            serde_json::to_vec(control).map_err(|e| alloc::format!("Serialization failed: {}", e))
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            let mut buf = [0u8; 128];
-            serde_json_core::to_slice(control, &mut buf)
-                .map(|len| buf[..len].to_vec())
-                .map_err(|_| String::from("Serialization buffer too small"))
-        }
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for SwitchState {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{boolean, group_address, record, record_meta};
+
+        record(
+            "SwitchState",
+            "KNX switch state (DPT 1.001), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address (e.g. \"1/0/7\")"),
+                ),
+                ("is_on", boolean("Switch on/off state")),
+                ("meta", record_meta()),
+            ],
+            &["address", "is_on"],
+        )
     }
+}
 
-    /// Deserialize SwitchControl from JSON
-    pub fn deserialize_control(data: &[u8]) -> Result<SwitchControl, String> {
-        #[cfg(feature = "std")]
-        {
-            serde_json::from_slice(data)
-                .map_err(|e| alloc::format!("Deserialization failed: {}", e))
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            serde_json_core::from_slice(data)
-                .map(|(control, _)| control)
-                .map_err(|_| String::from("Deserialization failed"))
-        }
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for SwitchControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{boolean, group_address, record};
+
+        record(
+            "SwitchControl",
+            "KNX switch command (DPT 1.001), sent by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address to control (e.g. \"1/0/6\")"),
+                ),
+                ("is_on", boolean("Desired on/off state")),
+            ],
+            &["address", "is_on"],
+        )
     }
 }
 
@@ -207,40 +189,43 @@ pub mod monitors {
 #[cfg(feature = "knx")]
 pub mod knx {
     use super::*;
+    use crate::error::RecordError;
     use alloc::vec::Vec;
-    use alloc::string::String;
 
     /// Deserialize SwitchState from KNX DPT 1.001 (boolean)
     ///
     /// Decodes the raw KNX telegram bytes using DPT 1.001 format.
+    /// Payloads that are not exactly one byte of value 0 or 1 are rejected
+    /// rather than decoded as "off".
     ///
     /// # Arguments
     /// * `data` - Raw KNX telegram bytes (1 byte for DPT 1.001)
-    /// * `group_address` - KNX group address (e.g., "1/0/7")
-    pub fn from_knx(data: &[u8], group_address: &str) -> Result<SwitchState, String> {
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn from_knx(data: &[u8], group_address: GroupAddress) -> Result<SwitchState, RecordError> {
         use aimdb_knx_connector::dpt::{Dpt1, DptDecode};
 
-        let is_on = Dpt1::Switch.decode(data).unwrap_or(false);
-        
-        let mut address = HeaplessString::<16>::new();
-        address.push_str(group_address).map_err(|_| String::from("Address too long"))?;
+        RecordError::check_dpt_length(data, 1)?;
+        if data[0] > 1 {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        let is_on = Dpt1::Switch
+            .decode(data)
+            .map_err(|_| RecordError::ValueOutOfRange)?;
 
-        Ok(SwitchState {
-            address,
-            is_on,
-        })
+        Ok(SwitchState::new(group_address, is_on))
     }
 
     /// Serialize SwitchControl to KNX DPT 1.001 (boolean)
     ///
     /// Converts SwitchControl command to KNX bus format using DPT 1.001 encoder.
-    pub fn to_knx(control: &SwitchControl) -> Result<Vec<u8>, String> {
+    pub fn to_knx(control: &SwitchControl) -> Result<Vec<u8>, RecordError> {
         use aimdb_knx_connector::dpt::{Dpt1, DptEncode};
 
         let mut buf = [0u8; 1];
         let len = Dpt1::Switch
             .encode(control.is_on, &mut buf)
-            .map_err(|_| String::from("Failed to encode DPT 1.001"))?;
+            .map_err(|_| RecordError::ValueOutOfRange)?;
 
         Ok(buf[..len].to_vec())
     }
@@ -5,7 +5,9 @@
 //! This module is no_std by default and works in both embedded and std environments.
 
 extern crate alloc;
-use heapless::String as HeaplessString;
+use crate::address::GroupAddress;
+use crate::error::RecordError;
+use crate::meta::RecordMeta;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -18,10 +20,14 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Temperature {
     /// KNX group address (e.g., "9/1/0")
-    pub address: HeaplessString<16>,
+    pub address: GroupAddress,
 
     /// Temperature in Celsius
     pub celsius: f32,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
 }
 
 // ============================================================================
@@ -29,14 +35,23 @@ pub struct Temperature {
 // ============================================================================
 
 impl Temperature {
-    /// MQTT topic for publishing temperature readings
-    pub const MQTT_TOPIC: &'static str = "knx/temperature/state";
-
     /// Create a new Temperature reading
-    pub fn new(address: &str, celsius: f32) -> Self {
-        let mut addr = HeaplessString::new();
-        let _ = addr.push_str(address);
-        Self { address: addr, celsius }
+    pub fn new(address: GroupAddress, celsius: f32) -> Self {
+        Self {
+            address,
+            celsius,
+            meta: None,
+        }
+    }
+
+    /// Create a new Temperature reading, rejecting NaN and values outside
+    /// the DPT 9 range (-671088.64..670760.96)
+    pub fn checked(address: GroupAddress, celsius: f32) -> Result<Self, RecordError> {
+        if (crate::dpt::Float16::MIN..=crate::dpt::Float16::MAX).contains(&celsius) {
+            Ok(Self::new(address, celsius))
+        } else {
+            Err(RecordError::ValueOutOfRange)
+        }
     }
 }
 
@@ -44,39 +59,31 @@ impl Temperature {
 // SERIALIZATION
 // ============================================================================
 
-pub mod json {
-    use super::*;
-    use alloc::vec::Vec;
-    use alloc::string::String;
+crate::impl_record_codec!(Temperature => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_timestamped!(Temperature);
 
-    /// Serialize Temperature to JSON
-    pub fn serialize(temp: &Temperature) -> Result<Vec<u8>, String> {
-        #[cfg(feature = "std")]
-        {
-            serde_json::to_vec(temp).map_err(|e| alloc::format!("Serialization failed: {}", e))
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            let mut buf = [0u8; 128];
-            serde_json_core::to_slice(temp, &mut buf)
-                .map(|len| buf[..len].to_vec())
-                .map_err(|_| String::from("Serialization buffer too small"))
-        }
-    }
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
 
-    /// Deserialize Temperature from JSON
-    pub fn deserialize(data: &[u8]) -> Result<Temperature, String> {
-        #[cfg(feature = "std")]
-        {
-            serde_json::from_slice(data)
-                .map_err(|e| alloc::format!("Deserialization failed: {}", e))
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            serde_json_core::from_slice(data)
-                .map(|(temp, _)| temp)
-                .map_err(|_| String::from("Deserialization failed"))
-        }
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for Temperature {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{celsius, group_address, record, record_meta};
+
+        record(
+            "Temperature",
+            "KNX temperature reading (DPT 9.001), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address (e.g. \"9/1/0\")"),
+                ),
+                ("celsius", celsius("Temperature")),
+                ("meta", record_meta()),
+            ],
+            &["address", "celsius"],
+        )
     }
 }
 
@@ -120,26 +127,31 @@ pub mod monitors {
 #[cfg(feature = "knx")]
 pub mod knx {
     use super::*;
-    use alloc::string::String;
+    use crate::dpt::{DptCodec, Float16};
+    use alloc::vec::Vec;
 
     /// Deserialize Temperature from KNX DPT 9.001 (2-byte float)
     ///
     /// Decodes the raw KNX telegram bytes using DPT 9.001 format.
+    /// Payloads that are not exactly two bytes are rejected rather than
+    /// decoded as 0.0°C, and the invalid marker 0x7FFF (e.g. a broken
+    /// sensor) fails with [`RecordError::InvalidDptValue`].
     ///
     /// # Arguments
     /// * `data` - Raw KNX telegram bytes (2 bytes for DPT 9.001)
-    /// * `group_address` - KNX group address (e.g., "9/1/0")
-    pub fn from_knx(data: &[u8], group_address: &str) -> Result<Temperature, String> {
-        use aimdb_knx_connector::dpt::{Dpt9, DptDecode};
+    /// * `group_address` - KNX group address the telegram was received on
+    pub fn from_knx(data: &[u8], group_address: GroupAddress) -> Result<Temperature, RecordError> {
+        let celsius = Float16::decode(data)?;
 
-        let celsius = Dpt9::Temperature.decode(data).unwrap_or(0.0);
-        
-        let mut address = HeaplessString::<16>::new();
-        address.push_str(group_address).map_err(|_| String::from("Address too long"))?;
+        Ok(Temperature::new(group_address, celsius))
+    }
 
-        Ok(Temperature {
-            address,
-            celsius,
-        })
+    /// Serialize Temperature to KNX DPT 9.001 (2-byte float)
+    ///
+    /// The value is rounded to the nearest DPT 9 step (0.01°C below
+    /// 20.48°C, coarser above). NaN and values outside -671088.64..670760.96
+    /// are rejected.
+    pub fn to_knx(temperature: &Temperature) -> Result<Vec<u8>, RecordError> {
+        Float16::encode(temperature.celsius)
     }
 }
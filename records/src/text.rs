@@ -0,0 +1,404 @@
+//! KNX Text Message Records
+//!
+//! Contains text-related data structures and utilities:
+//! - TextMessage: Text observed on the bus, e.g. alarm panel messages
+//! - TextMessageControl: Text to show on a KNX display
+//!
+//! Texts travel on KNX as DPT 16.000 (ASCII) or DPT 16.001 (ISO 8859-1),
+//! always 14 bytes padded with NUL. [`KnxText`] enforces the 14-character
+//! limit and the Latin-1 character range on construction, so every text
+//! that reaches a codec fits the DPT.
+//!
+//! This module is no_std by default and works in both embedded and std environments.
+
+extern crate alloc;
+use crate::address::GroupAddress;
+use crate::error::RecordError;
+use crate::meta::RecordMeta;
+use core::fmt;
+use core::str::FromStr;
+use heapless::String as HeaplessString;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Character set of a DPT 16 text
+///
+/// Serialized in snake_case: `"ascii"` or `"latin1"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    /// 7-bit ASCII (DPT 16.000)
+    #[default]
+    Ascii,
+    /// ISO 8859-1 (DPT 16.001)
+    Latin1,
+}
+
+/// Text of at most 14 Latin-1 characters, as carried by DPT 16
+///
+/// Serialized as a plain JSON string. NUL characters are rejected because
+/// KNX uses them as padding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KnxText(HeaplessString<{ KnxText::MAX_BYTES }>);
+
+/// KNX text message (DPT 16.000 / DPT 16.001)
+///
+/// Represents a text observed on a group address, such as an alarm panel
+/// message. Published by the gateway when monitoring KNX bus activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextMessage {
+    /// KNX group address (e.g., "8/0/1")
+    pub address: GroupAddress,
+
+    /// Received text
+    pub text: KnxText,
+
+    /// Character set the text was received in
+    pub encoding: TextEncoding,
+
+    /// Gateway receive metadata (`null` unless stamped by the gateway)
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// KNX text message command (DPT 16.000 / DPT 16.001)
+///
+/// Represents a short notice to show on a KNX display.
+/// Consumed by the gateway to control KNX devices.
+///
+/// Serialized as `{"address":"8/0/0","text":"Laundry done"}`; `encoding`
+/// defaults to `"ascii"` and must match the display's DPT.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextMessageControl {
+    /// KNX group address of the display (e.g., "8/0/0")
+    pub address: GroupAddress,
+
+    /// Text to display
+    pub text: KnxText,
+
+    /// Character set to send the text in
+    #[serde(default)]
+    pub encoding: TextEncoding,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl TextEncoding {
+    /// KNX datapoint type identifier ("16.000" or "16.001")
+    pub fn dpt(self) -> &'static str {
+        match self {
+            Self::Ascii => "16.000",
+            Self::Latin1 => "16.001",
+        }
+    }
+
+    /// Check whether a character can be sent in this encoding
+    pub fn supports(self, c: char) -> bool {
+        match self {
+            Self::Ascii => c.is_ascii(),
+            Self::Latin1 => u32::from(c) <= 0xFF,
+        }
+    }
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.dpt())
+    }
+}
+
+impl KnxText {
+    /// Maximum number of characters (one byte each on the bus)
+    pub const MAX_CHARS: usize = 14;
+
+    /// Maximum UTF-8 length (Latin-1 characters take up to two bytes)
+    pub const MAX_BYTES: usize = 2 * Self::MAX_CHARS;
+
+    /// Text as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Number of characters (equals the number of bytes on the bus)
+    pub fn len(&self) -> usize {
+        self.0.chars().count()
+    }
+
+    /// Check whether the text is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for KnxText {
+    type Err = RecordError;
+
+    /// Accept up to 14 characters from U+0001..U+00FF
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().count() > Self::MAX_CHARS
+            || s.chars()
+                .any(|c| c == '\0' || !TextEncoding::Latin1.supports(c))
+        {
+            return Err(RecordError::ValueOutOfRange);
+        }
+
+        let mut text = HeaplessString::new();
+        // Cannot fail: 14 characters of at most two UTF-8 bytes each
+        let _ = text.push_str(s);
+        Ok(Self(text))
+    }
+}
+
+impl fmt::Display for KnxText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TextMessage {
+    /// Create a new TextMessage
+    pub fn new(address: GroupAddress, text: KnxText, encoding: TextEncoding) -> Self {
+        Self {
+            address,
+            text,
+            encoding,
+            meta: None,
+        }
+    }
+}
+
+impl TextMessageControl {
+    /// Create a new TextMessageControl command
+    pub fn new(address: GroupAddress, text: KnxText, encoding: TextEncoding) -> Self {
+        Self {
+            address,
+            text,
+            encoding,
+        }
+    }
+}
+
+impl Serialize for KnxText {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for KnxText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl Visitor<'_> for TextVisitor {
+            type Value = KnxText;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a text of at most 14 Latin-1 characters")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(TextVisitor)
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+
+crate::impl_record_codec!(TextMessage => crate::codec::STATE_JSON_BUFFER_SIZE);
+crate::impl_record_codec!(TextMessageControl);
+crate::impl_timestamped!(TextMessage);
+
+// ============================================================================
+// JSON SCHEMA (std only)
+// ============================================================================
+
+#[cfg(feature = "std")]
+fn text_schema(description: &str) -> serde_json::Value {
+    crate::schema::string(
+        &alloc::format!(
+            "{} (ASCII for DPT 16.000, Latin-1 for DPT 16.001)",
+            description
+        ),
+        KnxText::MAX_CHARS,
+    )
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for TextMessage {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record, record_meta, string_enum};
+
+        record(
+            "TextMessage",
+            "KNX display/alarm text (DPT 16.000/16.001), published by the gateway",
+            [
+                (
+                    "address",
+                    group_address("KNX group address (e.g. \"8/0/1\")"),
+                ),
+                ("text", text_schema("Received text")),
+                (
+                    "encoding",
+                    string_enum(
+                        "Character set the text was received in",
+                        &["ascii", "latin1"],
+                    ),
+                ),
+                ("meta", record_meta()),
+            ],
+            &["address", "text", "encoding"],
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::schema::RecordSchema for TextMessageControl {
+    fn json_schema() -> serde_json::Value {
+        use crate::schema::{group_address, record, string_enum};
+
+        record(
+            "TextMessageControl",
+            "KNX display text command (DPT 16.000/16.001), sent by the gateway",
+            [
+                ("address", group_address("KNX group address of the display")),
+                ("text", text_schema("Text to display")),
+                (
+                    "encoding",
+                    string_enum(
+                        "Character set to send the text in (default \"ascii\")",
+                        &["ascii", "latin1"],
+                    ),
+                ),
+            ],
+            &["address", "text"],
+        )
+    }
+}
+
+// ============================================================================
+// MONITORS - Generic over runtime adapter
+// ============================================================================
+
+#[cfg(feature = "monitors")]
+pub mod monitors {
+    use super::*;
+    use aimdb_core::{Consumer, Runtime, RuntimeContext};
+    use alloc::format;
+
+    /// Monitor for TextMessage changes
+    ///
+    /// Logs all text messages observed on the bus.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    /// Can be used as a tap in aimdb configuration.
+    pub async fn state_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<TextMessage, R>,
+    ) {
+        let log = ctx.log();
+        log.info("💬 Text message monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to TextMessage buffer");
+            return;
+        };
+
+        while let Ok(message) = reader.recv().await {
+            log.info(&format!(
+                "💬 Text: {} = \"{}\"",
+                message.address, message.text
+            ));
+        }
+    }
+
+    /// Monitor for TextMessageControl commands
+    ///
+    /// Logs all outgoing text commands.
+    /// Works with any runtime adapter (Tokio, Embassy, etc.).
+    pub async fn control_monitor<R: Runtime>(
+        ctx: RuntimeContext<R>,
+        consumer: Consumer<TextMessageControl, R>,
+    ) {
+        let log = ctx.log();
+        log.info("📤 Text control monitor started");
+
+        let Ok(mut reader) = consumer.subscribe() else {
+            log.error("Failed to subscribe to TextMessageControl buffer");
+            return;
+        };
+
+        while let Ok(control) = reader.recv().await {
+            log.info(&format!(
+                "📤 Text: {} → \"{}\"",
+                control.address, control.text
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// KNX-SPECIFIC SERIALIZATION (for gateway)
+// ============================================================================
+
+#[cfg(feature = "knx")]
+pub mod knx {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Deserialize TextMessage from KNX DPT 16.000 / DPT 16.001
+    ///
+    /// The text ends at the first NUL byte. ASCII texts containing bytes
+    /// above 0x7F are rejected.
+    ///
+    /// # Arguments
+    /// * `data` - Raw KNX telegram bytes (14 bytes for DPT 16)
+    /// * `group_address` - KNX group address the telegram was received on
+    /// * `encoding` - Character set of the group address (its DPT)
+    pub fn from_knx(
+        data: &[u8],
+        group_address: GroupAddress,
+        encoding: TextEncoding,
+    ) -> Result<TextMessage, RecordError> {
+        RecordError::check_dpt_length(data, KnxText::MAX_CHARS)?;
+
+        let mut text = HeaplessString::new();
+        for &byte in data.iter().take_while(|&&byte| byte != 0) {
+            let c = char::from(byte);
+            if !encoding.supports(c) {
+                return Err(RecordError::ValueOutOfRange);
+            }
+            // Cannot fail: at most 14 characters of at most two UTF-8 bytes
+            let _ = text.push(c);
+        }
+
+        Ok(TextMessage::new(group_address, KnxText(text), encoding))
+    }
+
+    /// Serialize TextMessageControl to KNX DPT 16.000 / DPT 16.001
+    ///
+    /// The text is padded with NUL to 14 bytes. Characters the command's
+    /// encoding cannot represent are rejected.
+    pub fn to_knx(control: &TextMessageControl) -> Result<Vec<u8>, RecordError> {
+        let mut data = Vec::with_capacity(KnxText::MAX_CHARS);
+        for c in control.text.as_str().chars() {
+            if !control.encoding.supports(c) {
+                return Err(RecordError::ValueOutOfRange);
+            }
+            // Latin-1 code points are the byte values
+            data.push(u32::from(c) as u8);
+        }
+        data.resize(KnxText::MAX_CHARS, 0);
+
+        Ok(data)
+    }
+}
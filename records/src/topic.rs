@@ -0,0 +1,60 @@
+//! Per-Device MQTT Topics
+//!
+//! Contains the naming shared by the gateway and the console:
+//! - DeviceTopic: Room and name of one device, and the topics derived from them
+//!
+//! Every device publishes its state on `knx/<room>/<name>/state` and receives
+//! commands on `knx/<room>/<name>/control`, so several devices of one record
+//! type (e.g. two switches) stay apart on MQTT while sharing a record.
+//!
+//! This module is no_std and works in both embedded and std environments.
+
+extern crate alloc;
+use alloc::format;
+use alloc::string::String;
+
+// ============================================================================
+// DATA TYPES
+// ============================================================================
+
+/// Room and name of a device, as written in the device registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceTopic<'a> {
+    /// Room of the device (e.g. "living-room")
+    pub room: &'a str,
+
+    /// Name of the device within its room (e.g. "tv")
+    pub name: &'a str,
+}
+
+// ============================================================================
+// CONSTRUCTORS
+// ============================================================================
+
+impl<'a> DeviceTopic<'a> {
+    /// Create a new DeviceTopic
+    pub const fn new(room: &'a str, name: &'a str) -> Self {
+        Self { room, name }
+    }
+}
+
+// ============================================================================
+// TOPICS
+// ============================================================================
+
+impl DeviceTopic<'_> {
+    /// Device id used to key per-device values (e.g. "living-room/tv")
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.room, self.name)
+    }
+
+    /// MQTT topic of the device's state record (e.g. "knx/living-room/tv/state")
+    pub fn state(&self) -> String {
+        format!("knx/{}/{}/state", self.room, self.name)
+    }
+
+    /// MQTT topic of the device's control record (e.g. "knx/living-room/tv/control")
+    pub fn control(&self) -> String {
+        format!("knx/{}/{}/control", self.room, self.name)
+    }
+}
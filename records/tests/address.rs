@@ -0,0 +1,128 @@
+//! Tests for group and individual address parsing and formatting
+
+use records::address::AddressStyle;
+use records::*;
+
+fn parse(s: &str) -> Result<GroupAddress, GroupAddressError> {
+    s.parse()
+}
+
+#[test]
+fn three_level_parts() {
+    let address = parse("31/7/255").unwrap();
+
+    assert_eq!(address.style(), AddressStyle::ThreeLevel);
+    assert_eq!(
+        (address.main(), address.middle(), address.sub()),
+        (31, 7, 255)
+    );
+    assert_eq!(address.raw(), 0xFFFF);
+}
+
+#[test]
+fn two_level_parts() {
+    let address = parse("1/7").unwrap();
+
+    assert_eq!(address.style(), AddressStyle::TwoLevel);
+    assert_eq!((address.main(), address.sub()), (1, 7));
+    assert_eq!(address.raw(), 0x0807);
+    assert_eq!(parse("31/2047").unwrap().raw(), 0xFFFF);
+}
+
+#[test]
+fn free_notation() {
+    let address = parse("2055").unwrap();
+
+    assert_eq!(address.style(), AddressStyle::Free);
+    assert_eq!(address.raw(), 2055);
+    assert_eq!(parse("65535").unwrap().raw(), u16::MAX);
+    assert_eq!(parse("0").unwrap().raw(), 0);
+}
+
+#[test]
+fn notations_name_the_same_group() {
+    assert_eq!(parse("1/0/7").unwrap(), parse("1/7").unwrap());
+    assert_eq!(parse("1/0/7").unwrap(), parse("2055").unwrap());
+}
+
+#[test]
+fn display_parses_back_in_every_style() {
+    for (text, style) in [
+        ("0/0/0", AddressStyle::ThreeLevel),
+        ("31/7/255", AddressStyle::ThreeLevel),
+        ("1/2/3", AddressStyle::ThreeLevel),
+        ("0/0", AddressStyle::TwoLevel),
+        ("31/2047", AddressStyle::TwoLevel),
+        ("1/7", AddressStyle::TwoLevel),
+        ("0", AddressStyle::Free),
+        ("65535", AddressStyle::Free),
+        ("2055", AddressStyle::Free),
+    ] {
+        let address = parse(text).unwrap();
+        assert_eq!(address.style(), style, "{}", text);
+        assert_eq!(address.to_string(), text);
+
+        let again = parse(&address.to_string()).unwrap();
+        assert_eq!(again, address);
+        assert_eq!(again.style(), style);
+        assert_eq!(address.to_heapless().as_str(), text);
+    }
+}
+
+#[test]
+fn out_of_range_parts_are_named() {
+    assert_eq!(parse("32/0/0"), Err(GroupAddressError::MainOutOfRange));
+    assert_eq!(parse("1/8/0"), Err(GroupAddressError::MiddleOutOfRange));
+    assert_eq!(parse("1/0/256"), Err(GroupAddressError::SubOutOfRange));
+    assert_eq!(parse("32/0"), Err(GroupAddressError::MainOutOfRange));
+    assert_eq!(parse("1/2048"), Err(GroupAddressError::SubOutOfRange));
+}
+
+#[test]
+fn parts_beyond_u16_are_out_of_range() {
+    assert_eq!(parse("1/2/70000"), Err(GroupAddressError::SubOutOfRange));
+    assert_eq!(parse("1/70000/1"), Err(GroupAddressError::MiddleOutOfRange));
+    assert_eq!(parse("70000/1/1"), Err(GroupAddressError::MainOutOfRange));
+    assert_eq!(parse("1/70000"), Err(GroupAddressError::SubOutOfRange));
+    assert_eq!(
+        parse("1/2/99999999999999999999"),
+        Err(GroupAddressError::SubOutOfRange)
+    );
+    assert_eq!(parse("65536"), Err(GroupAddressError::InvalidFormat));
+}
+
+#[test]
+fn malformed_strings_are_rejected() {
+    for text in [
+        "", "/", "1/", "/1", "1//2", "1/2/", "a/1/2", "1/b/2", "1/2/c", "1/-1/2", "+1/2/3",
+        " 1/2/3", "1/2/3 ", "1.2.3", "1/2/3/4", "1/2/3/", "0x10",
+    ] {
+        assert_eq!(
+            parse(text),
+            Err(GroupAddressError::InvalidFormat),
+            "{:?}",
+            text
+        );
+    }
+}
+
+#[test]
+fn individual_addresses() {
+    let address: IndividualAddress = "15.15.255".parse().unwrap();
+    assert_eq!(
+        (address.area(), address.line(), address.device()),
+        (15, 15, 255)
+    );
+    assert_eq!(address.to_string(), "15.15.255");
+
+    let parse = |s: &str| s.parse::<IndividualAddress>();
+    assert_eq!(parse("16.0.1"), Err(IndividualAddressError::AreaOutOfRange));
+    assert_eq!(parse("1.16.1"), Err(IndividualAddressError::LineOutOfRange));
+    assert_eq!(
+        parse("1.1.70000"),
+        Err(IndividualAddressError::DeviceOutOfRange)
+    );
+    assert_eq!(parse("1.1"), Err(IndividualAddressError::InvalidFormat));
+    assert_eq!(parse("1.1.1.1"), Err(IndividualAddressError::InvalidFormat));
+    assert_eq!(parse("1/1/1"), Err(IndividualAddressError::InvalidFormat));
+}
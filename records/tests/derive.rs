@@ -0,0 +1,115 @@
+//! Tests for records declared with `#[derive(KnxRecord)]`
+//!
+//! Checks what the derive generates on sample records: the constructor and
+//! `DPT` constant, the JSON codec (and postcard with `--features postcard`),
+//! `Timestamped` on a `meta` field and the KNX codec (`--features knx`).
+
+use records::*;
+use serde::{Deserialize, Serialize};
+
+/// Window contact, stamped by the gateway
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, KnxRecord)]
+#[knx(dpt = "1.019")]
+pub struct WindowState {
+    pub address: GroupAddress,
+    pub is_open: bool,
+    #[serde(default)]
+    pub meta: Option<RecordMeta>,
+}
+
+/// Fan speed step, without metadata and with its own JSON buffer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, KnxRecord)]
+#[knx(dpt = "5.010", json_buffer = 64)]
+pub struct FanSpeed {
+    pub address: GroupAddress,
+    pub step: u8,
+}
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+fn meta() -> RecordMeta {
+    RecordMeta::new("knx-gateway-001", 3, 120_000)
+        .unwrap()
+        .with_utc_ms(1_700_000_000_000)
+}
+
+/// Encode and decode `record` in every enabled encoding
+fn assert_roundtrip<T: RecordCodec + PartialEq + core::fmt::Debug>(record: &T) {
+    let json = record.to_json().unwrap();
+    assert!(json.len() <= T::JSON_BUFFER_SIZE);
+    assert_eq!(&T::from_json(&json).unwrap(), record);
+
+    #[cfg(feature = "postcard")]
+    {
+        let bytes = record.encode(Encoding::Postcard).unwrap();
+        assert_eq!(&T::decode(&bytes, Encoding::Postcard).unwrap(), record);
+    }
+}
+
+#[test]
+fn constructor_and_constants() {
+    let state = WindowState::new(address("3/1/0"), true);
+
+    assert_eq!(state.address, address("3/1/0"));
+    assert!(state.is_open);
+    assert_eq!(state.meta, None);
+    assert_eq!(WindowState::DPT, "1.019");
+    assert_eq!(<WindowState as KnxRecord>::NAME, "WindowState");
+    assert_eq!(<FanSpeed as KnxRecord>::DPT, "5.010");
+    assert_eq!(FanSpeed::new(address("3/2/0"), 2).value(), 2);
+}
+
+#[test]
+fn json_buffer_covers_the_meta_field() {
+    assert_eq!(
+        WindowState::JSON_BUFFER_SIZE,
+        codec::STATE_JSON_BUFFER_SIZE + codec::ENVELOPE_JSON_LEN
+    );
+    assert_eq!(FanSpeed::JSON_BUFFER_SIZE, 64 + codec::ENVELOPE_JSON_LEN);
+}
+
+#[test]
+fn meta_makes_the_record_timestamped() {
+    let mut state = WindowState::new(address("3/1/0"), false);
+    state.set_meta(meta());
+
+    assert_eq!(state.meta(), Some(&meta()));
+    assert_eq!(state.meta, Some(meta()));
+}
+
+#[test]
+fn roundtrip() {
+    assert_roundtrip(&WindowState::new(address("31/7/255"), true).with_meta(meta()));
+    assert_roundtrip(&WindowState::new(address("1/7"), false));
+    assert_roundtrip(&FanSpeed::new(address("3/2/0"), 255));
+}
+
+#[test]
+fn json_has_the_field_names() {
+    let json = FanSpeed::new(address("3/2/0"), 3).to_json().unwrap();
+    let json = core::str::from_utf8(&json).unwrap();
+
+    assert!(json.contains(r#""address":"3/2/0""#), "{}", json);
+    assert!(json.contains(r#""step":3"#), "{}", json);
+}
+
+#[cfg(feature = "knx")]
+#[test]
+fn knx_codec_follows_the_dpt() {
+    let ga = address("3/1/0");
+
+    let state = WindowState::from_knx(&[0x01], ga).unwrap();
+    assert_eq!(state, WindowState::new(ga, true));
+    assert_eq!(state.to_knx().unwrap(), [0x01]);
+    assert_eq!(
+        WindowState::from_knx(&[0x02], ga),
+        Err(RecordError::ValueOutOfRange)
+    );
+    assert!(WindowState::from_knx(&[], ga).is_err());
+
+    let speed = FanSpeed::from_knx(&[0x03], address("3/2/0")).unwrap();
+    assert_eq!(speed.step, 3);
+    assert_eq!(speed.to_knx().unwrap(), [0x03]);
+}
@@ -0,0 +1,164 @@
+//! Property tests for the DPT 9 2-byte float
+//!
+//! DPT 9 stores `0.01 * M * 2^E` with a 4-bit exponent, so the resolution
+//! halves with every doubling of the value. Run with `cargo test --features knx`.
+
+#![cfg(feature = "knx")]
+
+use proptest::prelude::*;
+use records::dpt::{DptCodec, Float16};
+use records::*;
+
+/// Largest value that can be sent (0x7FFE); 0x7FFF is the invalid marker
+const LARGEST: f32 = 670_433.28;
+
+/// Largest rounding error of an encoded value: half a mantissa step
+fn resolution(value: f32) -> f32 {
+    0.005 + value.abs() / 2048.0
+}
+
+proptest! {
+    #[test]
+    fn roundtrip_within_resolution(value in Float16::MIN..LARGEST) {
+        let raw = Float16::encode(value).unwrap();
+        prop_assert_eq!(raw.len(), 2);
+
+        let decoded = Float16::decode(&raw).unwrap();
+        prop_assert!(
+            (decoded - value).abs() <= resolution(value),
+            "{} decoded as {}", value, decoded
+        );
+    }
+
+    #[test]
+    fn reencoding_is_stable(value in Float16::MIN..LARGEST) {
+        let raw = Float16::encode(value).unwrap();
+        let decoded = Float16::decode(&raw).unwrap();
+        prop_assert_eq!(Float16::encode(decoded).unwrap(), raw);
+    }
+
+    #[test]
+    fn sign_is_symmetric(value in 0.0f32..LARGEST) {
+        let positive = Float16::decode(&Float16::encode(value).unwrap()).unwrap();
+        let negative = Float16::decode(&Float16::encode(-value).unwrap()).unwrap();
+        prop_assert_eq!(negative, -positive);
+    }
+
+    #[test]
+    fn temperature_roundtrip(celsius in -273.0f32..1000.0) {
+        let ga: GroupAddress = "9/1/0".parse().unwrap();
+        let raw = temperature::knx::to_knx(&Temperature::new(ga, celsius)).unwrap();
+        let temperature = temperature::knx::from_knx(&raw, ga).unwrap();
+        prop_assert!((temperature.celsius - celsius).abs() <= resolution(celsius));
+    }
+
+    #[test]
+    fn setpoint_roundtrip(celsius in -273.0f32..1000.0) {
+        let ga: GroupAddress = "3/1/1".parse().unwrap();
+        let raw = hvac::knx::setpoint_to_knx(&SetpointControl::new(ga, celsius)).unwrap();
+        let state = hvac::knx::setpoint_from_knx(&raw, ga).unwrap();
+        prop_assert!((state.celsius - celsius).abs() <= resolution(celsius));
+    }
+}
+
+#[test]
+fn rounds_to_nearest_step() {
+    let encode = |value: f32| Float16::decode(&Float16::encode(value).unwrap()).unwrap();
+
+    // 0.01 steps up to 20.47
+    assert_eq!(encode(21.004), 21.0);
+    assert_eq!(encode(-0.004), 0.0);
+    assert_eq!(encode(-20.466), -20.47);
+
+    // 0.02 steps up to 40.94, 0.04 up to 81.88, ...
+    assert_eq!(encode(21.509), 21.5);
+    assert_eq!(encode(21.511), 21.52);
+    assert_eq!(encode(-21.511), -21.52);
+    assert_eq!(encode(50.01), 50.0);
+    assert_eq!(encode(50.03), 50.04);
+
+    // 327.68 steps at the top of the range
+    assert_eq!(encode(670_000.0), 670_105.6);
+
+    // Rounding past the smallest mantissa keeps the smaller exponent
+    assert_eq!(Float16::encode(-83_920.0).unwrap(), [0xE0, 0x00]);
+    assert_eq!(Float16::encode(-20.485).unwrap(), [0x80, 0x00]);
+}
+
+#[test]
+fn rejects_non_finite() {
+    for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        assert_eq!(Float16::encode(value), Err(RecordError::ValueOutOfRange));
+    }
+
+    let ga: GroupAddress = "9/1/0".parse().unwrap();
+    assert!(Temperature::checked(ga, f32::NAN).is_err());
+    assert!(temperature::knx::to_knx(&Temperature::new(ga, f32::NAN)).is_err());
+}
+
+#[test]
+fn rejects_out_of_range() {
+    for value in [
+        670_600.0,
+        Float16::MAX,
+        671_000.0,
+        -671_100.0,
+        f32::MAX,
+        f32::MIN,
+    ] {
+        assert_eq!(
+            Float16::encode(value),
+            Err(RecordError::ValueOutOfRange),
+            "{}",
+            value
+        );
+    }
+
+    let ga: GroupAddress = "9/1/0".parse().unwrap();
+    assert!(Temperature::checked(ga, -1e6).is_err());
+    assert!(Temperature::checked(ga, 21.5).is_ok());
+}
+
+#[test]
+fn zero_has_one_encoding() {
+    assert_eq!(Float16::encode(0.0).unwrap(), [0x00, 0x00]);
+    assert_eq!(Float16::encode(-0.0).unwrap(), [0x00, 0x00]);
+    assert_eq!(Float16::encode(0.004).unwrap(), [0x00, 0x00]);
+    assert_eq!(Float16::encode(-0.004).unwrap(), [0x00, 0x00]);
+}
+
+#[test]
+fn every_payload_decodes_exactly() {
+    for raw in 0..=u16::MAX {
+        let data = raw.to_be_bytes();
+        let Ok(value) = Float16::decode(&data) else {
+            assert_eq!(data, Float16::INVALID);
+            continue;
+        };
+
+        assert!((Float16::MIN..=Float16::MAX).contains(&value));
+        // Non-canonical payloads (e.g. 0x0802 for 0x0004) re-encode to the same value
+        let reencoded = Float16::decode(&Float16::encode(value).unwrap()).unwrap();
+        assert_eq!(reencoded, value, "0x{:04X}", raw);
+    }
+}
+
+#[test]
+fn invalid_marker_is_rejected() {
+    let ga: GroupAddress = "9/1/0".parse().unwrap();
+
+    assert_eq!(
+        temperature::knx::from_knx(&Float16::INVALID, ga),
+        Err(RecordError::InvalidDptValue)
+    );
+    assert_eq!(
+        sensor::knx::from_knx::<Illuminance>(&Float16::INVALID, ga),
+        Err(RecordError::InvalidDptValue)
+    );
+}
+
+#[test]
+fn rejects_wrong_length() {
+    assert!(Float16::decode(&[0x0C]).is_err());
+    assert!(Float16::decode(&[0x0C, 0x33, 0x00]).is_err());
+}
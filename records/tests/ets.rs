@@ -0,0 +1,363 @@
+//! ETS import tests
+//!
+//! Run with `cargo test --features ets`. The exports below are trimmed
+//! versions of what ETS 6 writes.
+
+#![cfg(feature = "ets")]
+
+use records::ets::{EtsProject, Import};
+use records::*;
+use std::io::{Cursor, Write};
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+fn device<'a>(import: &'a Import, id: &str) -> &'a Device {
+    import
+        .registry
+        .devices
+        .iter()
+        .find(|device| device.id() == id)
+        .unwrap_or_else(|| panic!("no device {} in {:#?}", id, import.registry))
+}
+
+/// 1/1 layout with header, as ETS writes by default
+const CSV_1_1: &str = "\
+\"Group name\";\"Address\";\"Central\";\"Unfiltered\";\"Description\";\"DatapointType\";\"Security\"
+\"Lighting\";\"1/-/-\";\"\";\"\";\"\";\"\";\"Auto\"
+\"Living room\";\"1/0/-\";\"\";\"\";\"\";\"\";\"Auto\"
+\"TV switch\";\"1/0/6\";\"\";\"\";\"\";\"DPST-1-1\";\"Auto\"
+\"TV status\";\"1/0/7\";\"\";\"\";\"\";\"DPST-1-1\";\"Auto\"
+\"Ceiling light dimming\";\"1/0/10\";\"\";\"\";\"\";\"DPST-3-7\";\"Auto\"
+\"Ceiling light brightness\";\"1/0/11\";\"\";\"\";\"\";\"DPST-5-1\";\"Auto\"
+\"Ceiling light brightness status\";\"1/0/12\";\"\";\"\";\"\";\"DPST-5-1\";\"Auto\"
+\"Ceiling light switch\";\"1/0/13\";\"\";\"\";\"\";\"DPST-1-1\";\"Auto\"
+\"Shading\";\"2/-/-\";\"\";\"\";\"\";\"\";\"Auto\"
+\"Living room\";\"2/1/-\";\"\";\"\";\"\";\"\";\"Auto\"
+\"Blind up/down\";\"2/1/0\";\"\";\"\";\"\";\"DPST-1-8\";\"Auto\"
+\"Blind stop\";\"2/1/1\";\"\";\"\";\"\";\"DPST-1-7\";\"Auto\"
+\"Blind position\";\"2/1/2\";\"\";\"\";\"\";\"DPST-5-1\";\"Auto\"
+\"Blind slat angle\";\"2/1/3\";\"\";\"\";\"\";\"DPST-5-1\";\"Auto\"
+\"Blind position status\";\"2/1/4\";\"\";\"\";\"\";\"DPST-5-1\";\"Auto\"
+\"Blind moving status\";\"2/1/6\";\"\";\"\";\"\";\"DPST-1-11\";\"Auto\"
+\"Blind wind alarm\";\"2/1/7\";\"\";\"\";\"\";\"DPST-1-5\";\"Auto\"
+\"Heating\";\"3/-/-\";\"\";\"\";\"\";\"\";\"Auto\"
+\"Bedroom\";\"3/1/-\";\"\";\"\";\"\";\"\";\"Auto\"
+\"Thermostat mode\";\"3/1/0\";\"\";\"\";\"\";\"DPST-20-102\";\"Auto\"
+\"Thermostat mode status\";\"3/1/1\";\"\";\"\";\"\";\"DPST-20-102\";\"Auto\"
+\"Setpoint\";\"3/1/2\";\"\";\"\";\"\";\"DPST-9-1\";\"Auto\"
+\"Setpoint status\";\"3/1/3\";\"\";\"\";\"\";\"DPST-9-1\";\"Auto\"
+\"Temperature\";\"3/1/4\";\"\";\"\";\"\";\"DPST-9-1\";\"Auto\"
+\"Valve\";\"3/1/5\";\"\";\"\";\"\";\"DPST-5-4\";\"Auto\"
+\"Spare\";\"3/1/6\";\"\";\"\";\"\";\"\";\"Auto\"
+";
+
+#[test]
+fn csv_1_1() {
+    let project = EtsProject::from_csv(CSV_1_1).unwrap();
+    assert_eq!(project.group_addresses.len(), 20);
+    assert_eq!(
+        project.group_addresses[0].ranges,
+        ["Lighting", "Living room"]
+    );
+    assert_eq!(project.group_addresses[0].dpt, Some(Dpt::new(1, 1)));
+    assert_eq!(project.group_addresses[19].dpt, None);
+
+    let import = project.to_registry().unwrap();
+
+    let tv = device(&import, "living-room/tv");
+    assert_eq!(tv.kind, DeviceKind::Switch);
+    assert_eq!(tv.location, ["Lighting"]);
+    assert_eq!(tv.address(Role::Control), Some(address("1/0/6")));
+    assert_eq!(tv.address(Role::State), Some(address("1/0/7")));
+
+    // The dimmer's switching object becomes a switch of the same name
+    let dimmer = device(&import, "living-room/ceiling-light-dimmer");
+    assert_eq!(dimmer.address(Role::Step), Some(address("1/0/10")));
+    assert_eq!(dimmer.address(Role::Value), Some(address("1/0/11")));
+    assert_eq!(dimmer.address(Role::State), Some(address("1/0/12")));
+    let switch = device(&import, "living-room/ceiling-light-switch");
+    assert_eq!(switch.address(Role::Control), Some(address("1/0/13")));
+
+    let blind = device(&import, "living-room/blind");
+    assert_eq!(blind.kind, DeviceKind::Blind);
+    assert_eq!(blind.location, ["Shading"]);
+    for (role, ga) in [
+        (Role::Move, "2/1/0"),
+        (Role::Step, "2/1/1"),
+        (Role::Position, "2/1/2"),
+        (Role::SlatAngle, "2/1/3"),
+        (Role::PositionState, "2/1/4"),
+        (Role::Moving, "2/1/6"),
+    ] {
+        assert_eq!(blind.address(role), Some(address(ga)), "{}", role);
+    }
+    let alarm = device(&import, "living-room/blind-wind-alarm");
+    assert_eq!(alarm.kind, DeviceKind::Switch);
+
+    let mode = device(&import, "bedroom/thermostat-mode");
+    assert_eq!(mode.kind, DeviceKind::HvacMode);
+    assert_eq!(mode.address(Role::Control), Some(address("3/1/0")));
+    assert_eq!(mode.address(Role::State), Some(address("3/1/1")));
+    let setpoint = device(&import, "bedroom/setpoint");
+    assert_eq!(setpoint.kind, DeviceKind::Setpoint);
+    assert_eq!(setpoint.address(Role::State), Some(address("3/1/3")));
+    assert_eq!(
+        device(&import, "bedroom/temperature").kind,
+        DeviceKind::Temperature
+    );
+
+    // Reported, not dropped
+    let skipped: Vec<String> = import.skipped.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        skipped,
+        [
+            "3/1/5 \"Valve\": DPT 5.004 is not supported",
+            "3/1/6 \"Spare\": no DPT assigned in ETS",
+        ]
+    );
+}
+
+#[test]
+fn csv_1_1_without_header() {
+    let (_, rows) = CSV_1_1.split_once('\n').unwrap();
+    let project = EtsProject::from_csv(rows).unwrap();
+
+    // Without a header the DPT is taken from the sixth column
+    assert_eq!(project.group_addresses.len(), 20);
+    assert_eq!(project.group_addresses[0].dpt, Some(Dpt::new(1, 1)));
+    assert_eq!(
+        project.group_addresses,
+        EtsProject::from_csv(CSV_1_1).unwrap().group_addresses
+    );
+}
+
+#[test]
+fn csv_3_1_without_header() {
+    let csv = "\
+Lighting,,,1/-/-,,,,,Auto
+,Kitchen,,1/3/-,,,,,Auto
+,,Spots,1/3/0,,,,DPST-1-1,Auto
+,,Spots status,1/3/1,,,,DPST-1-1,Auto
+";
+    let project = EtsProject::from_csv(csv).unwrap();
+
+    // Without a header the DPT is taken from the eighth column
+    assert_eq!(project.group_addresses[0].name, "Spots");
+    assert_eq!(project.group_addresses[0].dpt, Some(Dpt::new(1, 1)));
+
+    let import = project.to_registry().unwrap();
+
+    let spots = device(&import, "kitchen/spots");
+    assert_eq!(spots.location, ["Lighting"]);
+    assert_eq!(spots.address(Role::Control), Some(address("1/3/0")));
+    assert_eq!(spots.address(Role::State), Some(address("1/3/1")));
+    assert!(import.skipped.is_empty());
+}
+
+/// Import an export written to a file, as `tower import` reads it
+fn load(name: &str, data: &[u8]) -> Import {
+    let path = std::env::temp_dir().join(format!("ets-{}-{}", std::process::id(), name));
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(data)
+        .unwrap();
+    let project = EtsProject::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    project.unwrap().to_registry().unwrap()
+}
+
+#[test]
+fn csv_latin1() {
+    let csv = b"\"Group name\",\"Address\",\"Central\",\"Unfiltered\",\"Description\",\"DatapointType\"\n\
+\"Licht\",\"1/-/-\",\"\",\"\",\"\",\"\"\n\
+\"K\xFCche\",\"1/0/-\",\"\",\"\",\"\",\"\"\n\
+\"Decke\",\"1/0/0\",\"\",\"\",\"\",\"DPST-1-1\"\n";
+    let import = load("latin1.csv", csv);
+
+    assert_eq!(device(&import, "küche/decke").kind, DeviceKind::Switch);
+    assert_eq!(device(&import, "küche/decke").location, ["Licht"]);
+}
+
+#[test]
+fn csv_utf8_with_bom() {
+    // Read as UTF-8, not as Latin-1 ("KÃ¼che")
+    let csv = "\u{FEFF}\"Group name\",\"Address\",\"DatapointType\"\n\
+\"Licht\",\"1/-/-\",\"\"\n\
+\"Küche\",\"1/0/-\",\"\"\n\
+\"Decke\",\"1/0/0\",\"DPST-1-1\"\n";
+    let import = load("bom.csv", csv.as_bytes());
+
+    assert_eq!(device(&import, "küche/decke").kind, DeviceKind::Switch);
+}
+
+/// Group address XML export
+const XML_EXPORT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<GroupAddress-Export xmlns="http://knx.org/xml/ga-export/01">
+  <GroupRange Name="Central" RangeStart="3584" RangeEnd="3839">
+    <GroupRange Name="House" RangeStart="3584" RangeEnd="3839">
+      <GroupAddress Name="Clock time" Address="7/0/0" DPTs="DPST-10-1" />
+      <GroupAddress Name="Clock date" Address="7/0/1" DPTs="DPST-11-1" />
+      <GroupAddress Name="Scene" Address="6/0/0" DPTs="DPST-18-1" />
+      <GroupAddress Name="Display" Address="7/0/3" DPTs="DPST-16-1" />
+      <GroupAddress Name="Meter" Address="7/0/4" DPTs="DPST-13-10" />
+      <GroupAddress Name="Lux" Address="7/0/5" DPTs="DPST-9-4" />
+      <GroupAddress Name="Strip" Address="7/0/6" DPTs="DPST-251-600" />
+    </GroupRange>
+  </GroupRange>
+</GroupAddress-Export>
+"#;
+
+#[test]
+fn xml_export() {
+    let import = EtsProject::from_xml(XML_EXPORT)
+        .unwrap()
+        .to_registry()
+        .unwrap();
+
+    let clock = device(&import, "house/clock");
+    assert_eq!(clock.address(Role::Time), Some(address("7/0/0")));
+    assert_eq!(clock.address(Role::Date), Some(address("7/0/1")));
+    assert_eq!(device(&import, "house/scene").kind, DeviceKind::Scene);
+    assert_eq!(device(&import, "house/display").kind, DeviceKind::Text);
+    assert_eq!(
+        device(&import, "house/meter").kind,
+        DeviceKind::EnergyCounter
+    );
+    assert_eq!(device(&import, "house/lux").kind, DeviceKind::Illuminance);
+    assert_eq!(device(&import, "house/strip").kind, DeviceKind::ColorLight);
+    assert!(import.skipped.is_empty());
+}
+
+/// Installation data (`P-XXXX/0.xml`) with ETS 6 functions in rooms
+const PROJECT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<KNX xmlns="http://knx.org/xml/project/23" CreatedBy="ETS6">
+  <Project Id="P-0A1B">
+    <Installations>
+      <Installation Name="">
+        <Locations>
+          <Space Id="P-0A1B-0_BP-1" Type="Building" Name="House">
+            <Space Id="P-0A1B-0_BP-2" Type="Floor" Name="Ground floor">
+              <Space Id="P-0A1B-0_BP-3" Type="Room" Name="Living Room">
+                <Function Id="P-0A1B-0_F-1" Name="TV" Type="FT-1">
+                  <GroupAddressRef Id="P-0A1B-0_F-1_GR-1" RefId="P-0A1B-0_GA-1" Role="SwitchOnOff" />
+                  <GroupAddressRef Id="P-0A1B-0_F-1_GR-2" RefId="P-0A1B-0_GA-2" Role="InfoOnOff" />
+                </Function>
+              </Space>
+            </Space>
+          </Space>
+        </Locations>
+        <GroupAddresses>
+          <GroupRanges>
+            <GroupRange Id="P-0A1B-0_GR-1" RangeStart="2048" RangeEnd="4095" Name="Lighting">
+              <GroupRange Id="P-0A1B-0_GR-2" RangeStart="2048" RangeEnd="2303" Name="Switching">
+                <GroupAddress Id="P-0A1B-0_GA-1" Address="2054" Name="Socket" DatapointType="DPST-1-1" />
+                <GroupAddress Id="P-0A1B-0_GA-2" Address="2055" Name="Socket feedback" DatapointType="DPST-1-1" />
+                <GroupAddress Id="P-0A1B-0_GA-3" Address="2056" Name="Hall light" DatapointType="DPT-1" />
+              </GroupRange>
+            </GroupRange>
+          </GroupRanges>
+        </GroupAddresses>
+      </Installation>
+    </Installations>
+  </Project>
+</KNX>
+"#;
+
+fn knxproj(files: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    let mut archive = zip.finish().unwrap();
+    archive.set_position(0);
+    archive
+}
+
+#[test]
+fn knxproj_functions_and_rooms() {
+    let archive = knxproj(&[
+        ("knx_master.xml", "<KNX />"),
+        (
+            "P-0A1B/project.xml",
+            r#"<KNX><Project><ProjectInformation GroupAddressStyle="ThreeLevel" /></Project></KNX>"#,
+        ),
+        ("P-0A1B/0.xml", PROJECT),
+    ]);
+    let import = EtsProject::from_knxproj(archive)
+        .unwrap()
+        .to_registry()
+        .unwrap();
+
+    // The function names the device and its room, whatever the address names
+    let tv = device(&import, "living-room/tv");
+    assert_eq!(tv.location, ["House", "Ground floor"]);
+    assert_eq!(tv.address(Role::Control), Some(address("1/0/6")));
+    assert_eq!(tv.address(Role::State), Some(address("1/0/7")));
+    assert_eq!(tv.datapoints[0].address.to_string(), "1/0/6");
+
+    // Addresses outside functions fall back to their group ranges
+    let hall = device(&import, "switching/hall-light");
+    assert_eq!(hall.location, ["Lighting"]);
+    assert_eq!(hall.datapoints[0].dpt, Dpt::any(1));
+}
+
+#[test]
+fn knxproj_two_level_addresses() {
+    let archive = knxproj(&[
+        (
+            "P-0A1B/project.xml",
+            r#"<KNX><Project><ProjectInformation GroupAddressStyle="TwoLevel" /></Project></KNX>"#,
+        ),
+        ("P-0A1B/0.xml", PROJECT),
+    ]);
+    let project = EtsProject::from_knxproj(archive).unwrap();
+    assert_eq!(project.group_addresses[0].address.to_string(), "1/6");
+}
+
+#[test]
+fn knxproj_with_password_is_rejected() {
+    // ETS stores a protected project as an encrypted P-XXXX.zip inside the archive
+    let archive = knxproj(&[("knx_master.xml", "<KNX />"), ("P-0A1B.zip", "encrypted")]);
+    let error = EtsProject::from_knxproj(archive).unwrap_err();
+    assert!(matches!(error, RegistryError::Parse(_)));
+    assert!(
+        error.to_string().contains("password-protected"),
+        "{}",
+        error
+    );
+
+    let archive = knxproj(&[("knx_master.xml", "<KNX />")]);
+    let error = EtsProject::from_knxproj(archive).unwrap_err();
+    assert!(error.to_string().contains("no project found"), "{}", error);
+}
+
+#[test]
+fn import_roundtrips_through_toml() {
+    let import = EtsProject::from_csv(CSV_1_1)
+        .unwrap()
+        .to_registry()
+        .unwrap();
+    let toml = import.registry.to_toml().unwrap();
+    assert_eq!(Registry::from_toml(&toml).unwrap(), import.registry);
+}
+
+#[test]
+fn duplicate_names_stay_unique() {
+    let csv = "\
+\"Group name\";\"Address\";\"DatapointType\"
+\"Ground floor\";\"1/-/-\";\"\"
+\"Hall\";\"1/0/-\";\"\"
+\"Light\";\"1/0/0\";\"DPST-1-1\"
+\"First floor\";\"2/-/-\";\"\"
+\"Hall\";\"2/0/-\";\"\"
+\"Light\";\"2/0/0\";\"DPST-1-1\"
+";
+    let import = EtsProject::from_csv(csv).unwrap().to_registry().unwrap();
+    let ids: Vec<String> = import.registry.devices.iter().map(Device::id).collect();
+    assert_eq!(ids, ["hall/light", "hall/light-2"]);
+    assert_eq!(import.registry.devices[1].location, ["First floor"]);
+}
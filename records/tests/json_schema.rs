@@ -0,0 +1,91 @@
+//! JSON Schema export tests
+//!
+//! Checks that each schema lists exactly the fields its record serializes.
+
+#![cfg(feature = "std")]
+
+use records::*;
+use serde::Serialize;
+use serde_json::Value;
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+/// Serialized fields of `record` are the schema's properties, and required ones are present
+fn assert_fields<T: Serialize + RecordSchema>(record: &T) {
+    let schema = T::json_schema();
+    let properties = schema["properties"].as_object().unwrap();
+    let json = serde_json::to_value(record).unwrap();
+    let fields = json.as_object().unwrap();
+
+    for field in fields.keys() {
+        assert!(properties.contains_key(field), "{} not in schema", field);
+    }
+    for field in properties.keys() {
+        assert!(fields.contains_key(field), "{} not serialized", field);
+    }
+    for field in schema["required"].as_array().unwrap() {
+        assert!(fields.contains_key(field.as_str().unwrap()));
+    }
+    assert_eq!(schema["additionalProperties"], Value::Bool(false));
+}
+
+#[test]
+fn schemas_match_serialized_fields() {
+    let meta = RecordMeta::new("knx-gateway-001", 1, 2).unwrap();
+
+    assert_fields(&SwitchState::new(address("1/0/7"), true).with_meta(meta.clone()));
+    assert_fields(&SwitchControl::new(address("1/0/6"), true));
+    assert_fields(&Temperature::new(address("9/1/0"), 21.5));
+    assert_fields(&DimmerState::new(address("1/2/2"), 40));
+    assert_fields(&DimmerControl::new(address("1/2/1"), DimmerCommand::Stop));
+    assert_fields(&BlindControl::new(address("2/1/2"), BlindCommand::Up));
+    assert_fields(&SensorReading::<Co2>::new(address("9/1/4"), 800.0));
+    assert_fields(&PowerReading::new(address("5/0/1"), 230.0));
+    assert_fields(&SceneControl::new(address("6/0/0"), 3, false));
+    assert_fields(&HvacModeControl::new(address("3/1/0"), HvacMode::Comfort));
+    assert_fields(&SetpointState::new(address("3/1/3"), 21.0));
+    assert_fields(
+        &CommandRejection::new(address("1/0/9"), RejectReason::UnknownAddress)
+            .with_meta(meta.clone()),
+    );
+    assert_fields(&ColorLightState::new(
+        address("4/0/1"),
+        Rgbw::rgb(255, 128, 0),
+    ));
+}
+
+#[test]
+fn collection_uses_aimx_record_names() {
+    let schemas = RecordSchemas::all();
+
+    assert_eq!(schemas.schema_version, codec::SCHEMA_VERSION);
+    assert_eq!(schemas.records.len(), 27);
+    assert!(schemas
+        .records
+        .contains_key("records::switch::SwitchControl"));
+    assert_eq!(
+        schemas.records[core::any::type_name::<SensorReading<Humidity>>()]["properties"]["unit"]
+            ["const"],
+        "%"
+    );
+}
+
+#[test]
+fn documents_address_formats_and_ranges() {
+    let schema = DimmerControl::json_schema();
+
+    assert_eq!(schema["$schema"], schema::DIALECT);
+    assert_eq!(
+        schema["properties"]["address"]["anyOf"]
+            .as_array()
+            .unwrap()
+            .len(),
+        3
+    );
+
+    let brightness = &schema["properties"]["command"]["oneOf"][0]["properties"]["brightness"];
+    assert_eq!(brightness["minimum"], 0);
+    assert_eq!(brightness["maximum"], 100);
+}
@@ -0,0 +1,370 @@
+//! KNX encode/decode tests against DPT reference vectors
+//!
+//! Payloads are taken from the examples in the KNX datapoint type
+//! specification (03_07_02). Run with `cargo test --features knx`.
+
+#![cfg(feature = "knx")]
+
+use records::dpt::{DptCodec, Float16, Float32, Percent, Signed32, Unsigned16};
+use records::*;
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+#[test]
+fn dpt1_switch() {
+    let ga = address("1/0/7");
+
+    assert!(switch::knx::from_knx(&[0x01], ga).unwrap().is_on);
+    assert!(!switch::knx::from_knx(&[0x00], ga).unwrap().is_on);
+    assert_eq!(
+        switch::knx::to_knx(&SwitchControl::new(ga, true)).unwrap(),
+        [0x01]
+    );
+    assert_eq!(
+        switch::knx::to_knx(&SwitchControl::new(ga, false)).unwrap(),
+        [0x00]
+    );
+    assert_eq!(
+        switch::knx::from_knx(&[], ga),
+        Err(RecordError::InvalidDptLength {
+            expected: 1,
+            actual: 0
+        })
+    );
+}
+
+#[test]
+fn dpt1_blind_move_and_step() {
+    let ga = address("2/1/0");
+    let encode = |command| {
+        let control = BlindControl::new(ga, command);
+        blinds::knx::move_to_knx(&control)
+            .unwrap()
+            .or(blinds::knx::step_to_knx(&control).unwrap())
+    };
+
+    // DPT 1.008 / 1.007: 0 = up, 1 = down
+    assert_eq!(encode(BlindCommand::Up).unwrap(), [0x00]);
+    assert_eq!(encode(BlindCommand::Down).unwrap(), [0x01]);
+    assert_eq!(encode(BlindCommand::StepUp).unwrap(), [0x00]);
+    assert_eq!(encode(BlindCommand::StepDown).unwrap(), [0x01]);
+    assert_eq!(encode(BlindCommand::Stop).unwrap(), [0x00]);
+    assert_eq!(encode(BlindCommand::Position(50)), None);
+
+    let status = blinds::knx::BlindStatus::new(ga);
+    assert!(status.moving_from_knx(&[0x01]).unwrap().moving);
+    assert!(status.moving_from_knx(&[0x02]).is_err());
+}
+
+#[test]
+fn dpt3_007_dimming_step() {
+    let ga = address("1/2/0");
+    let step = |increase, intervals| {
+        dimmer::knx::step_to_knx(&DimmerControl::new(
+            ga,
+            DimmerCommand::Step {
+                increase,
+                intervals,
+            },
+        ))
+    };
+
+    // Bit 3 = increase, bits 0..2 = step code (2^(code-1) intervals)
+    assert_eq!(step(true, 1).unwrap().unwrap(), [0x09]);
+    assert_eq!(step(true, 4).unwrap().unwrap(), [0x0B]);
+    assert_eq!(step(false, 64).unwrap().unwrap(), [0x07]);
+    assert_eq!(
+        dimmer::knx::step_to_knx(&DimmerControl::new(ga, DimmerCommand::Stop))
+            .unwrap()
+            .unwrap(),
+        [0x00]
+    );
+    assert_eq!(step(true, 3), Err(RecordError::ValueOutOfRange));
+    assert_eq!(
+        dimmer::knx::step_to_knx(&DimmerControl::brightness(ga, 50)).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn dpt5_001_percentage() {
+    // 0..100 % scaled to 0..255
+    for (percent, raw) in [(0, 0x00), (1, 0x03), (50, 0x80), (99, 0xFC), (100, 0xFF)] {
+        assert_eq!(Percent::encode(percent).unwrap(), [raw], "{}%", percent);
+        assert_eq!(Percent::decode(&[raw]).unwrap(), percent, "0x{:02X}", raw);
+    }
+    assert!(Percent::encode(101).is_err());
+
+    let ga = address("1/2/2");
+    assert_eq!(dimmer::knx::from_knx(&[0xFF], ga).unwrap().brightness, 100);
+    assert_eq!(
+        dimmer::knx::brightness_to_knx(&DimmerControl::brightness(ga, 50))
+            .unwrap()
+            .unwrap(),
+        [0x80]
+    );
+    assert_eq!(
+        blinds::knx::position_to_knx(&BlindControl::new(ga, BlindCommand::Position(100)))
+            .unwrap()
+            .unwrap(),
+        [0xFF]
+    );
+    assert_eq!(
+        blinds::knx::BlindStatus::new(ga)
+            .slat_angle_from_knx(&[0x80])
+            .unwrap()
+            .slat_angle,
+        Some(50)
+    );
+}
+
+#[test]
+fn dpt7_unsigned() {
+    assert_eq!(Unsigned16::encode(0x1234).unwrap(), [0x12, 0x34]);
+    assert_eq!(Unsigned16::decode(&[0xFF, 0xFF]).unwrap(), u16::MAX);
+}
+
+#[test]
+fn dpt9_float() {
+    // Encoders use the smallest exponent that fits the 12-bit mantissa
+    for (value, raw) in [
+        (0.0, [0x00, 0x00]),
+        (0.01, [0x00, 0x01]),
+        (20.47, [0x07, 0xFF]),
+        (21.5, [0x0C, 0x33]),
+        (1000.32, [0x36, 0x1B]),
+        (-0.01, [0x87, 0xFF]),
+        (-5.0, [0x86, 0x0C]),
+        (-30.0, [0x8A, 0x24]),
+        (670_433.28, [0x7F, 0xFE]),
+        (Float16::MIN, [0xF8, 0x00]),
+    ] {
+        assert_eq!(Float16::encode(value).unwrap(), raw, "{}", value);
+        assert_eq!(Float16::decode(&raw).unwrap(), value, "{:02X?}", raw);
+    }
+
+    // 0x7FFF marks an invalid value
+    assert_eq!(
+        Float16::decode(&Float16::INVALID),
+        Err(RecordError::InvalidDptValue)
+    );
+    assert_eq!(
+        Float16::encode(Float16::MAX),
+        Err(RecordError::ValueOutOfRange)
+    );
+}
+
+#[test]
+fn dpt9_records() {
+    let ga = address("9/1/0");
+
+    assert_eq!(
+        temperature::knx::from_knx(&[0x8A, 0x24], ga)
+            .unwrap()
+            .celsius,
+        -30.0
+    );
+    assert_eq!(
+        temperature::knx::to_knx(&Temperature::new(ga, 21.5)).unwrap(),
+        [0x0C, 0x33]
+    );
+    assert_eq!(
+        hvac::knx::setpoint_to_knx(&SetpointControl::new(ga, 21.5)).unwrap(),
+        [0x0C, 0x33]
+    );
+    assert_eq!(
+        hvac::knx::setpoint_from_knx(&[0x86, 0x0C], ga)
+            .unwrap()
+            .celsius,
+        -5.0
+    );
+    assert_eq!(
+        sensor::knx::from_knx::<Humidity>(&[0x0C, 0x33], ga)
+            .unwrap()
+            .value,
+        21.5
+    );
+    assert_eq!(
+        sensor::knx::from_knx::<Humidity>(&[0x86, 0x0C], ga),
+        Err(RecordError::ValueOutOfRange)
+    );
+}
+
+#[test]
+fn dpt13_signed() {
+    assert_eq!(Signed32::encode(12345).unwrap(), [0x00, 0x00, 0x30, 0x39]);
+    assert_eq!(
+        Signed32::decode(&[0x80, 0x00, 0x00, 0x00]).unwrap(),
+        i32::MIN
+    );
+
+    let counter = energy::knx::counter_from_knx(&[0xFF, 0xFF, 0xFF, 0xFE], address("5/0/0"));
+    assert_eq!(counter.unwrap().watt_hours, -2);
+}
+
+#[test]
+fn dpt14_float() {
+    assert_eq!(Float32::encode(230.0).unwrap(), [0x43, 0x66, 0x00, 0x00]);
+    assert_eq!(Float32::decode(&[0xC2, 0x48, 0x00, 0x00]).unwrap(), -50.0);
+
+    let ga = address("5/0/1");
+    assert_eq!(
+        PowerReading::from_knx(&[0x43, 0x66, 0x00, 0x00], ga)
+            .unwrap()
+            .watts,
+        230.0
+    );
+    assert_eq!(
+        PowerReading::from_knx(&[0x7F, 0xC0, 0x00, 0x00], ga),
+        Err(RecordError::ValueOutOfRange)
+    );
+}
+
+#[test]
+fn dpt10_time() {
+    let time = TimeOfDay::new(Some(Weekday::Wednesday), 13, 45, 30);
+    let raw = [0x6D, 0x2D, 0x1E];
+
+    assert_eq!(clock::knx::time_to_knx(&time).unwrap(), raw);
+    assert_eq!(clock::knx::time_from_knx(&raw).unwrap(), time);
+
+    // Weekday 0 = no day
+    assert_eq!(
+        clock::knx::time_from_knx(&[0x17, 0x3B, 0x3B]).unwrap(),
+        TimeOfDay::new(None, 23, 59, 59)
+    );
+    assert!(clock::knx::time_from_knx(&[0x18, 0x00, 0x00]).is_err());
+}
+
+#[test]
+fn dpt11_date() {
+    // Years 90..99 are 1990..1999, 0..89 are 2000..2089
+    assert_eq!(
+        clock::knx::date_to_knx(&Date::new(2026, 10, 16)).unwrap(),
+        [0x10, 0x0A, 0x1A]
+    );
+    assert_eq!(
+        clock::knx::date_from_knx(&[0x1F, 0x0C, 0x63]).unwrap(),
+        Date::new(1999, 12, 31)
+    );
+    assert!(clock::knx::date_from_knx(&[0x00, 0x01, 0x00]).is_err());
+}
+
+#[test]
+fn dpt19_datetime() {
+    let mut datetime = DateTime::new(
+        Some(Date::new(2026, 10, 16)),
+        Some(TimeOfDay::new(Some(Weekday::Friday), 8, 30, 0)),
+    );
+    datetime.flags.working_day = Some(true);
+    datetime.flags.summer_time = true;
+    datetime.flags.external_sync = true;
+
+    // Year - 1900, month, day, weekday/hour, minute, second, status, quality
+    let raw = [0x7E, 0x0A, 0x10, 0xA8, 0x1E, 0x00, 0x41, 0x80];
+
+    assert_eq!(clock::knx::datetime_to_knx(&datetime).unwrap(), raw);
+    assert_eq!(clock::knx::datetime_from_knx(&raw).unwrap(), datetime);
+}
+
+#[test]
+fn dpt16_text() {
+    let ga = address("8/0/0");
+    let raw = *b"KNX is OK\0\0\0\0\0";
+    let control = TextMessageControl::new(ga, "KNX is OK".parse().unwrap(), TextEncoding::Ascii);
+
+    assert_eq!(text::knx::to_knx(&control).unwrap(), raw);
+    assert_eq!(
+        text::knx::from_knx(&raw, ga, TextEncoding::Ascii)
+            .unwrap()
+            .text
+            .as_str(),
+        "KNX is OK"
+    );
+
+    // DPT 16.001 is ISO 8859-1, DPT 16.000 is 7-bit ASCII
+    let latin1 = [0xC4, b'n', b'd', b'e', b'r', b'n', 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(
+        text::knx::from_knx(&latin1, ga, TextEncoding::Latin1)
+            .unwrap()
+            .text
+            .as_str(),
+        "Ändern"
+    );
+    assert!(text::knx::from_knx(&latin1, ga, TextEncoding::Ascii).is_err());
+}
+
+#[test]
+fn dpt17_18_scene() {
+    let ga = address("6/0/0");
+
+    // Scene numbers are 1-based, the wire format 0-based
+    assert_eq!(
+        scene::knx::to_knx(&SceneControl::activate(ga, 1)).unwrap(),
+        [0x00]
+    );
+    assert_eq!(
+        scene::knx::to_knx(&SceneControl::learn(ga, 64)).unwrap(),
+        [0xBF]
+    );
+    assert_eq!(
+        scene::knx::from_knx(&[0x82], ga).unwrap(),
+        SceneState::new(ga, 3, true)
+    );
+    assert!(scene::knx::from_knx(&[0x40], ga).is_err());
+    assert!(scene::knx::to_knx(&SceneControl::activate(ga, 65)).is_err());
+}
+
+#[test]
+fn dpt20_102_hvac_mode() {
+    let ga = address("3/1/0");
+
+    for (mode, raw) in [
+        (HvacMode::Auto, 0),
+        (HvacMode::Comfort, 1),
+        (HvacMode::Standby, 2),
+        (HvacMode::Economy, 3),
+        (HvacMode::BuildingProtection, 4),
+    ] {
+        assert_eq!(
+            hvac::knx::mode_to_knx(&HvacModeControl::new(ga, mode)).unwrap(),
+            [raw]
+        );
+        assert_eq!(hvac::knx::mode_from_knx(&[raw], ga).unwrap().mode, mode);
+    }
+    assert!(hvac::knx::mode_from_knx(&[5], ga).is_err());
+}
+
+#[test]
+fn dpt232_251_color() {
+    let ga = address("4/0/0");
+
+    assert_eq!(
+        color::knx::rgb_to_knx(&ColorLightControl::hex(ga, Rgbw::rgb(255, 128, 0))).unwrap(),
+        [0xFF, 0x80, 0x00]
+    );
+    assert_eq!(
+        color::knx::rgb_from_knx(&[0xFF, 0x80, 0x00], ga)
+            .unwrap()
+            .color,
+        Rgbw::rgb(255, 128, 0)
+    );
+
+    // R, G, B, W, reserved, validity mask (R = bit 3 .. W = bit 0)
+    assert_eq!(
+        color::knx::rgbw_to_knx(&ColorLightControl::hex(ga, Rgbw::rgb_white(1, 2, 3, 4))).unwrap(),
+        [0x01, 0x02, 0x03, 0x04, 0x00, 0x0F]
+    );
+    assert_eq!(
+        color::knx::rgbw_to_knx(&ColorLightControl::hex(ga, Rgbw::rgb(1, 2, 3))).unwrap(),
+        [0x01, 0x02, 0x03, 0x00, 0x00, 0x0E]
+    );
+    assert_eq!(
+        color::knx::rgbw_from_knx(&[0x01, 0x02, 0x03, 0x04, 0x00, 0x0E], ga)
+            .unwrap()
+            .color,
+        Rgbw::rgb(1, 2, 3)
+    );
+}
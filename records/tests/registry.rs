@@ -0,0 +1,247 @@
+//! Device registry tests
+//!
+//! Run with `cargo test --features registry`. Also checks the repository's
+//! own `registry.toml`, which tower loads by default.
+
+#![cfg(feature = "registry")]
+
+use records::*;
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+const SWITCH: &str = r#"
+[[devices]]
+name = "tv"
+room = "living-room"
+kind = "switch"
+datapoints = [
+    { role = "state", address = "1/0/7", dpt = "1.001" },
+    { role = "control", address = "1/0/6", dpt = "DPST-1-1" },
+]
+"#;
+
+#[test]
+fn parses_toml() {
+    let registry = Registry::from_toml(SWITCH).unwrap();
+    let device = &registry.devices[0];
+
+    assert_eq!(device.kind, DeviceKind::Switch);
+    assert_eq!(device.id(), "living-room/tv");
+    assert_eq!(device.address(Role::State), Some(address("1/0/7")));
+    assert_eq!(device.address(Role::Control), Some(address("1/0/6")));
+    assert_eq!(device.datapoints[1].dpt, Dpt::new(1, 1));
+    assert_eq!(device.state_topic(), "knx/living-room/tv/state");
+    assert_eq!(
+        device.control_topic().as_deref(),
+        Some("knx/living-room/tv/control")
+    );
+}
+
+#[test]
+fn parses_json() {
+    let json = r#"{"devices": [{
+        "name": "outdoor", "room": "garden", "kind": "temperature",
+        "datapoints": [{"role": "state", "address": "9/1/10", "dpt": "9.001"}]
+    }]}"#;
+    let registry = Registry::from_json(json).unwrap();
+    let device = &registry.devices[0];
+
+    assert_eq!(device.state_topic(), "knx/garden/outdoor/state");
+    assert_eq!(device.control_topic(), None);
+    assert_eq!(device.writable_addresses().count(), 0);
+}
+
+#[test]
+fn toml_roundtrip() {
+    let registry = Registry::from_toml(SWITCH).unwrap();
+    let toml = registry.to_toml().unwrap();
+    assert_eq!(Registry::from_toml(&toml).unwrap(), registry);
+}
+
+#[test]
+fn dpt_forms() {
+    assert_eq!("9.001".parse::<Dpt>().unwrap(), Dpt::new(9, 1));
+    assert_eq!("DPST-9-1".parse::<Dpt>().unwrap(), Dpt::new(9, 1));
+    assert_eq!("232.600".parse::<Dpt>().unwrap(), Dpt::new(232, 600));
+    assert_eq!("1".parse::<Dpt>().unwrap(), Dpt::any(1));
+    assert_eq!("1.xxx".parse::<Dpt>().unwrap(), Dpt::any(1));
+    assert_eq!("DPT-1".parse::<Dpt>().unwrap(), Dpt::any(1));
+    assert!("9.1.1".parse::<Dpt>().is_err());
+    assert!("DPST-9".parse::<Dpt>().is_err());
+    assert!("".parse::<Dpt>().is_err());
+
+    assert_eq!(Dpt::new(9, 1).to_string(), "9.001");
+    assert_eq!(Dpt::any(1).to_string(), "1.xxx");
+
+    assert!(Dpt::any(1).accepts(&Dpt::new(1, 11)));
+    assert!(Dpt::new(1, 8).accepts(&Dpt::any(1)));
+    assert!(!Dpt::new(9, 1).accepts(&Dpt::new(9, 4)));
+}
+
+#[test]
+fn rejects_inconsistent_devices() {
+    let device = |name: &str, kind: &str, role: &str, dpt: &str| {
+        format!(
+            "[[devices]]\nname = \"{}\"\nroom = \"hall\"\nkind = \"{}\"\n\
+             datapoints = [{{ role = \"{}\", address = \"1/1/1\", dpt = \"{}\" }}]\n",
+            name, kind, role, dpt
+        )
+    };
+
+    assert!(matches!(
+        Registry::from_toml(&device("lamp", "switch", "step", "1.001")),
+        Err(RegistryError::UnsupportedRole {
+            role: Role::Step,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Registry::from_toml(&device("outdoor", "temperature", "state", "9.004")),
+        Err(RegistryError::UnsupportedDpt { .. })
+    ));
+    assert!(matches!(
+        Registry::from_toml(&device("a/b", "switch", "state", "1.001")),
+        Err(RegistryError::InvalidName(_))
+    ));
+    assert!(matches!(
+        Registry::from_toml(&device("", "switch", "state", "1.001")),
+        Err(RegistryError::InvalidName(_))
+    ));
+
+    let twice = device("lamp", "switch", "state", "1.001").repeat(2);
+    assert_eq!(
+        Registry::from_toml(&twice),
+        Err(RegistryError::DuplicateDevice("hall/lamp".into()))
+    );
+
+    let empty =
+        "[[devices]]\nname = \"lamp\"\nroom = \"hall\"\nkind = \"switch\"\ndatapoints = []\n";
+    assert_eq!(
+        Registry::from_toml(empty),
+        Err(RegistryError::NoDatapoints("hall/lamp".into()))
+    );
+
+    assert!(matches!(
+        Registry::from_toml(&device("lamp", "heater", "state", "1.001")),
+        Err(RegistryError::Parse(_))
+    ));
+    assert!(matches!(
+        Registry::from_toml(&device("lamp", "switch", "state", "1.001").replace("1/1/1", "32/0/0")),
+        Err(RegistryError::Parse(_))
+    ));
+}
+
+#[test]
+fn rejects_devices_without_their_main_value() {
+    let blind = r#"
+[[devices]]
+name = "blind"
+room = "hall"
+kind = "blind"
+datapoints = [
+    { role = "move", address = "2/1/0", dpt = "1.008" },
+    { role = "step", address = "2/1/1", dpt = "1.007" },
+]
+"#;
+    assert_eq!(
+        Registry::from_toml(blind),
+        Err(RegistryError::MissingRole {
+            device: "hall/blind".into(),
+            roles: &[Role::Position, Role::PositionState],
+        })
+    );
+
+    // Position status alone is enough for a blind the gateway only reports
+    let reported = blind.replace(
+        "{ role = \"step\", address = \"2/1/1\", dpt = \"1.007\" },",
+        "{ role = \"position-state\", address = \"2/1/4\", dpt = \"5.001\" },",
+    );
+    assert!(Registry::from_toml(&reported).is_ok());
+
+    let dimmer = "[[devices]]\nname = \"light\"\nroom = \"hall\"\nkind = \"dimmer\"\n\
+                  datapoints = [{ role = \"step\", address = \"1/2/0\", dpt = \"3.007\" }]\n";
+    assert!(matches!(
+        Registry::from_toml(dimmer),
+        Err(RegistryError::MissingRole { .. })
+    ));
+}
+
+#[test]
+fn rejects_addresses_shared_between_devices() {
+    let lamp = SWITCH
+        .replace("\"tv\"", "\"lamp\"")
+        .replace("1/0/6", "1/1/6");
+    assert_eq!(
+        Registry::from_toml(&format!("{}{}", SWITCH, lamp)),
+        Err(RegistryError::SharedAddress {
+            address: address("1/0/7"),
+            first: "living-room/tv".into(),
+            second: "living-room/lamp".into(),
+        })
+    );
+
+    // One device may use an address for several roles
+    let scene = r#"
+[[devices]]
+name = "scene"
+room = "central"
+kind = "scene"
+datapoints = [
+    { role = "state", address = "6/0/0", dpt = "17.001" },
+    { role = "control", address = "6/0/0", dpt = "18.001" },
+]
+"#;
+    assert!(Registry::from_toml(scene).is_ok());
+}
+
+#[test]
+fn every_kind_has_a_state_record() {
+    for kind in DeviceKind::ALL {
+        let roles = kind.roles();
+        assert!(!roles.is_empty(), "{}", kind);
+
+        // Every kind reports its status; only some accept commands
+        let status = roles.iter().any(|(role, _)| !role.is_writable());
+        assert!(status, "{}", kind);
+    }
+}
+
+#[test]
+fn devices_of_one_kind_keep_apart() {
+    let lamp = SWITCH
+        .replace("\"tv\"", "\"lamp\"")
+        .replace("1/0/7", "1/1/7")
+        .replace("1/0/6", "1/1/6");
+    let registry = Registry::from_toml(&format!("{}{}", SWITCH, lamp)).unwrap();
+    let [tv, lamp] = [&registry.devices[0], &registry.devices[1]];
+
+    assert_ne!(tv.state_topic(), lamp.state_topic());
+    assert_ne!(tv.control_topic(), lamp.control_topic());
+
+    // Records find their device by group address, whatever its role
+    let at = |s| registry.device_at(DeviceKind::Switch, address(s));
+    assert_eq!(at("1/0/6"), Some(tv));
+    assert_eq!(at("1/1/7"), Some(lamp));
+    assert_eq!(at("1/2/7"), None);
+    assert_eq!(
+        registry.device_at(DeviceKind::Dimmer, address("1/0/7")),
+        None
+    );
+}
+
+#[test]
+fn repository_registry_is_valid() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../registry.toml");
+    let registry = Registry::load(path).unwrap();
+
+    // Every kind the gateway bridges is listed
+    assert_eq!(registry.kinds().len(), DeviceKind::ALL.len());
+
+    let writable = registry.writable_addresses();
+    assert!(writable.contains(&address("1/0/6")));
+    assert!(writable.contains(&address("2/1/3")));
+    assert!(!writable.contains(&address("1/0/7")));
+    assert!(!writable.contains(&address("9/1/0")));
+}
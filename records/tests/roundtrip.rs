@@ -0,0 +1,283 @@
+//! Round-trip tests for the record codecs
+//!
+//! Run under both JSON backends:
+//! - `cargo test` (default): `serde-json-core` with fixed stack buffers
+//! - `cargo test --features std`: `serde_json`
+//!
+//! Adding `--features postcard` also round-trips every record through postcard.
+//! Worst-case values (longest addresses, texts and floats, fully stamped
+//! metadata) check that the no_std buffers are large enough.
+
+use records::*;
+use serde::{Deserialize, Serialize};
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+/// Metadata with every field at its longest
+fn worst_meta() -> RecordMeta {
+    RecordMeta::new("knx-gateway-with-long-id", u32::MAX, u64::MAX)
+        .unwrap()
+        .with_utc_ms(u64::MAX)
+        .with_source("15.15.255".parse().unwrap())
+}
+
+/// Encode and decode `record` in every enabled encoding
+fn assert_roundtrip<T: RecordCodec + PartialEq + core::fmt::Debug>(record: &T) {
+    let json = record
+        .to_json()
+        .unwrap_or_else(|e| panic!("encoding {:?}: {}", record, e));
+    assert!(json.len() <= T::JSON_BUFFER_SIZE);
+    assert_eq!(&T::from_json(&json).unwrap(), record);
+
+    #[cfg(feature = "postcard")]
+    {
+        let bytes = record.encode(Encoding::Postcard).unwrap();
+        assert_eq!(&T::decode(&bytes, Encoding::Postcard).unwrap(), record);
+    }
+}
+
+fn worst_datetime() -> DateTime {
+    let mut datetime = DateTime::new(
+        Some(Date::new(2155, 12, 31)),
+        Some(TimeOfDay::new(Some(Weekday::Wednesday), 23, 59, 59)),
+    );
+    datetime.flags = DateTimeFlags {
+        fault: true,
+        working_day: Some(false),
+        no_year: true,
+        summer_time: true,
+        external_sync: true,
+        reliable_source: true,
+    };
+    datetime
+}
+
+#[test]
+fn switch() {
+    assert_roundtrip(&SwitchState::new(address("31/7/255"), true).with_meta(worst_meta()));
+    assert_roundtrip(&SwitchState::new(address("1/7"), false));
+    assert_roundtrip(&SwitchControl::new(address("65535"), true));
+}
+
+#[test]
+fn command_rejection() {
+    let rejection = CommandRejection::new(address("31/7/255"), RejectReason::UnknownAddress);
+    assert_roundtrip(&rejection.clone().with_meta(worst_meta()));
+    assert_roundtrip(&rejection);
+}
+
+#[test]
+fn temperature_and_setpoint() {
+    assert_roundtrip(&Temperature::new(address("31/7/255"), -671_088.6).with_meta(worst_meta()));
+    assert_roundtrip(&Temperature::new(address("9/1/0"), 21.5));
+    assert_roundtrip(&SetpointState::new(address("31/7/255"), -0.01).with_meta(worst_meta()));
+    assert_roundtrip(&SetpointControl::new(address("31/7/255"), 670_760.94));
+}
+
+#[test]
+fn dimmer() {
+    assert_roundtrip(&DimmerState::new(address("31/7/255"), 100).with_meta(worst_meta()));
+    assert_roundtrip(&DimmerControl::new(
+        address("31/7/255"),
+        DimmerCommand::Brightness(100),
+    ));
+    assert_roundtrip(&DimmerControl::new(
+        address("31/7/255"),
+        DimmerCommand::Step {
+            increase: false,
+            intervals: 64,
+        },
+    ));
+    assert_roundtrip(&DimmerControl::new(address("1/2/1"), DimmerCommand::Stop));
+}
+
+#[test]
+fn blinds() {
+    assert_roundtrip(
+        &BlindState::new(address("31/7/255"), Some(100), Some(100), true).with_meta(worst_meta()),
+    );
+    assert_roundtrip(&BlindState::new(address("2/1/4"), None, None, false));
+    for command in [
+        BlindCommand::Up,
+        BlindCommand::Down,
+        BlindCommand::Stop,
+        BlindCommand::StepUp,
+        BlindCommand::StepDown,
+        BlindCommand::Position(100),
+        BlindCommand::SlatAngle(100),
+    ] {
+        assert_roundtrip(&BlindControl::new(address("31/7/255"), command));
+    }
+}
+
+#[test]
+fn sensors() {
+    assert_roundtrip(
+        &SensorReading::<Illuminance>::new(address("31/7/255"), 670_760.0).with_meta(worst_meta()),
+    );
+    assert_roundtrip(&SensorReading::<WindSpeed>::new(address("9/1/1"), 12.3));
+    assert_roundtrip(&SensorReading::<Humidity>::new(address("9/1/3"), 45.5));
+    assert_roundtrip(&SensorReading::<Co2>::new(address("9/1/4"), 1234.56));
+}
+
+#[test]
+fn energy() {
+    assert_roundtrip(&EnergyCounter::new(address("31/7/255"), i32::MIN).with_meta(worst_meta()));
+    assert_roundtrip(
+        &PowerReading::new(address("31/7/255"), -1.234_567_8e-38).with_meta(worst_meta()),
+    );
+    assert_roundtrip(&PowerReading::new(address("5/0/1"), f32::MAX));
+    assert_roundtrip(&EnergyTotal::new(address("31/7/255"), i64::MIN, u32::MAX));
+}
+
+#[test]
+fn color() {
+    let mut state = ColorLightState::new(address("31/7/255"), Rgbw::rgb_white(255, 255, 255, 255))
+        .with_meta(worst_meta());
+    state.hsv = Hsv {
+        hue: 359.999_97,
+        saturation: 99.999_99,
+        value: 99.999_99,
+    };
+    assert_roundtrip(&state);
+
+    assert_roundtrip(&ColorLightControl::hex(
+        address("31/7/255"),
+        Rgbw::rgb_white(1, 2, 3, 4),
+    ));
+    assert_roundtrip(&ColorLightControl::new(
+        address("31/7/255"),
+        ColorCommand::Hsv(Hsv {
+            hue: 359.999_97,
+            saturation: 0.000_001_234_567_8,
+            value: 99.999_99,
+        }),
+    ));
+}
+
+#[test]
+fn scene() {
+    assert_roundtrip(&SceneState::new(address("31/7/255"), 64, true).with_meta(worst_meta()));
+    assert_roundtrip(&SceneControl::new(address("31/7/255"), 64, true));
+}
+
+#[test]
+fn clock() {
+    assert_roundtrip(
+        &ClockState::new(address("31/7/255"), worst_datetime()).with_meta(worst_meta()),
+    );
+    assert_roundtrip(&ClockState::new(
+        address("7/0/0"),
+        DateTime::new(None, Some(TimeOfDay::new(None, 0, 0, 0))),
+    ));
+    assert_roundtrip(&ClockControl::new(worst_datetime()));
+}
+
+#[test]
+fn text() {
+    let latin1: KnxText = "ÄÖÜäöüßÿéèêëçñ".parse().unwrap();
+    assert_roundtrip(
+        &TextMessage::new(address("31/7/255"), latin1.clone(), TextEncoding::Latin1)
+            .with_meta(worst_meta()),
+    );
+    assert_roundtrip(&TextMessageControl::new(
+        address("31/7/255"),
+        latin1,
+        TextEncoding::Latin1,
+    ));
+}
+
+#[test]
+fn escaped_text_fits_buffer() {
+    // Quotes and backslashes are escaped in JSON, doubling their length
+    let escaped: KnxText = r#""\"\"\"\"\"\""#.parse().unwrap();
+    let control = TextMessageControl::new(address("31/7/255"), escaped, TextEncoding::Ascii);
+    assert!(control.to_json().is_ok());
+
+    // serde-json-core 0.5 hands strings over without unescaping them
+    #[cfg(feature = "std")]
+    assert_roundtrip(&control);
+}
+
+#[test]
+fn rejects_overlong_text() {
+    let json = br#"{"address":"8/0/0","text":"fifteen chars!!","encoding":"ascii"}"#;
+    assert!(TextMessageControl::from_json(json).is_err());
+}
+
+/// A record with unbounded JSON length, using the default 128-byte buffer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Note {
+    address: GroupAddress,
+    text: String,
+}
+
+records::impl_record_codec!(Note);
+
+/// `{"v":2,"record":{"address":"1/0/7","text":""}}` is 46 bytes
+fn note(text_len: usize) -> Note {
+    Note {
+        address: address("1/0/7"),
+        text: "x".repeat(text_len),
+    }
+}
+
+#[test]
+fn default_buffer_holds_record_and_envelope() {
+    assert_eq!(Note::JSON_BUFFER_SIZE, 128 + codec::ENVELOPE_JSON_LEN);
+
+    let json = note(Note::JSON_BUFFER_SIZE - 46).to_json().unwrap();
+    assert_eq!(json.len(), Note::JSON_BUFFER_SIZE);
+}
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn overflowing_default_buffer_fails() {
+    assert_eq!(
+        note(Note::JSON_BUFFER_SIZE - 45).to_json(),
+        Err(RecordError::BufferTooSmall)
+    );
+    assert_eq!(note(1000).to_json(), Err(RecordError::BufferTooSmall));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_has_no_buffer_limit() {
+    let long = note(1000);
+    assert_eq!(Note::from_json(&long.to_json().unwrap()).unwrap(), long);
+}
+
+/// A record whose serialization always fails
+///
+/// Only used with serializers that report custom errors; `serde-json-core`
+/// cannot.
+#[cfg(any(feature = "std", feature = "postcard"))]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Unserializable;
+
+#[cfg(any(feature = "std", feature = "postcard"))]
+impl Serialize for Unserializable {
+    fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("not serializable"))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "postcard"))]
+records::impl_record_codec!(Unserializable);
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_failure_is_an_encode_error() {
+    assert_eq!(
+        Unserializable.encode(Encoding::Postcard),
+        Err(RecordError::PostcardEncode)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn json_failure_is_an_encode_error() {
+    assert_eq!(Unserializable.to_json(), Err(RecordError::JsonEncode));
+}
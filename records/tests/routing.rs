@@ -0,0 +1,70 @@
+//! Command routing tests
+//!
+//! The gateway's SwitchControl dispatch runs off-target here: one KNX link
+//! per allowed address, plus the error topic for everything else.
+
+use records::*;
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+const TV: GroupAddress = GroupAddress::new(1, 0, 6);
+const LAMP: GroupAddress = GroupAddress::new(1, 1, 6);
+const SWITCHES: AllowList = AllowList::new(&[TV, LAMP]);
+
+/// KNX links a command goes out on, like the gateway's per-address links
+fn links_for(control: &SwitchControl) -> Vec<GroupAddress> {
+    let route = SWITCHES.route(control.address);
+    SWITCHES
+        .addresses()
+        .iter()
+        .copied()
+        .filter(|&link| route == Route::Write(link))
+        .collect()
+}
+
+#[test]
+fn dispatches_to_the_named_address() {
+    assert_eq!(links_for(&SwitchControl::new(TV, true)), [TV]);
+    assert_eq!(links_for(&SwitchControl::new(LAMP, false)), [LAMP]);
+
+    // Other notations of the same group address are the same target
+    assert_eq!(links_for(&SwitchControl::new(address("1/6"), true)), [TV]);
+    assert_eq!(SWITCHES.route(address("2310")), Route::Write(LAMP));
+}
+
+#[test]
+fn rejects_unknown_addresses() {
+    let control = SwitchControl::new(address("1/0/7"), true);
+    let route = SWITCHES.route(control.address);
+
+    assert_eq!(route, Route::Reject(RejectReason::UnknownAddress));
+    assert!(links_for(&control).is_empty());
+    assert_eq!(
+        route.rejection(control.address),
+        Some(CommandRejection::new(
+            control.address,
+            RejectReason::UnknownAddress
+        ))
+    );
+    assert_eq!(SWITCHES.route(TV).rejection(TV), None);
+
+    // An empty allow-list refuses everything
+    assert_eq!(
+        AllowList::new(&[]).route(TV),
+        Route::Reject(RejectReason::UnknownAddress)
+    );
+}
+
+#[test]
+fn rejection_wire_form() {
+    let rejection = CommandRejection::new(address("1/0/9"), RejectReason::UnknownAddress);
+    let json = rejection.to_json().unwrap();
+
+    assert_eq!(
+        json,
+        br#"{"v":2,"record":{"address":"1/0/9","reason":"unknown-address","meta":null}}"#
+    );
+    assert_eq!(CommandRejection::from_json(&json).unwrap(), rejection);
+}
@@ -0,0 +1,176 @@
+//! Schema version tests
+//!
+//! Payloads of the unversioned layout (schema version 1) are copied from
+//! what gateways and towers published before records were wrapped in an
+//! envelope; they must keep decoding after every layout change.
+
+use records::codec::{self, Envelope, SCHEMA_VERSION};
+use records::{
+    ClockState, DimmerCommand, DimmerControl, GroupAddress, Humidity, RecordCodec, RecordError,
+    RecordMeta, SensorReading, SwitchControl, SwitchState, Temperature, TextEncoding, TextMessage,
+    Timestamped,
+};
+use serde::{Deserialize, Serialize};
+
+fn address(s: &str) -> GroupAddress {
+    s.parse().unwrap()
+}
+
+#[test]
+fn writes_current_envelope() {
+    let state = SwitchState::new(address("1/0/7"), true);
+    let json = state.to_json().unwrap();
+
+    assert!(json.starts_with(br#"{"v":2,"record":{"address":"1/0/7","is_on":true"#));
+    assert_eq!(SwitchState::from_json(&json).unwrap(), state);
+}
+
+#[test]
+fn decodes_v1_switch() {
+    let state = SwitchState::from_json(br#"{"address":"1/0/7","is_on":true}"#).unwrap();
+    assert_eq!(state, SwitchState::new(address("1/0/7"), true));
+
+    let control = SwitchControl::from_json(br#"{"address":"1/0/6","is_on":false}"#).unwrap();
+    assert_eq!(control, SwitchControl::new(address("1/0/6"), false));
+}
+
+#[test]
+fn decodes_v1_temperature() {
+    let temperature = Temperature::from_json(br#"{"address":"9/1/0","celsius":21.5}"#).unwrap();
+    assert_eq!(temperature.address, address("9/1/0"));
+    assert_eq!(temperature.celsius, 21.5);
+    assert_eq!(temperature.meta, None);
+}
+
+#[test]
+fn decodes_v1_dimmer_control() {
+    let control =
+        DimmerControl::from_json(br#"{"address":"1/2/1","command":{"brightness":40}}"#).unwrap();
+    assert_eq!(control.command, DimmerCommand::Brightness(40));
+}
+
+#[test]
+fn decodes_v1_sensor_reading() {
+    let reading =
+        SensorReading::<Humidity>::from_json(br#"{"address":"9/1/3","value":45.0,"unit":"%"}"#)
+            .unwrap();
+    assert_eq!(reading.value, 45.0);
+}
+
+#[test]
+fn decodes_v1_clock_and_text() {
+    let clock = ClockState::from_json(
+        br#"{"address":"7/0/2","datetime":{"date":{"year":2026,"month":10,"day":16},"time":null}}"#,
+    )
+    .unwrap();
+    assert_eq!(clock.meta, None);
+
+    let text = TextMessage::from_json(br#"{"address":"8/0/1","text":"Alarm","encoding":"ascii"}"#)
+        .unwrap();
+    assert_eq!(text.encoding, TextEncoding::Ascii);
+}
+
+#[test]
+fn keeps_meta_through_envelope() {
+    let meta = RecordMeta::new("knx-gateway-001", 7, 81_234).unwrap();
+    let state = SwitchState::new(address("1/0/7"), true).with_meta(meta.clone());
+
+    let decoded = SwitchState::from_json(&state.to_json().unwrap()).unwrap();
+    assert_eq!(decoded.meta(), Some(&meta));
+}
+
+#[test]
+fn rejects_newer_version() {
+    let json = br#"{"v":3,"record":{"address":"1/0/7","is_on":true,"brightness":80}}"#;
+    assert_eq!(
+        SwitchState::from_json(json),
+        Err(RecordError::UnsupportedSchemaVersion(3))
+    );
+}
+
+#[test]
+fn rejects_malformed_envelope() {
+    let json = br#"{"v":2,"address":"1/0/7","is_on":true}"#;
+    assert_eq!(
+        SwitchState::from_json(json),
+        Err(RecordError::MalformedJson)
+    );
+}
+
+/// A record whose field was renamed in schema version 2
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DoorState {
+    address: GroupAddress,
+    is_open: bool,
+}
+
+/// Layout of [`DoorState`] in schema version 1
+#[derive(Deserialize)]
+struct DoorStateV1 {
+    address: GroupAddress,
+    open: bool,
+}
+
+impl RecordCodec for DoorState {
+    const JSON_BUFFER_SIZE: usize = 128;
+
+    fn to_json(&self) -> Result<Vec<u8>, RecordError> {
+        codec::encode_record::<Self, 128>(self)
+    }
+
+    fn migrate_json(version: u16, data: &[u8]) -> Result<Self, RecordError> {
+        match version {
+            1 => codec::decode_version::<DoorStateV1>(version, data).map(|old| DoorState {
+                address: old.address,
+                is_open: old.open,
+            }),
+            _ => Err(RecordError::UnsupportedSchemaVersion(version)),
+        }
+    }
+}
+
+#[test]
+fn migrates_renamed_field() {
+    let door = DoorState::from_json(br#"{"address":"3/1/0","open":true}"#).unwrap();
+    assert_eq!(
+        door,
+        DoorState {
+            address: address("3/1/0"),
+            is_open: true
+        }
+    );
+
+    let current = DoorState::from_json(&door.to_json().unwrap()).unwrap();
+    assert_eq!(current, door);
+}
+
+#[test]
+fn envelope_layout() {
+    let envelope = Envelope {
+        v: SCHEMA_VERSION,
+        record: SwitchControl::new(address("1/0/6"), true),
+    };
+    let decoded: Envelope<SwitchControl> =
+        codec::decode(&codec::encode::<_, 128>(&envelope).unwrap()).unwrap();
+    assert_eq!(decoded, envelope);
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_carries_version() {
+    use records::Encoding;
+
+    let state = SwitchState::new(address("1/0/7"), true);
+    let mut bytes = state.encode(Encoding::Postcard).unwrap();
+    assert_eq!(bytes[0], SCHEMA_VERSION as u8);
+    assert_eq!(
+        SwitchState::decode(&bytes, Encoding::Postcard).unwrap(),
+        state
+    );
+
+    bytes[0] = 1;
+    assert_eq!(
+        SwitchState::decode(&bytes, Encoding::Postcard),
+        Err(RecordError::UnsupportedSchemaVersion(1))
+    );
+}
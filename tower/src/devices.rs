@@ -0,0 +1,400 @@
+//! Registry-Driven Records
+//!
+//! Builds the console's KNX records from the device [`Registry`] instead of
+//! hard-coded topics: every kind with at least one device gets its state
+//! record (subscribed from each device's state topic) and, if it has one,
+//! its control record (published to each device's control topic by a
+//! dispatch on the MQTT connector).
+//!
+//! All devices of one kind share these records, so they are told apart by
+//! group address:
+//! - a command is only published to the topic of the device owning its
+//!   `address`
+//! - [`DeviceStates`] keeps the latest state of each device, keyed by
+//!   `room/name`
+
+use crate::freshness::FreshnessTracker;
+use aimdb_core::remote::SecurityPolicy;
+use aimdb_core::{buffer::BufferCfg, AimDb, AimDbBuilder, DbError, DbResult};
+use aimdb_tokio_adapter::{TokioAdapter, TokioRecordRegistrarExt};
+use gateway::Dispatch;
+use records::{
+    BlindControl, BlindState, ClockState, Co2, ColorLightControl, ColorLightState, DeviceKind,
+    DimmerControl, DimmerState, Encoding, EnergyCounter, GroupAddress, Humidity, HvacModeControl,
+    HvacModeState, Illuminance, PowerReading, RecordCodec, Registry, SceneControl, SceneState,
+    SensorReading, SetpointControl, SetpointState, SwitchControl, SwitchState, Temperature,
+    TextMessage, TextMessageControl, Timestamped, WindSpeed,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+use tracing::warn;
+
+/// Latest state of every device of one kind
+///
+/// Keyed by device id (e.g. `living-room/tv`). The state record itself only
+/// holds the newest value of any device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceStates<T> {
+    /// State per device id
+    pub devices: BTreeMap<String, T>,
+}
+
+impl<T> Default for DeviceStates<T> {
+    fn default() -> Self {
+        Self {
+            devices: BTreeMap::new(),
+        }
+    }
+}
+
+/// Configure the state and control records of every kind in the registry
+pub fn configure(
+    builder: &mut AimDbBuilder<TokioAdapter>,
+    registry: &Registry,
+    encoding: Encoding,
+) {
+    for kind in registry.kinds() {
+        match kind {
+            DeviceKind::Switch => {
+                configure_state::<SwitchState>(builder, registry, kind, encoding);
+                configure_control::<SwitchControl>(builder);
+            }
+            DeviceKind::Temperature => {
+                configure_state::<Temperature>(builder, registry, kind, encoding);
+            }
+            DeviceKind::Dimmer => {
+                configure_state::<DimmerState>(builder, registry, kind, encoding);
+                configure_control::<DimmerControl>(builder);
+            }
+            DeviceKind::Blind => {
+                configure_state::<BlindState>(builder, registry, kind, encoding);
+                configure_control::<BlindControl>(builder);
+            }
+            DeviceKind::Illuminance => {
+                configure_state::<SensorReading<Illuminance>>(builder, registry, kind, encoding);
+            }
+            DeviceKind::WindSpeed => {
+                configure_state::<SensorReading<WindSpeed>>(builder, registry, kind, encoding);
+            }
+            DeviceKind::Humidity => {
+                configure_state::<SensorReading<Humidity>>(builder, registry, kind, encoding);
+            }
+            DeviceKind::Co2 => {
+                configure_state::<SensorReading<Co2>>(builder, registry, kind, encoding);
+            }
+            DeviceKind::EnergyCounter => {
+                configure_state::<EnergyCounter>(builder, registry, kind, encoding);
+            }
+            DeviceKind::Power => {
+                configure_state::<PowerReading>(builder, registry, kind, encoding);
+            }
+            DeviceKind::ColorLight => {
+                configure_state::<ColorLightState>(builder, registry, kind, encoding);
+                configure_control::<ColorLightControl>(builder);
+            }
+            DeviceKind::Scene => {
+                configure_state::<SceneState>(builder, registry, kind, encoding);
+                configure_control::<SceneControl>(builder);
+            }
+            DeviceKind::Clock => {
+                configure_state::<ClockState>(builder, registry, kind, encoding);
+            }
+            DeviceKind::Text => {
+                configure_state::<TextMessage>(builder, registry, kind, encoding);
+                configure_control::<TextMessageControl>(builder);
+            }
+            DeviceKind::HvacMode => {
+                configure_state::<HvacModeState>(builder, registry, kind, encoding);
+                configure_control::<HvacModeControl>(builder);
+            }
+            DeviceKind::Setpoint => {
+                configure_state::<SetpointState>(builder, registry, kind, encoding);
+                configure_control::<SetpointControl>(builder);
+            }
+        }
+    }
+}
+
+/// Publishing of the control record of every kind in the registry, for a
+/// [`gateway::Dispatcher`] around the MQTT connector
+pub fn dispatch(registry: &Registry, encoding: Encoding) -> Vec<Dispatch<TokioAdapter>> {
+    let mut dispatches = Vec::new();
+    for kind in registry.kinds() {
+        dispatches.push(match kind {
+            DeviceKind::Switch => {
+                dispatch_control::<SwitchControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::Dimmer => {
+                dispatch_control::<DimmerControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::Blind => {
+                dispatch_control::<BlindControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::ColorLight => {
+                dispatch_control::<ColorLightControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::Scene => {
+                dispatch_control::<SceneControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::Text => {
+                dispatch_control::<TextMessageControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::HvacMode => {
+                dispatch_control::<HvacModeControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::Setpoint => {
+                dispatch_control::<SetpointControl>(registry, kind, encoding, |c| c.address)
+            }
+            DeviceKind::Temperature
+            | DeviceKind::Illuminance
+            | DeviceKind::WindSpeed
+            | DeviceKind::Humidity
+            | DeviceKind::Co2
+            | DeviceKind::EnergyCounter
+            | DeviceKind::Power
+            | DeviceKind::Clock => continue,
+        });
+    }
+    dispatches
+}
+
+/// Allow AimX writes to the control record of every kind in the registry
+pub fn allow_writes(policy: &mut SecurityPolicy, registry: &Registry) {
+    for kind in registry.kinds() {
+        match kind {
+            DeviceKind::Switch => policy.allow_write::<SwitchControl>(),
+            DeviceKind::Dimmer => policy.allow_write::<DimmerControl>(),
+            DeviceKind::Blind => policy.allow_write::<BlindControl>(),
+            DeviceKind::ColorLight => policy.allow_write::<ColorLightControl>(),
+            DeviceKind::Scene => policy.allow_write::<SceneControl>(),
+            DeviceKind::Text => policy.allow_write::<TextMessageControl>(),
+            DeviceKind::HvacMode => policy.allow_write::<HvacModeControl>(),
+            DeviceKind::Setpoint => policy.allow_write::<SetpointControl>(),
+            DeviceKind::Temperature
+            | DeviceKind::Illuminance
+            | DeviceKind::WindSpeed
+            | DeviceKind::Humidity
+            | DeviceKind::Co2
+            | DeviceKind::EnergyCounter
+            | DeviceKind::Power
+            | DeviceKind::Clock => {}
+        }
+    }
+}
+
+/// Track the age and the per-device values of every state record in the registry
+pub fn track(
+    db: &AimDb<TokioAdapter>,
+    tracker: &FreshnessTracker,
+    registry: &Registry,
+) -> DbResult<()> {
+    for kind in registry.kinds() {
+        match kind {
+            DeviceKind::Switch => {
+                track_state::<SwitchState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Temperature => {
+                track_state::<Temperature>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Dimmer => {
+                track_state::<DimmerState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Blind => {
+                track_state::<BlindState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Illuminance => {
+                track_state::<SensorReading<Illuminance>>(db, tracker, registry, kind, |s| {
+                    s.address
+                })?
+            }
+            DeviceKind::WindSpeed => {
+                track_state::<SensorReading<WindSpeed>>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Humidity => {
+                track_state::<SensorReading<Humidity>>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Co2 => {
+                track_state::<SensorReading<Co2>>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::EnergyCounter => {
+                track_state::<EnergyCounter>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Power => {
+                track_state::<PowerReading>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::ColorLight => {
+                track_state::<ColorLightState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Scene => {
+                track_state::<SceneState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Clock => {
+                track_state::<ClockState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Text => {
+                track_state::<TextMessage>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::HvacMode => {
+                track_state::<HvacModeState>(db, tracker, registry, kind, |s| s.address)?
+            }
+            DeviceKind::Setpoint => {
+                track_state::<SetpointState>(db, tracker, registry, kind, |s| s.address)?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Configure a read-only record subscribed from its devices' MQTT topics (published by KNX Gateway)
+///
+/// `encoding` selects the wire format of these links and, through it, the
+/// topics (e.g. `knx/living-room/tv/state/postcard`). The record buffers
+/// every value so that [`DeviceStates`] sees each device's update, and
+/// [`DeviceStates<T>`] is configured alongside it.
+fn configure_state<T>(
+    builder: &mut AimDbBuilder<TokioAdapter>,
+    registry: &Registry,
+    kind: DeviceKind,
+    encoding: Encoding,
+) where
+    T: RecordCodec + Send + Sync + Debug + Clone + 'static,
+{
+    let topics: BTreeSet<String> = registry
+        .devices(kind)
+        .map(|device| device.state_topic())
+        .collect();
+
+    builder.configure::<T>(|reg| {
+        let mut reg = reg
+            .buffer(BufferCfg::SpmcRing { capacity: 50 })
+            .with_serialization();
+        for topic in &topics {
+            reg = reg
+                .link_from(&format!("mqtt://{}", encoding.topic(topic)))
+                .with_config("qos", "1")
+                .with_deserializer(move |data: &[u8]| {
+                    T::decode(data, encoding).map_err(|e| e.to_string())
+                })
+                .finish();
+        }
+    });
+
+    builder.configure::<DeviceStates<T>>(|reg| {
+        reg.buffer(BufferCfg::SingleLatest).with_serialization();
+    });
+}
+
+/// Configure a controllable record written by AimX clients
+///
+/// Its commands are published to the device topics by the dispatch from
+/// [`dispatch`].
+fn configure_control<T>(builder: &mut AimDbBuilder<TokioAdapter>)
+where
+    T: RecordCodec + Send + Sync + Debug + Clone + 'static,
+{
+    builder.configure::<T>(|reg| {
+        reg.buffer(BufferCfg::SpmcRing { capacity: 50 })
+            .with_serialization();
+    });
+}
+
+/// Publish the commands of a control record to its devices' MQTT topics (consumed by KNX Gateway)
+///
+/// `address` picks the topic of each command: it is only published for the
+/// device that uses the commanded group address, and dropped if none does.
+fn dispatch_control<T>(
+    registry: &Registry,
+    kind: DeviceKind,
+    encoding: Encoding,
+    address: fn(&T) -> GroupAddress,
+) -> Dispatch<TokioAdapter>
+where
+    T: RecordCodec + Send + Sync + Debug + Clone + 'static,
+{
+    let mut topics = BTreeMap::new();
+    for device in registry.devices(kind) {
+        if let Some(topic) = device.control_topic() {
+            for writable in device.writable_addresses() {
+                topics.insert(writable, encoding.topic(&topic));
+            }
+        }
+    }
+
+    Dispatch::new(move |control: &T| {
+        let Some(topic) = topics.get(&address(control)) else {
+            warn!(
+                "No {} device is commanded at {}, dropping command",
+                kind,
+                address(control)
+            );
+            return Vec::new();
+        };
+        match control.encode(encoding) {
+            Ok(payload) => vec![(topic.clone(), payload)],
+            Err(e) => {
+                warn!("Failed to encode {} command: {}", kind, e);
+                Vec::new()
+            }
+        }
+    })
+    .with_qos(1)
+}
+
+/// Collect the values of state record `T` into [`DeviceStates<T>`]
+///
+/// Each value is assigned to the device of `kind` using its group address,
+/// which also keys its age in the tracker; values from addresses no device
+/// uses are dropped.
+fn track_state<T>(
+    db: &AimDb<TokioAdapter>,
+    tracker: &FreshnessTracker,
+    registry: &Registry,
+    kind: DeviceKind,
+    address: fn(&T) -> GroupAddress,
+) -> DbResult<()>
+where
+    T: Timestamped + Serialize + DeserializeOwned + Send + Sync + Debug + Clone + 'static,
+{
+    // Like `Registry::device_at`, the first device using an address owns it
+    let mut devices = BTreeMap::new();
+    for device in registry.devices(kind) {
+        for datapoint in &device.datapoints {
+            devices
+                .entry(datapoint.address)
+                .or_insert_with(|| device.id());
+        }
+    }
+    let mut values = db.subscribe::<T>()?;
+    let states = db.clone();
+    let tracker = tracker.clone();
+
+    db.spawn_task(async move {
+        let mut latest = DeviceStates::default();
+        loop {
+            let value = match values.recv().await {
+                Ok(value) => value,
+                Err(DbError::BufferLagged { .. }) => {
+                    warn!("{} device states fell behind, values were skipped", kind);
+                    continue;
+                }
+                Err(_) => break,
+            };
+            let Some(id) = devices.get(&address(&value)) else {
+                warn!(
+                    "No {} device uses {}, dropping its state",
+                    kind,
+                    address(&value)
+                );
+                continue;
+            };
+            tracker.observe(id, value.meta().cloned());
+            latest.devices.insert(id.clone(), value);
+            if let Err(e) = states.produce(latest.clone()).await {
+                warn!("Failed to update {} device states: {}", kind, e);
+            }
+        }
+    })
+}
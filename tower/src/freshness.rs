@@ -0,0 +1,249 @@
+//! Value Freshness
+//!
+//! Tracks how old the latest state of each device is and publishes the ages
+//! as the [`ValueAges`] record, so AimX clients (and the LLM
+//! behind them) can tell a fresh reading from a stale one.
+//!
+//! Ages come from the gateway's [`RecordMeta`] envelope when present:
+//! - `utc_ms` set: compared against the console's clock
+//! - uptime only: compared against the newest uptime seen from the same
+//!   gateway boot, so a retained value published long ago shows its real age
+//!   once any fresher value from that gateway has arrived
+//! - uptime from a boot other than the gateway's latest: unknown, since
+//!   uptimes of different boots cannot be compared
+//!
+//! The latest boot is the highest boot counter seen, not the one heard from
+//! last: retained values of an earlier boot may arrive after current ones.
+//! - no envelope: time since the console received the value
+
+use aimdb_core::{AimDb, DbResult};
+use aimdb_tokio_adapter::TokioAdapter;
+use records::{IndividualAddress, RecordMeta};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// How often [`ValueAges`] is refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Age of the latest state of one device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueAge {
+    /// Seconds since the value was received on the KNX bus, `None` if the
+    /// value was stamped during an earlier boot of its gateway
+    pub age_secs: Option<u64>,
+
+    /// Whether the age is based on gateway metadata rather than on when
+    /// the console received the value
+    pub stamped: bool,
+
+    /// Gateway that received the value
+    pub gateway: Option<String>,
+
+    /// KNX device that sent the value
+    pub source: Option<IndividualAddress>,
+}
+
+/// Ages of the latest state of every device
+///
+/// Keyed by device id (e.g. `living-room/tv`), like the devices'
+/// `DeviceStates`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValueAges {
+    /// Age per device id
+    pub values: BTreeMap<String, ValueAge>,
+}
+
+/// Latest state of a device, as observed by the console
+struct Observation {
+    received: Instant,
+    meta: Option<RecordMeta>,
+}
+
+/// Newest uptime seen from the latest boot of one gateway
+struct GatewayClock {
+    boot: u32,
+    uptime_ms: u64,
+    received: Instant,
+}
+
+#[derive(Default)]
+struct TrackerState {
+    latest: HashMap<String, Observation>,
+    clocks: HashMap<String, GatewayClock>,
+}
+
+/// Shared freshness state, updated by the task tracking each state record
+#[derive(Clone, Default)]
+pub struct FreshnessTracker {
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl FreshnessTracker {
+    /// Record that a new state of device `id` arrived
+    pub fn observe(&self, id: &str, meta: Option<RecordMeta>) {
+        let received = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(meta) = &meta {
+            let newer = state
+                .clocks
+                .get(meta.gateway.as_str())
+                .is_none_or(|clock| (meta.boot, meta.uptime_ms) >= (clock.boot, clock.uptime_ms));
+            if newer {
+                let clock = GatewayClock {
+                    boot: meta.boot,
+                    uptime_ms: meta.uptime_ms,
+                    received,
+                };
+                state.clocks.insert(meta.gateway.to_string(), clock);
+            }
+        }
+
+        state
+            .latest
+            .insert(id.to_string(), Observation { received, meta });
+    }
+
+    /// Ages of all observed devices
+    pub fn ages(&self) -> ValueAges {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let values = state
+            .latest
+            .iter()
+            .map(|(id, observation)| (id.clone(), state.age_of(observation)))
+            .collect();
+
+        ValueAges { values }
+    }
+}
+
+impl TrackerState {
+    fn age_of(&self, observation: &Observation) -> ValueAge {
+        let Some(meta) = &observation.meta else {
+            return ValueAge {
+                age_secs: Some(observation.received.elapsed().as_secs()),
+                stamped: false,
+                gateway: None,
+                source: None,
+            };
+        };
+
+        let age = match meta.utc_ms {
+            Some(utc_ms) => {
+                let now_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |now| now.as_millis() as u64);
+                Some(Duration::from_millis(now_ms.saturating_sub(utc_ms)))
+            }
+            None => match self.clocks.get(meta.gateway.as_str()) {
+                Some(clock) if clock.boot == meta.boot => Some(
+                    clock.received.elapsed()
+                        + Duration::from_millis(clock.uptime_ms.saturating_sub(meta.uptime_ms)),
+                ),
+                // Stamped before the gateway rebooted: its uptime says nothing now
+                _ => None,
+            },
+        };
+
+        ValueAge {
+            age_secs: age.map(|age| age.as_secs()),
+            stamped: true,
+            gateway: Some(meta.gateway.to_string()),
+            source: meta.source,
+        }
+    }
+}
+
+/// Publish [`ValueAges`] every few seconds
+pub fn spawn_publisher(db: &AimDb<TokioAdapter>, tracker: FreshnessTracker) -> DbResult<()> {
+    let ages = db.clone();
+
+    db.spawn_task(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = ages.produce(tracker.ages()).await {
+                warn!("Failed to update value ages: {}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(boot: u32, uptime_ms: u64) -> Option<RecordMeta> {
+        Some(RecordMeta::new("knx-gateway-001", boot, uptime_ms).unwrap())
+    }
+
+    fn age(ages: &ValueAges, id: &str) -> Option<u64> {
+        ages.values[id].age_secs
+    }
+
+    #[test]
+    fn same_boot_ages_against_newest_uptime() {
+        let tracker = FreshnessTracker::default();
+        tracker.observe("living-room/tv", meta(7, 1_000));
+        tracker.observe("living-room/temperature", meta(7, 61_000));
+
+        let ages = tracker.ages();
+        assert_eq!(age(&ages, "living-room/tv"), Some(60));
+        assert_eq!(age(&ages, "living-room/temperature"), Some(0));
+        assert!(ages.values["living-room/tv"].stamped);
+    }
+
+    #[test]
+    fn devices_sharing_a_record_keep_their_own_age() {
+        let tracker = FreshnessTracker::default();
+        tracker.observe("living-room/tv", meta(7, 1_000));
+        tracker.observe("kitchen/lamp", meta(7, 31_000));
+
+        let ages = tracker.ages();
+        assert_eq!(age(&ages, "living-room/tv"), Some(30));
+        assert_eq!(age(&ages, "kitchen/lamp"), Some(0));
+    }
+
+    #[test]
+    fn earlier_boot_is_unknown() {
+        let tracker = FreshnessTracker::default();
+        tracker.observe("living-room/tv", meta(7, 500_000));
+        tracker.observe("living-room/temperature", meta(8, 1_000));
+
+        let ages = tracker.ages();
+        assert_eq!(age(&ages, "living-room/tv"), None);
+        assert_eq!(age(&ages, "living-room/temperature"), Some(0));
+    }
+
+    #[test]
+    fn late_value_from_an_earlier_boot_keeps_the_clock() {
+        let tracker = FreshnessTracker::default();
+        tracker.observe("living-room/temperature", meta(8, 1_000));
+        tracker.observe("living-room/tv", meta(7, 500_000));
+
+        let ages = tracker.ages();
+        assert_eq!(age(&ages, "living-room/tv"), None);
+        assert_eq!(age(&ages, "living-room/temperature"), Some(0));
+
+        tracker.observe("kitchen/lamp", meta(8, 31_000));
+        let ages = tracker.ages();
+        assert_eq!(age(&ages, "living-room/temperature"), Some(30));
+        assert_eq!(age(&ages, "kitchen/lamp"), Some(0));
+    }
+
+    #[test]
+    fn missing_clock_is_unknown() {
+        let state = TrackerState::default();
+        let observation = Observation {
+            received: Instant::now(),
+            meta: meta(7, 1_000),
+        };
+
+        let age = state.age_of(&observation);
+        assert_eq!(age.age_secs, None);
+        assert_eq!(age.gateway.as_deref(), Some("knx-gateway-001"));
+    }
+}
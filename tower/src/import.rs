@@ -0,0 +1,56 @@
+//! ETS Import
+//!
+//! `tower import <ets-export> <registry>` turns what the integrator exports
+//! from ETS (`.knxproj`, or a group address `.xml`/`.csv` export) into the
+//! device registry the console loads, and reports every group address it
+//! could not map to a record.
+
+use records::ets::EtsProject;
+use std::collections::BTreeMap;
+
+const USAGE: &str =
+    "usage: tower import <project.knxproj | addresses.xml | addresses.csv> <registry.toml | registry.json>";
+
+/// Run the `import` subcommand with the arguments after `import`
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let [source, target] = args else {
+        return Err(USAGE.into());
+    };
+
+    let project = EtsProject::load(source)?;
+    let import = project.to_registry()?;
+    import.registry.save(target)?;
+
+    let mut kinds: BTreeMap<_, usize> = BTreeMap::new();
+    for device in &import.registry.devices {
+        *kinds.entry(device.kind).or_default() += 1;
+    }
+    let datapoints: usize = import
+        .registry
+        .devices
+        .iter()
+        .map(|device| device.datapoints.len())
+        .sum();
+
+    println!("📥 Imported {}", source);
+    println!(
+        "📒 Wrote {}: {} devices, {} of {} group addresses",
+        target,
+        import.registry.devices.len(),
+        datapoints,
+        project.group_addresses.len()
+    );
+    for (kind, count) in kinds {
+        println!("   - {}: {}", kind, count);
+    }
+
+    if !import.skipped.is_empty() {
+        println!();
+        println!("⚠️  Not imported ({}):", import.skipped.len());
+        for skipped in &import.skipped {
+            println!("   - {}", skipped);
+        }
+    }
+
+    Ok(())
+}
@@ -24,24 +24,107 @@
 //!
 //! ```bash
 //! cargo run --release
+//! cargo run --release -- import project.knxproj ../registry.toml
 //! ```
 //!
 //! The server will:
-//! 1. Connect to MQTT broker for KNX gateway communication
-//! 2. Enable remote access on `/tmp/knx-mcp.sock`
-//! 3. Register KNX device records (lights, sensors, etc.)
-//! 4. Handle bidirectional communication between LLM and KNX devices
+//! 1. Load the device registry (`DEVICE_REGISTRY`, defaults to `registry.toml`)
+//! 2. Connect to MQTT broker for KNX gateway communication
+//! 3. Enable remote access on `/tmp/knx-mcp.sock`
+//! 4. Register the KNX device records listed in the registry (lights, sensors, etc.)
+//! 5. Handle bidirectional communication between LLM and KNX devices
+
+mod devices;
+mod freshness;
+mod import;
 
 use aimdb_core::remote::{AimxConfig, SecurityPolicy};
-use aimdb_core::{buffer::BufferCfg, AimDbBuilder};
+use aimdb_core::{buffer::BufferCfg, AimDb, AimDbBuilder, DbError, DbResult};
 use aimdb_mqtt_connector::MqttConnector;
 use aimdb_tokio_adapter::{TokioAdapter, TokioRecordRegistrarExt};
-use records::{SwitchControl, SwitchState, Temperature};
+use devices::DeviceStates;
+use freshness::{FreshnessTracker, ValueAges};
+use gateway::Dispatcher;
+use records::{
+    DeviceKind, Encoding, EnergyCounter, EnergyTotal, EnergyTracker, RecordSchemas, Registry,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
+
+/// Derive EnergyTotal from EnergyCounter readings, one tracker per meter
+///
+/// Keeps dashboards continuous across DPT 13 counter wraparound and meter resets.
+/// The totals are published as [`DeviceStates<EnergyTotal>`], keyed by the id
+/// of the meter owning the counter's group address, so every meter's total
+/// stays readable; readings from addresses no meter uses are dropped.
+fn spawn_energy_tracking(db: &AimDb<TokioAdapter>, registry: &Registry) -> DbResult<()> {
+    let mut counters = db.subscribe::<EnergyCounter>()?;
+    let totals = db.clone();
+    // Like `Registry::device_at`, the first meter using an address owns it
+    let mut meters = HashMap::new();
+    for meter in registry.devices(DeviceKind::EnergyCounter) {
+        for datapoint in &meter.datapoints {
+            meters
+                .entry(datapoint.address)
+                .or_insert_with(|| meter.id());
+        }
+    }
+
+    db.spawn_task(async move {
+        let mut trackers: HashMap<String, EnergyTracker> = HashMap::new();
+        let mut latest = DeviceStates::default();
+
+        loop {
+            let counter = match counters.recv().await {
+                Ok(counter) => counter,
+                Err(DbError::BufferLagged { .. }) => {
+                    warn!("Energy tracking fell behind, counter readings were skipped");
+                    continue;
+                }
+                Err(_) => break,
+            };
+            let Some(id) = meters.get(&counter.address) else {
+                warn!(
+                    "No energy meter uses {}, dropping its counter",
+                    counter.address
+                );
+                continue;
+            };
+            let tracker = trackers.entry(id.clone()).or_default();
+            let resets = tracker.resets();
+            let total = tracker.update(&counter);
+
+            if total.resets > resets {
+                warn!(
+                    "⚡ Energy meter {} was reset (counter now {} Wh)",
+                    id, counter.watt_hours
+                );
+            }
+
+            latest.devices.insert(id.clone(), total);
+            if let Err(e) = totals.produce(latest.clone()).await {
+                warn!("Failed to update energy totals: {}", e);
+            }
+        }
+    })
+}
+
+/// Track every state record in the registry, publishing ValueAges and DeviceStates
+fn spawn_state_tracking(db: &AimDb<TokioAdapter>, registry: &Registry) -> DbResult<()> {
+    let tracker = FreshnessTracker::default();
+    devices::track(db, &tracker, registry)?;
+    freshness::spawn_publisher(db, tracker)
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `tower import <ets-export> <registry>`: write the registry and exit
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("import") {
+        return import::run(&args[1..]);
+    }
+
     // Initialize tracing
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -53,6 +136,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("🚀 Starting Home Automation Console");
     info!("📡 Home automation with LLM integration");
 
+    // Load the device registry (devices, group addresses and topics)
+    let registry_path = std::env::var("DEVICE_REGISTRY")
+        .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/../registry.toml").to_string());
+    let registry = Registry::load(&registry_path)?;
+    info!(
+        "📒 Device registry: {} ({} devices)",
+        registry_path,
+        registry.devices.len()
+    );
+
     // Create runtime adapter
     let adapter = Arc::new(TokioAdapter);
 
@@ -64,7 +157,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Configure security: read-write access for controllable devices
     let mut security_policy = SecurityPolicy::read_write();
-    security_policy.allow_write::<SwitchControl>(); // Switch control commands can be sent
+    devices::allow_writes(&mut security_policy, &registry);
 
     let remote_config = AimxConfig::uds_default()
         .socket_path(socket_path)
@@ -73,14 +166,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .subscription_queue_size(100);
 
     info!("📡 Remote access socket: {}", socket_path);
-    info!("🔒 Security policy: ReadWrite (switches controllable)");
+    info!("🔒 Security policy: ReadWrite (control records of registered devices controllable)");
 
     // Initialize MQTT connector for communicating with KNX Gateway
     let mqtt_broker =
         std::env::var("MQTT_BROKER").unwrap_or_else(|_| "mqtt://192.168.1.7:1883".to_string());
     info!("📡 Connecting to MQTT broker: {}", mqtt_broker);
 
-    let mqtt_connector = MqttConnector::new(&mqtt_broker).with_client_id("home-automation-console");
+    // Wire format shared with the KNX Gateway (must match its `postcard` feature)
+    let encoding = match std::env::var("MQTT_ENCODING") {
+        Ok(name) => Encoding::from_name(&name).ok_or_else(|| {
+            format!(
+                "unknown MQTT_ENCODING {:?} (expected json or postcard)",
+                name
+            )
+        })?,
+        Err(_) => Encoding::Json,
+    };
+    info!(
+        "📦 MQTT payload encoding: {} ({})",
+        encoding,
+        encoding.content_type()
+    );
+
+    // Commands go to the topic of the device they address
+    let mqtt_connector = Dispatcher::new(
+        MqttConnector::new(&mqtt_broker).with_client_id("home-automation-console"),
+        devices::dispatch(&registry, encoding),
+    );
 
     // Build database with remote access and MQTT connector
     let mut builder = AimDbBuilder::new()
@@ -91,73 +204,61 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Configure KNX device records (via MQTT communication with KNX Gateway)
     info!("⚙️  Configuring KNX device records...");
 
-    // Switch state (read-only - subscribe from MQTT published by KNX Gateway)
-    builder.configure::<SwitchState>(|reg| {
-        reg.buffer(BufferCfg::SingleLatest)
-            .with_serialization()
-            // Subscribe from MQTT topic (published by KNX Gateway)
-            .link_from(&format!("mqtt://{}", SwitchState::MQTT_TOPIC))
-            .with_config("qos", "1")
-            .with_deserializer(|data: &[u8]| records::switch::json::deserialize_state(data))
-            .finish();
-    });
+    devices::configure(&mut builder, &registry, encoding);
 
-    // Switch control (controllable - publish control commands to MQTT)
-    builder.configure::<SwitchControl>(|reg| {
-        reg.buffer(BufferCfg::SpmcRing { capacity: 50 })
-            .with_serialization()
-            // Publish switch control commands to MQTT (consumed by KNX Gateway)
-            .link_to(&format!("mqtt://{}", SwitchControl::MQTT_TOPIC))
-            .with_config("qos", "1")
-            .with_config("retain", "false")
-            .with_serializer(|control: &SwitchControl| {
-                records::switch::json::serialize_control(control)
-                    .map_err(|_| aimdb_core::connector::SerializeError::InvalidData)
-            })
-            .finish();
+    // Energy totals (read-only - derived locally from EnergyCounter, per meter)
+    let energy = registry.kinds().contains(&DeviceKind::EnergyCounter);
+    if energy {
+        builder.configure::<DeviceStates<EnergyTotal>>(|reg| {
+            reg.buffer(BufferCfg::SingleLatest).with_serialization();
+        });
+    }
+
+    // Record schemas (read-only - JSON Schema of every record, for validating writes)
+    builder.configure::<RecordSchemas>(|reg| {
+        reg.buffer(BufferCfg::SingleLatest).with_serialization();
     });
 
-    // Temperature sensor (read-only - subscribe from MQTT published by KNX Gateway)
-    builder.configure::<Temperature>(|reg| {
-        reg.buffer(BufferCfg::SingleLatest)
-            .with_serialization()
-            // Subscribe from MQTT topic (published by KNX Gateway)
-            .link_from(&format!("mqtt://{}", Temperature::MQTT_TOPIC))
-            .with_config("qos", "1")
-            .with_deserializer(|data: &[u8]| records::temperature::json::deserialize(data))
-            .finish();
+    // Value ages (read-only - derived locally from gateway receive metadata)
+    builder.configure::<ValueAges>(|reg| {
+        reg.buffer(BufferCfg::SingleLatest).with_serialization();
     });
 
-    let _db = builder.build().await?;
+    let db = builder.build().await?;
+    if energy {
+        spawn_energy_tracking(&db, &registry)?;
+    }
+    spawn_state_tracking(&db, &registry)?;
+    db.produce(RecordSchemas::all()).await?;
 
     info!("✅ Database initialized with KNX device records (via MQTT)");
-    info!(
-        "   - SwitchState ← {} (read-only monitoring)",
-        SwitchState::MQTT_TOPIC
-    );
-    info!(
-        "   - SwitchControl → {} (controllable via MCP)",
-        SwitchControl::MQTT_TOPIC
-    );
-    info!(
-        "   - Temperature ← {} (read-only monitoring)",
-        Temperature::MQTT_TOPIC
-    );
-    info!("");
-    info!("📡 MQTT Topics:");
-    info!(
-        "   PUBLISH: {} (switch commands to KNX Gateway)",
-        SwitchControl::MQTT_TOPIC
-    );
-    info!(
-        "   SUBSCRIBE: {} (light state from KNX Gateway)",
-        SwitchState::MQTT_TOPIC
-    );
-    info!(
-        "   SUBSCRIBE: {} (temperature from KNX Gateway)",
-        Temperature::MQTT_TOPIC
-    );
-
+    for device in &registry.devices {
+        match device.control_topic() {
+            Some(control) => info!(
+                "   - {}/{} ({}) ← {}, → {} (controllable via MCP)",
+                device.room,
+                device.name,
+                device.kind,
+                encoding.topic(&device.state_topic()),
+                encoding.topic(&control)
+            ),
+            None => info!(
+                "   - {}/{} ({}) ← {} (read-only monitoring)",
+                device.room,
+                device.name,
+                device.kind,
+                encoding.topic(&device.state_topic())
+            ),
+        }
+    }
+    if energy {
+        info!(
+            "   - DeviceStates<EnergyTotal> (derived, survives counter wraparound and meter resets, keyed by room/name)"
+        );
+    }
+    info!("   - DeviceStates<…> (derived, latest state of each device, keyed by room/name)");
+    info!("   - ValueAges (derived, seconds since each device's state was received, keyed by room/name)");
+    info!("   - RecordSchemas (JSON Schema of every record, keyed by record name)");
     info!("");
     info!("🎯 Console ready!");
     info!("");
@@ -170,14 +271,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("");
     info!("   3. Ask natural language questions:");
     info!("      - 'What is the current temperature?'");
+    info!("      - 'How humid is it and is the CO2 level OK?'");
+    info!("      - 'How much energy have we used today?'");
     info!("      - 'Turn on the living room light'");
+    info!("      - 'Set the living room to 40%'");
+    info!("      - 'Close the shutters halfway'");
+    info!("      - 'Make the LED strip a warm orange'");
+    info!("      - 'Start movie mode (scene 3)'");
+    info!("      - 'Show \"Laundry done\" on the hallway display'");
+    info!("      - 'What is the bedroom set to? Make it 21 degrees'");
     info!("      - 'Show me recent switch events'");
+    info!("      - 'How old is the outdoor temperature reading?'");
     info!("");
     info!("   4. Test manually:");
     info!(
         "      echo '{{\"id\":1,\"method\":\"record.list\"}}' | socat - UNIX-CONNECT:{}",
         socket_path
     );
+    info!(
+        "      echo '{{\"id\":2,\"method\":\"record.get\",\"params\":{{\"record\":\"{}\"}}}}' | socat - UNIX-CONNECT:{}",
+        std::any::type_name::<RecordSchemas>(),
+        socket_path
+    );
     info!("");
     info!("🔍 Monitoring:");
     info!("   - KNX bus activity will be logged");